@@ -61,8 +61,8 @@ fn ordinal_example() {
     }
 
     // `valid_ordinal()` returns true if the index is valid.
-    assert_eq!(MyEnum::valid_ordinal(1), true);
-    assert_eq!(MyEnum::valid_ordinal(3), false);
+    assert!(MyEnum::valid_ordinal(1));
+    assert!(!MyEnum::valid_ordinal(3));
 
     // ************************************************************************** //
     // *** From here down just prints a summary of what this example covered. *** //
@@ -0,0 +1,49 @@
+// A `#![no_std]` library, built as an example so `cargo test` checks that `Embedded` enums don't need std or an
+// allocator. Run `cargo build --no-default-features --example no_std` to check the other enums as well.
+#![no_std]
+#![allow(unused, dead_code)]
+use enum_ext::enum_extend;
+
+// Embedded leaves out everything that needs std, with or without the std feature
+#[enum_extend(IntType = "u8", Embedded, TryFrom, Versioned, Collections)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    Start = 1,
+    Stop = 2,
+    Reboot = 3,
+}
+
+// the bitmask helpers
+#[enum_extend(Embedded)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Permission {
+    Read = 1,
+    Write = 2,
+    Execute = 4,
+}
+
+#[enum_extend(Embedded)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Led {
+    Off,
+    Blinking,
+    On,
+}
+
+// without the std feature, enums don't need Embedded
+#[cfg(not(feature = "std"))]
+#[enum_extend(IntType = "u16", TryFrom, Versioned, Collections)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Open = 1,
+    InQA = 2,
+    Done = 3,
+}
+
+pub fn decode(bytes: &[u8]) -> Option<Command> {
+    Command::decode_versioned(bytes).ok()
+}
+
+pub fn next_led(led: Led) -> Led {
+    Led::from_ordinal((led.ordinal() + 1) % Led::count() as u8).unwrap_or(Led::Off)
+}
@@ -1,10 +1,10 @@
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens};
-use std::collections::HashMap;
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream, Result as ParseResult};
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{Attribute, Expr, LitStr, Token, Variant, Visibility};
+use syn::{Attribute, Expr, Generics, LitStr, Token, Variant, Visibility};
 
 /// Returns true if the given string represents a supported valid integer type ("i8" through "usize")
 pub(crate) fn valid_int_type(int_type: &str) -> bool {
@@ -24,6 +24,11 @@ pub(crate) fn valid_int_type(int_type: &str) -> bool {
     )
 }
 
+/// Splits an `IntType` value such as `"u8, u32, i64"` into its individual types.
+pub(crate) fn split_int_types(int_types: &str) -> Vec<&str> {
+    int_types.split(',').map(|x| x.trim()).collect()
+}
+
 #[derive(Debug)]
 pub(crate) enum EnumMacroError {
     ParseError(String),
@@ -41,17 +46,153 @@ impl std::fmt::Display for EnumMacroError {
 
 impl std::error::Error for EnumMacroError {}
 
+#[derive(Default, Clone)]
 pub(crate) struct EnumDefArgs {
     pub int_type: Option<LitStr>,
     pub other_type: Option<LitStr>,
+    /// `Atomic`: generate an `Atomic<EnumName>` companion type.
+    pub atomic: bool,
+    /// `NoAutoDerives`: never add derives (such as `Clone`) to the enum.
+    pub no_auto_derives: bool,
+    /// `AutoCopy`: derive `Copy` and `Clone` if they're missing.
+    pub auto_copy: bool,
+    /// `DeriveExtra = "Hash, Eq"`: additional derives for the enum.
+    pub derive_extra: Option<LitStr>,
+    /// `OrdinalType = "u8"`: the integer type of the ordinals, `usize` by default.
+    pub ordinal_type: Option<LitStr>,
+    /// `AsciiCase`: only change the case of ASCII letters in the case conversions and the case-insensitive parsers.
+    pub ascii_case: bool,
+    /// `IntegrationCfg = "feature = \"serde\""`: the cfg predicate of the using crate that the generated
+    /// integration impls (metrics, ts, config, ...) are gated behind.
+    pub integration_cfg: Option<syn::Meta>,
+    /// `CustomCase = "::"`: the separator of `custom_case()`, which joins the lowercased words of the variant name.
+    pub custom_case: Option<LitStr>,
+    /// `RequireSorted = "name"` or `"value"`: fail to compile unless the variants are declared in alphabetical or
+    /// ascending discriminant order.
+    pub require_sorted: Option<LitStr>,
+    /// `Collections`: generate the `<EnumName>Map<T>` and `<EnumName>Set` companion types.
+    pub collections: bool,
+    /// `OtelKey = "workflow.state"`: the OpenTelemetry attribute key of `otel_key_value()`, the snake_case enum name
+    /// by default.
+    pub otel_key: Option<LitStr>,
+    /// `Web`: implement `Display`, `FromStr` with a `<EnumName>ParseError`, and with the `serde` feature, serialize
+    /// the variants as their names, so the enum works as a path or query parameter.
+    pub web: bool,
+    /// `Embedded`: the smallest `IntType` and `OrdinalType` unless they're set, no allocating helpers, and with the
+    /// `serde` feature, serialize the variants as their discriminants.
+    pub embedded: bool,
+    /// `TryFrom`: replace the panicking `From<IntType>` with a `TryFrom<IntType>` whose error is `<EnumName>TryFromError`.
+    pub try_from: bool,
+    /// `IntoString`: implement `From<EnumName>` for `String` and `Cow<'static, str>`.
+    pub into_string: bool,
+    /// `FlatBuffers = "schema::Color"`: the flatc-generated enum to convert to and from, matching variants by name.
+    pub flatbuffers: Option<syn::Path>,
+    /// `Capnp = "schema_capnp::Color"`: the capnpc-generated enum to convert to and from, matching variants by name.
+    pub capnp: Option<syn::Path>,
+    /// `SerdeMode = "strict"` or `"lenient"`: whether the generated `Deserialize` impls reject unknown values, or fall
+    /// back to the `#[ext(other)]` variant or the `Default` one. Strict by default.
+    pub serde_mode: Option<LitStr>,
+    /// `Serde = "int"` or a case such as `"snake_case"`: generate serde impls that store the variants as their `IntType`
+    /// discriminants, or as their names in one of [`NAME_CASES`].
+    pub serde: Option<LitStr>,
+    /// `Display = "pascal_spaced"`: generate `Display` writing `variant_name()` or one of the [`NAME_CASES`].
+    pub display: Option<LitStr>,
+    /// `Acronyms = "HTTP,QA,ID"`: words the case conversions keep whole when splitting the variant names.
+    pub acronyms: Option<LitStr>,
+    /// `ProtoPrefix = "STATUS_"`: the prefix of `proto_name()`, the SCREAMING_SNAKE_CASE enum name and `_` by default.
+    pub proto_prefix: Option<LitStr>,
+    /// `Ffi`: emit the `<ENUM_NAME>_NAMES` and `<ENUM_NAME>_VALUES` statics next to the enum for FFI callers.
+    pub ffi: bool,
+    /// `CompareSchema`: generate `compare_schema()` and the `<EnumName>SchemaDiff` type it returns.
+    pub compare_schema: bool,
+    /// `Versioned`: generate `SCHEMA_VERSION`, `encode_versioned()` and `decode_versioned()` with its `<EnumName>DecodeError`.
+    pub versioned: bool,
+    /// `Metrics`: generate `metric_label()` and `From<EnumName>` for `metrics::SharedString`.
+    pub metrics: bool,
+    /// `Otel`: generate `otel_key_value()`, `otel_value()` and `From<EnumName>` for `opentelemetry::Value`.
+    pub otel: bool,
+    /// `TypeScript`: generate `ts_declaration()` and implement `ts_rs::TS`.
+    pub ts: bool,
+    /// `Avro`: generate `avro_schema()` and implement `apache_avro::AvroSchema`.
+    pub avro: bool,
+    /// `Arrow`: generate the helpers for dictionary-encoded Arrow columns.
+    pub arrow: bool,
+    /// `Deku`: implement `deku::DekuReader` and `deku::DekuWriter` using `bits_required()` bits.
+    pub deku: bool,
+    /// `Speedy`: implement `speedy::Readable` and `speedy::Writable`, encoding the discriminant.
+    pub speedy: bool,
+    /// `Jni`: generate the conversions to and from the Java enum.
+    pub jni: bool,
+    /// `Godot`: implement the gdext traits for an int property with an enum dropdown.
+    pub godot: bool,
+    /// `Egui`: generate `ui_select()`, an egui ComboBox over the variants.
+    pub egui: bool,
+    /// `Ratatui`: generate `list_items()` and the `<EnumName>Selector` list state.
+    pub ratatui: bool,
+    /// `Fuzzy`: generate `find_closest()` and `search()`.
+    pub fuzzy: bool,
+    /// `Ansi`: generate `ansi_colored_name()`.
+    pub ansi: bool,
+    /// `Config`: generate `from_config_value()` and a `serde::Deserialize` accepting names or numbers.
+    pub config: bool,
+    /// `Registry`: register the enum in the `EnumExtInfo` registry declared by `enum_ext_registry!()`.
+    pub registry: bool,
+    /// `Bevy`: derive `bevy_reflect::Reflect` on the enum.
+    pub bevy: bool,
+    /// `Heapless`: return a `heapless::Vec` from `parse_list()` and leave out the allocating helpers.
+    pub heapless: bool,
     // other fields for additional configurations
 }
 
-impl Default for EnumDefArgs {
-    fn default() -> Self {
+impl EnumDefArgs {
+    /// Fills the options that aren't set with the ones from `defaults`, such as the `config!(...)` block of
+    /// `enum_ext!`. Flags are set if they're set in either.
+    pub fn or(self, defaults: EnumDefArgs) -> Self {
         EnumDefArgs {
-            int_type: None,
-            other_type: None,
+            int_type: self.int_type.or(defaults.int_type),
+            other_type: self.other_type.or(defaults.other_type),
+            atomic: self.atomic || defaults.atomic,
+            no_auto_derives: self.no_auto_derives || defaults.no_auto_derives,
+            auto_copy: self.auto_copy || defaults.auto_copy,
+            derive_extra: self.derive_extra.or(defaults.derive_extra),
+            ordinal_type: self.ordinal_type.or(defaults.ordinal_type),
+            ascii_case: self.ascii_case || defaults.ascii_case,
+            integration_cfg: self.integration_cfg.or(defaults.integration_cfg),
+            custom_case: self.custom_case.or(defaults.custom_case),
+            require_sorted: self.require_sorted.or(defaults.require_sorted),
+            collections: self.collections || defaults.collections,
+            otel_key: self.otel_key.or(defaults.otel_key),
+            web: self.web || defaults.web,
+            embedded: self.embedded || defaults.embedded,
+            try_from: self.try_from || defaults.try_from,
+            into_string: self.into_string || defaults.into_string,
+            flatbuffers: self.flatbuffers.or(defaults.flatbuffers),
+            capnp: self.capnp.or(defaults.capnp),
+            serde_mode: self.serde_mode.or(defaults.serde_mode),
+            serde: self.serde.or(defaults.serde),
+            display: self.display.or(defaults.display),
+            acronyms: self.acronyms.or(defaults.acronyms),
+            proto_prefix: self.proto_prefix.or(defaults.proto_prefix),
+            metrics: self.metrics || defaults.metrics,
+            otel: self.otel || defaults.otel,
+            ts: self.ts || defaults.ts,
+            avro: self.avro || defaults.avro,
+            arrow: self.arrow || defaults.arrow,
+            deku: self.deku || defaults.deku,
+            speedy: self.speedy || defaults.speedy,
+            jni: self.jni || defaults.jni,
+            godot: self.godot || defaults.godot,
+            egui: self.egui || defaults.egui,
+            ratatui: self.ratatui || defaults.ratatui,
+            fuzzy: self.fuzzy || defaults.fuzzy,
+            ansi: self.ansi || defaults.ansi,
+            config: self.config || defaults.config,
+            registry: self.registry || defaults.registry,
+            bevy: self.bevy || defaults.bevy,
+            heapless: self.heapless || defaults.heapless,
+            versioned: self.versioned || defaults.versioned,
+            compare_schema: self.compare_schema || defaults.compare_schema,
+            ffi: self.ffi || defaults.ffi,
         }
     }
 }
@@ -60,26 +201,270 @@ impl Parse for EnumDefArgs {
     fn parse(input: ParseStream) -> ParseResult<Self> {
         let mut int_type = None;
         let mut other_type = None;
+        let mut atomic = false;
+        let mut no_auto_derives = false;
+        let mut auto_copy = false;
+        let mut derive_extra = None;
+        let mut ordinal_type = None;
+        let mut ascii_case = false;
+        let mut integration_cfg = None;
+        let mut custom_case = None;
+        let mut require_sorted = None;
+        let mut collections = false;
+        let mut otel_key = None;
+        let mut web = false;
+        let mut embedded = false;
+        let mut try_from = false;
+        let mut into_string = false;
+        let mut flatbuffers = None;
+        let mut capnp = None;
+        let mut serde_mode = None;
+        let mut serde = None;
+        let mut display = None;
+        let mut acronyms = None;
+        let mut proto_prefix = None;
+        let mut metrics = false;
+        let mut otel = false;
+        let mut ts = false;
+        let mut avro = false;
+        let mut arrow = false;
+        let mut deku = false;
+        let mut speedy = false;
+        let mut jni = false;
+        let mut godot = false;
+        let mut egui = false;
+        let mut ratatui = false;
+        let mut fuzzy = false;
+        let mut ansi = false;
+        let mut config = false;
+        let mut registry = false;
+        let mut bevy = false;
+        let mut heapless = false;
+        let mut versioned = false;
+        let mut compare_schema = false;
+        let mut ffi = false;
         // ... handle other fields similarly
 
         while !input.is_empty() {
             let ident: Ident = input.parse()?;
-            let _: Token![=] = input.parse()?;
-            if ident == "IntType" {
+            if ident == "Atomic" {
+                // flags don't take a value
+                atomic = true;
+            } else if ident == "NoAutoDerives" {
+                no_auto_derives = true;
+            } else if ident == "AutoCopy" {
+                auto_copy = true;
+            } else if ident == "AsciiCase" {
+                ascii_case = true;
+            } else if ident == "Collections" {
+                collections = true;
+            } else if ident == "Web" || ident == "IntoString" || ident == "CompareSchema" {
+                // all of them need String
+                if !cfg!(feature = "std") {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!("{} needs the std feature of enum_ext", ident),
+                    ));
+                }
+                if ident == "Web" {
+                    web = true;
+                } else if ident == "IntoString" {
+                    into_string = true;
+                } else {
+                    compare_schema = true;
+                }
+            } else if ident == "Embedded" {
+                embedded = true;
+            } else if ident == "TryFrom" {
+                try_from = true;
+            } else if ident == "Ffi" {
+                ffi = true;
+            } else if ident == "Versioned" {
+                versioned = true;
+            } else if let Some((feature, enabled, flag)) = match ident.to_string().as_str() {
+                "Metrics" => Some(("metrics", cfg!(feature = "metrics"), &mut metrics)),
+                "Otel" => Some(("otel", cfg!(feature = "otel"), &mut otel)),
+                "TypeScript" => Some(("ts", cfg!(feature = "ts"), &mut ts)),
+                "Avro" => Some(("avro", cfg!(feature = "avro"), &mut avro)),
+                "Arrow" => Some(("arrow", cfg!(feature = "arrow"), &mut arrow)),
+                "Deku" => Some(("deku", cfg!(feature = "deku"), &mut deku)),
+                "Speedy" => Some(("speedy", cfg!(feature = "speedy"), &mut speedy)),
+                "Jni" => Some(("jni", cfg!(feature = "jni"), &mut jni)),
+                "Godot" => Some(("godot", cfg!(feature = "godot"), &mut godot)),
+                "Egui" => Some(("egui", cfg!(feature = "egui"), &mut egui)),
+                "Ratatui" => Some(("ratatui", cfg!(feature = "ratatui"), &mut ratatui)),
+                "Fuzzy" => Some(("fuzzy", cfg!(feature = "fuzzy"), &mut fuzzy)),
+                "Ansi" => Some(("ansi", cfg!(feature = "ansi"), &mut ansi)),
+                "Config" => Some(("config", cfg!(feature = "config"), &mut config)),
+                "Registry" => Some(("registry", cfg!(feature = "registry"), &mut registry)),
+                "Bevy" => Some(("bevy", cfg!(feature = "bevy"), &mut bevy)),
+                "Heapless" => Some(("heapless", cfg!(feature = "heapless"), &mut heapless)),
+                _ => None,
+            } {
+                // the feature only lets the option be used, so enums that don't ask for an integration are left alone
+                if !enabled {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!("{} needs the {} feature of enum_ext", ident, feature),
+                    ));
+                }
+                *flag = true;
+            } else if ident == "IntType" {
+                let _: Token![=] = input.parse()?;
                 let int_type_v: LitStr = input.parse()?;
 
-                if !valid_int_type(&int_type_v.value()) {
-                    return Err(syn::Error::new(int_type_v.span(), format!("Invalid IntType: {}. Supported types are i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize", int_type_v.value())));
+                for int_type_s in split_int_types(&int_type_v.value()) {
+                    if int_type_s != "auto" && !valid_int_type(int_type_s) {
+                        return Err(syn::Error::new(int_type_v.span(), format!("Invalid IntType: {}. Supported types are auto, i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize", int_type_s)));
+                    }
                 }
 
                 int_type = Some(int_type_v);
+            } else if ident == "OrdinalType" {
+                let _: Token![=] = input.parse()?;
+                let ordinal_type_v: LitStr = input.parse()?;
+                if !valid_int_type(&ordinal_type_v.value()) {
+                    return Err(syn::Error::new(ordinal_type_v.span(), format!("Invalid OrdinalType: {}. Supported types are i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize", ordinal_type_v.value())));
+                }
+                ordinal_type = Some(ordinal_type_v);
+            } else if ident == "IntegrationCfg" {
+                let _: Token![=] = input.parse()?;
+                let integration_cfg_v: LitStr = input.parse()?;
+                integration_cfg = Some(integration_cfg_v.parse::<syn::Meta>()?);
+            } else if ident == "CustomCase" {
+                let _: Token![=] = input.parse()?;
+                let custom_case_v: LitStr = input.parse()?;
+                if custom_case_v.value().is_empty() {
+                    return Err(syn::Error::new(
+                        custom_case_v.span(),
+                        "CustomCase needs a non-empty separator, flat_case() covers joining without one",
+                    ));
+                }
+                custom_case = Some(custom_case_v);
+            } else if ident == "RequireSorted" {
+                let _: Token![=] = input.parse()?;
+                let require_sorted_v: LitStr = input.parse()?;
+                if !["name", "value"].contains(&require_sorted_v.value().as_str()) {
+                    return Err(syn::Error::new(
+                        require_sorted_v.span(),
+                        format!(
+                            "Invalid RequireSorted: {}. Supported values are name and value",
+                            require_sorted_v.value()
+                        ),
+                    ));
+                }
+                require_sorted = Some(require_sorted_v);
+            } else if ident == "OtelKey" {
+                let _: Token![=] = input.parse()?;
+                let otel_key_v: LitStr = input.parse()?;
+                if otel_key_v.value().is_empty() {
+                    return Err(syn::Error::new(otel_key_v.span(), "OtelKey can't be empty"));
+                }
+                otel_key = Some(otel_key_v);
+            } else if ident == "FlatBuffers" || ident == "Capnp" {
+                let _: Token![=] = input.parse()?;
+                let path_v: LitStr = input.parse()?;
+                let (feature, enabled) = if ident == "FlatBuffers" {
+                    ("flatbuffers", cfg!(feature = "flatbuffers"))
+                } else {
+                    ("capnp", cfg!(feature = "capnp"))
+                };
+                // silently skipping the conversions would only fail later, where they're used
+                if !enabled {
+                    return Err(syn::Error::new(
+                        path_v.span(),
+                        format!("{} needs the {} feature of enum_ext", ident, feature),
+                    ));
+                }
+                let path = Some(path_v.parse::<syn::Path>()?);
+                if ident == "FlatBuffers" {
+                    flatbuffers = path;
+                } else {
+                    capnp = path;
+                }
+            } else if ident == "ProtoPrefix" {
+                let _: Token![=] = input.parse()?;
+                proto_prefix = Some(input.parse()?);
+            } else if ident == "SerdeMode" {
+                let _: Token![=] = input.parse()?;
+                let serde_mode_v: LitStr = input.parse()?;
+                if !["strict", "lenient"].contains(&serde_mode_v.value().as_str()) {
+                    return Err(syn::Error::new(
+                        serde_mode_v.span(),
+                        format!(
+                            "Invalid SerdeMode: {}. Supported values are strict and lenient",
+                            serde_mode_v.value()
+                        ),
+                    ));
+                }
+                serde_mode = Some(serde_mode_v);
+            } else if ident == "Serde" {
+                let _: Token![=] = input.parse()?;
+                let serde_v: LitStr = input.parse()?;
+                if serde_v.value() != "int" && !NAME_CASES.contains(&serde_v.value().as_str()) {
+                    return Err(syn::Error::new(
+                        serde_v.span(),
+                        format!(
+                            "Invalid Serde: {}. Supported values are int, {}",
+                            serde_v.value(),
+                            NAME_CASES.join(", ")
+                        ),
+                    ));
+                }
+                if !cfg!(feature = "serde") {
+                    return Err(syn::Error::new(
+                        serde_v.span(),
+                        "Serde needs the serde feature of enum_ext",
+                    ));
+                }
+                serde = Some(serde_v);
+            } else if ident == "Display" {
+                let _: Token![=] = input.parse()?;
+                let display_v: LitStr = input.parse()?;
+                if display_v.value() != "variant_name"
+                    && !NAME_CASES.contains(&display_v.value().as_str())
+                {
+                    return Err(syn::Error::new(
+                        display_v.span(),
+                        format!(
+                            "Invalid Display: {}. Supported values are variant_name, {}",
+                            display_v.value(),
+                            NAME_CASES.join(", ")
+                        ),
+                    ));
+                }
+                display = Some(display_v);
+            } else if ident == "Acronyms" {
+                let _: Token![=] = input.parse()?;
+                let acronyms_v: LitStr = input.parse()?;
+                let valid = acronyms_v.value().split(',').all(|acronym| {
+                    let acronym = acronym.trim();
+                    acronym.chars().next().is_some_and(char::is_uppercase)
+                        && acronym.chars().all(char::is_alphanumeric)
+                });
+                if !valid {
+                    return Err(syn::Error::new(
+                        acronyms_v.span(),
+                        "Acronyms must be a comma separated list of words starting with a capital, e.g. \"HTTP,QA,ID\"",
+                    ));
+                }
+                acronyms = Some(acronyms_v);
+            } else if ident == "DeriveExtra" {
+                let _: Token![=] = input.parse()?;
+                let derive_extra_v: LitStr = input.parse()?;
+                derive_extra_v.parse_with(Punctuated::<syn::Path, Comma>::parse_terminated)?;
+                derive_extra = Some(derive_extra_v);
             } else if ident == "OtherType" {
+                let _: Token![=] = input.parse()?;
                 other_type = Some(input.parse()?);
                 // ... handle other fields similarly
             } else {
                 return Err(syn::Error::new(
                     ident.span(),
-                    format!("expected IntType, found {}", ident.to_string()),
+                    format!(
+                        "expected IntType, OrdinalType, DeriveExtra, IntegrationCfg, CustomCase, RequireSorted, OtelKey, FlatBuffers, Capnp, Serde, SerdeMode, Display, Acronyms, ProtoPrefix, Atomic, NoAutoDerives, AutoCopy, AsciiCase, Collections, Web, Embedded, TryFrom, IntoString, Ffi, CompareSchema, Versioned, Metrics, Otel, TypeScript, Avro, Arrow, Deku, Speedy, Jni, Godot, Egui, Ratatui, Fuzzy, Ansi, Config, Registry, Bevy or Heapless, found {}",
+                        ident
+                    ),
                 ));
             }
 
@@ -92,11 +477,213 @@ impl Parse for EnumDefArgs {
         Ok(EnumDefArgs {
             int_type,
             other_type,
+            atomic,
+            no_auto_derives,
+            auto_copy,
+            derive_extra,
+            ordinal_type,
+            ascii_case,
+            integration_cfg,
+            custom_case,
+            require_sorted,
+            collections,
+            otel_key,
+            web,
+            embedded,
+            try_from,
+            into_string,
+            flatbuffers,
+            capnp,
+            serde_mode,
+            serde,
+            display,
+            acronyms,
+            proto_prefix,
+            metrics,
+            otel,
+            ts,
+            avro,
+            arrow,
+            deku,
+            speedy,
+            jni,
+            godot,
+            egui,
+            ratatui,
+            fuzzy,
+            ansi,
+            config,
+            registry,
+            bevy,
+            heapless,
+            versioned,
+            compare_schema,
+            ffi,
             // ... set other fields
         })
     }
 }
 
+/// The value of `#[ext(sort_key = ...)]`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum SortKey {
+    Int(i128),
+    Str(String),
+}
+
+/// Arguments from the `#[ext(...)]` attributes of a single variant.
+#[derive(Default)]
+pub(crate) struct VariantArgs {
+    /// `#[ext(code = 'A')]`: a single character code for the variant.
+    pub code: Option<syn::LitChar>,
+    /// `#[ext(order = 3)]`: the position of the variant in `list()`, `iter()` and the ordinals.
+    pub order: Option<syn::LitInt>,
+    /// `#[ext(range = 200..300)]`: the values `from_range_value()` maps to the variant.
+    pub range: Option<syn::ExprRange>,
+    /// `#[ext(sort_key = 10)]` or `#[ext(sort_key = "b")]`: the position of the variant in `sorted_by_key()`.
+    pub sort_key: Option<SortKey>,
+    /// `#[ext(alias = "...")]`: other names `parse()` accepts for the variant. Can be repeated.
+    pub aliases: Vec<LitStr>,
+    /// `#[ext(color = "yellow")]`: the terminal color of the variant, one of [`ANSI_COLORS`].
+    pub color: Option<LitStr>,
+    /// `#[ext(other)]`: the variant unknown values deserialize to with `SerdeMode = "lenient"`.
+    pub other: bool,
+    /// `#[ext(legacy_value = 5)]`: retired discriminants `from_<IntType>()` still maps to the variant. Can be repeated.
+    pub legacy_values: Vec<i128>,
+    // fields for per-variant configurations
+}
+
+/// The name conversions `Serde = "..."` and `Display = "..."` accept. Each one is a generated `<case>()` and
+/// `from_<case>()` pair.
+pub(crate) const NAME_CASES: [&str; 8] = [
+    "pascal_spaced",
+    "snake_case",
+    "kebab_case",
+    "sentence_case",
+    "title_case",
+    "dot_case",
+    "flat_case",
+    "upper_flat",
+];
+
+/// The color names `#[ext(color = "...")]` accepts, with their ANSI SGR foreground codes.
+pub(crate) const ANSI_COLORS: [(&str, u8); 16] = [
+    ("black", 30),
+    ("red", 31),
+    ("green", 32),
+    ("yellow", 33),
+    ("blue", 34),
+    ("magenta", 35),
+    ("cyan", 36),
+    ("white", 37),
+    ("bright_black", 90),
+    ("bright_red", 91),
+    ("bright_green", 92),
+    ("bright_yellow", 93),
+    ("bright_blue", 94),
+    ("bright_magenta", 95),
+    ("bright_cyan", 96),
+    ("bright_white", 97),
+];
+
+impl VariantArgs {
+    /// Parses the contents of one `#[ext(...)]` attribute into self.
+    ///
+    /// A variant can have several `#[ext(...)]` attributes, so each one is merged into the same `VariantArgs`.
+    fn parse_into(&mut self, input: ParseStream) -> ParseResult<()> {
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident == "other" {
+                // a flag, without a value
+                self.other = true;
+            } else if ident == "code" {
+                let _: Token![=] = input.parse()?;
+                self.code = Some(input.parse()?);
+            } else if ident == "order" {
+                let _: Token![=] = input.parse()?;
+                let order: syn::LitInt = input.parse()?;
+                order.base10_parse::<i64>()?;
+                self.order = Some(order);
+            } else if ident == "sort_key" {
+                let _: Token![=] = input.parse()?;
+                let expr: Expr = input.parse()?;
+                self.sort_key = Some(match &expr {
+                    Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit_str),
+                        ..
+                    }) => SortKey::Str(lit_str.value()),
+                    _ => SortKey::Int(eval_int_expr(&expr, &|_| None).ok_or_else(|| {
+                        syn::Error::new_spanned(
+                            &expr,
+                            "sort_key must be an integer or string literal",
+                        )
+                    })?),
+                });
+            } else if ident == "alias" {
+                let _: Token![=] = input.parse()?;
+                let alias: LitStr = input.parse()?;
+                if alias.value().trim().is_empty() {
+                    return Err(syn::Error::new(alias.span(), "alias can't be empty"));
+                }
+                self.aliases.push(alias);
+            } else if ident == "legacy_value" {
+                let _: Token![=] = input.parse()?;
+                let expr: Expr = input.parse()?;
+                self.legacy_values
+                    .push(eval_int_expr(&expr, &|_| None).ok_or_else(|| {
+                        syn::Error::new_spanned(&expr, "legacy_value must be an integer literal")
+                    })?);
+            } else if ident == "range" {
+                let _: Token![=] = input.parse()?;
+                self.range = Some(input.parse()?);
+            } else if ident == "color" {
+                let _: Token![=] = input.parse()?;
+                let color: LitStr = input.parse()?;
+                if !ANSI_COLORS.iter().any(|(name, _)| *name == color.value()) {
+                    let names: Vec<&str> = ANSI_COLORS.iter().map(|(name, _)| *name).collect();
+                    return Err(syn::Error::new(
+                        color.span(),
+                        format!("unknown color, expected one of {}", names.join(", ")),
+                    ));
+                }
+                self.color = Some(color);
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("unknown ext attribute: {}", ident),
+                ));
+            }
+
+            if !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Splits the attributes of a variant into its `#[ext(...)]` arguments and everything else.
+///
+/// The `#[ext(...)]` attributes only mean something to enum_ext, so they are removed before the variant is re-emitted.
+/// All other attributes (docs, `#[default]`, serde, cfg, etc.) are returned untouched and in their original order.
+pub(crate) fn process_variant_attributes(
+    variant: &Variant,
+) -> Result<(VariantArgs, Vec<Attribute>), EnumMacroError> {
+    let mut args = VariantArgs::default();
+    let mut not_mine = Vec::<Attribute>::new();
+    for attr in &variant.attrs {
+        if attr.path().is_ident("ext") {
+            attr.parse_args_with(|input: ParseStream| args.parse_into(input))
+                .map_err(|e| {
+                    EnumMacroError::ParseError(format!("variant '{}': {}", variant.ident, e))
+                })?;
+        } else {
+            not_mine.push(attr.clone());
+        }
+    }
+    Ok((args, not_mine))
+}
+
 #[derive(Debug, Default, Clone)]
 pub(crate) struct DeriveSummary {
     pub has_derive: bool,
@@ -108,138 +695,627 @@ pub(crate) struct DeriveSummary {
     pub has_partial_ord: bool,
     pub has_eq: bool,
     pub has_ord: bool,
+    /// Traits derived behind `#[cfg_attr(...)]`, with the cfg predicate they're derived under.
+    pub cfg_derives: Vec<(String, TokenStream2)>,
+    /// Other derived traits, by the last segment of their path (e.g. `Reflect` for `bevy::reflect::Reflect`).
+    pub other_derives: Vec<String>,
+}
+
+impl DeriveSummary {
+    fn record_trait(&mut self, trait_name: &str) {
+        match trait_name {
+            "Clone" => {
+                self.has_clone = true;
+            }
+            "Copy" => {
+                self.has_copy = true;
+            }
+            "Debug" => {
+                self.has_debug = true;
+            }
+            "Default" => {
+                self.has_default = true;
+            }
+            "Eq" => {
+                self.has_eq = true;
+            }
+            "Ord" => {
+                self.has_ord = true;
+            }
+            "PartialEq" => {
+                self.has_partial_eq = true;
+            }
+            "PartialOrd" => {
+                self.has_partial_ord = true;
+            }
+            _ => {
+                let name = trait_name.rsplit("::").next().unwrap_or(trait_name).trim();
+                self.other_derives.push(name.to_string());
+            }
+        }
+    }
+
+    /// Records the derives of an attribute, looking inside (possibly nested) `cfg_attr` wrappers.
+    fn record_meta(&mut self, meta: &syn::Meta, predicate: Option<&TokenStream2>) {
+        if meta.path().is_ident("derive") {
+            // I was unable to find a way to check inner Ident tokens in a proc_macro2::TokenStream without converting it to a string. #noob
+            if let syn::Meta::List(ref meta_list) = meta {
+                for x in meta_list.tokens.to_string().split(',') {
+                    match predicate {
+                        None => self.record_trait(x.trim()),
+                        Some(predicate) => self
+                            .cfg_derives
+                            .push((x.trim().to_string(), predicate.clone())),
+                    }
+                }
+            }
+            if predicate.is_none() {
+                self.has_derive = true;
+            }
+        } else if meta.path().is_ident("cfg_attr") {
+            // #[cfg_attr(predicate, attr1, attr2, ...)]
+            if let syn::Meta::List(ref meta_list) = meta {
+                if let Ok(nested) =
+                    meta_list.parse_args_with(Punctuated::<syn::Meta, Comma>::parse_terminated)
+                {
+                    let mut nested = nested.into_iter();
+                    if let Some(cfg) = nested.next() {
+                        let predicate = match predicate {
+                            None => cfg.to_token_stream(),
+                            Some(outer) => quote! { all(#outer, #cfg) },
+                        };
+                        for meta in nested {
+                            self.record_meta(&meta, Some(&predicate));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns true if a trait is derived unconditionally. Other traits are matched by the last segment of their path.
+    pub fn has_trait(&self, trait_name: &str) -> bool {
+        match trait_name {
+            "Clone" => self.has_clone,
+            "Copy" => self.has_copy,
+            "Debug" => self.has_debug,
+            "Default" => self.has_default,
+            "Eq" => self.has_eq,
+            "Ord" => self.has_ord,
+            "PartialEq" => self.has_partial_eq,
+            "PartialOrd" => self.has_partial_ord,
+            _ => self.other_derives.iter().any(|x| x == trait_name),
+        }
+    }
+
+    /// Returns the cfg predicate a trait is derived under, if it's only derived behind `cfg_attr`.
+    ///
+    /// Returns None if the trait is derived unconditionally or not at all.
+    pub fn cfg_predicate(&self, trait_name: &str) -> Option<TokenStream2> {
+        if self.has_trait(trait_name) {
+            return None;
+        }
+        let predicates = self
+            .cfg_derives
+            .iter()
+            .filter(|(x, _)| x == trait_name)
+            .map(|(_, predicate)| predicate)
+            .collect::<Vec<_>>();
+        match predicates.len() {
+            0 => None,
+            1 => Some(predicates[0].clone()),
+            _ => Some(quote! { any(#(#predicates),*) }),
+        }
+    }
+}
+
+/// Returns the integer type of a `#[repr(u8)]` or `#[repr(C, u8)]` attribute on the enum, if there is one.
+pub(crate) fn repr_int_type(attrs: &[Attribute]) -> Option<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Ident, Comma>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .map(|ident| ident.to_string())
+        .find(|ident| valid_int_type(ident))
 }
 
 /// Checks whether the enum has a derives attribute and if it derives anything we may care about.
+///
+/// Derives behind `#[cfg_attr(...)]` are recorded separately in `cfg_derives`.
 pub(crate) fn check_derive_traits(derive_attrs: &[Attribute]) -> DeriveSummary {
     let mut summary = DeriveSummary::default();
 
     for attr in derive_attrs {
-        if attr.path().is_ident("derive") {
-            summary.has_derive = true;
-            // I was unable to find a way to check inner Ident tokens in a proc_macro2::TokenStream without converting it to a string. #noob
-            match attr.meta {
-                syn::Meta::List(ref meta_list) => {
-                    meta_list
-                        .tokens
-                        .to_string()
-                        .split(',')
-                        .for_each(|x| match x.trim() {
-                            "Clone" => {
-                                summary.has_clone = true;
-                            }
-                            "Copy" => {
-                                summary.has_copy = true;
-                            }
-                            "Debug" => {
-                                summary.has_debug = true;
-                            }
-                            "Default" => {
-                                summary.has_default = true;
-                            }
-                            "Eq" => {
-                                summary.has_eq = true;
-                            }
-                            "Ord" => {
-                                summary.has_ord = true;
-                            }
-                            "PartialEq" => {
-                                summary.has_partial_eq = true;
-                            }
-                            "PartialOrd" => {
-                                summary.has_partial_ord = true;
-                            }
-                            _ => {}
-                        });
+        summary.record_meta(&attr.meta, None);
+    }
+
+    summary
+}
+
+/// Splits a PascalCase name into words separated by spaces. For example, `QAInDev` becomes `QA In Dev`.
+///
+/// The `Acronyms` of the enum are words of their own wherever they appear at the start of a word, with any digits
+/// that follow them, so `QAIDList` becomes `QA ID List` with `QA` and `ID` declared. The rest of the name is split
+/// by its capitals.
+pub(crate) fn split_pascal_case(s: &str, acronyms: &[String]) -> String {
+    if acronyms.is_empty() {
+        return split_capitals(s);
+    }
+    let mut words = Vec::new();
+    let mut segment = String::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        // the longest acronym that isn't the start of a longer lowercase word ("ID" in "IDentity")
+        let acronym = acronyms
+            .iter()
+            .filter(|acronym| {
+                (segment.is_empty() || c.is_uppercase())
+                    && rest.starts_with(acronym.as_str())
+                    && !rest[acronym.len()..]
+                        .chars()
+                        .next()
+                        .is_some_and(char::is_lowercase)
+            })
+            .max_by_key(|acronym| acronym.len());
+        match acronym {
+            Some(acronym) => {
+                if !segment.is_empty() {
+                    words.push(split_capitals(&segment));
+                    segment.clear();
                 }
-                _ => {}
+                let digits = rest[acronym.len()..]
+                    .chars()
+                    .take_while(char::is_ascii_digit)
+                    .count();
+                words.push(rest[..acronym.len() + digits].to_string());
+                rest = &rest[acronym.len() + digits..];
+            }
+            None => {
+                segment.push(c);
+                rest = &rest[c.len_utf8()..];
             }
         }
     }
+    if !segment.is_empty() {
+        words.push(split_capitals(&segment));
+    }
+    words.join(" ")
+}
 
-    summary
+/// Splits a name at its capitals, the word boundaries of [`split_pascal_case`] without acronyms.
+fn split_capitals(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 4);
+    let chars = s.chars().collect::<Vec<_>>();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let last = chars[i - 1];
+            // a new word starts after a lowercase letter or digit ("InQA" -> "In QA"), or at the last
+            // capital of an acronym that's followed by a lowercase letter ("QAInDev" -> "QA In Dev")
+            let ends_acronym =
+                last.is_uppercase() && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if !last.is_uppercase() || ends_acronym {
+                result.push(' ');
+            }
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+/// How names are lowercased, for snake_case, kebab-case and the case-insensitive parsers.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum Lowercase {
+    /// Full Unicode lowercasing, where one character can become several (e.g. 'İ' becomes "i̇").
+    #[default]
+    Unicode,
+    /// Only ASCII letters are lowercased, everything else is kept as is. Set by `enum_def(AsciiCase)`.
+    Ascii,
+}
+
+impl Lowercase {
+    /// Appends the lowercase form of `c` to `result`.
+    fn push(self, result: &mut String, c: char) {
+        match self {
+            Lowercase::Unicode => result.extend(c.to_lowercase()),
+            Lowercase::Ascii => result.push(c.to_ascii_lowercase()),
+        }
+    }
+
+    /// Appends the uppercase form of `c` to `result`, following the same Unicode or ASCII rule.
+    fn push_upper(self, result: &mut String, c: char) {
+        match self {
+            Lowercase::Unicode => result.extend(c.to_uppercase()),
+            Lowercase::Ascii => result.push(c.to_ascii_uppercase()),
+        }
+    }
+
+    /// The name of the `str` method that lowercases the same way, for generated code.
+    fn str_method(self) -> Ident {
+        match self {
+            Lowercase::Unicode => Ident::new("to_lowercase", Span::call_site()),
+            Lowercase::Ascii => Ident::new("to_ascii_lowercase", Span::call_site()),
+        }
+    }
+}
+
+/// Returns the words of `Acronyms = "HTTP,QA,ID"`, or an empty list if the option isn't set.
+fn acronym_list(acronyms: &Option<LitStr>) -> Vec<String> {
+    acronyms
+        .as_ref()
+        .map(|acronyms| {
+            acronyms
+                .value()
+                .split(',')
+                .map(|acronym| acronym.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
-pub(crate) fn split_pascal_case(s: &str) -> String {
+/// Converts a PascalCase name to lowercase words joined by `separator`, using the same word
+/// boundaries as [`split_pascal_case`]. For example, `InQA` becomes `in_qa` with `'_'`.
+pub(crate) fn join_pascal_words(
+    s: &str,
+    separator: &str,
+    lowercase: Lowercase,
+    acronyms: &[String],
+) -> String {
     let mut result = String::with_capacity(s.len() + 1);
-    let mut chars = s.chars().peekable();
 
-    while let Some(c) = chars.next() {
-        if c.is_uppercase()
-            && result
-                .chars()
-                .last()
-                .map_or(false, |last| !last.is_uppercase())
-        {
+    for word in split_pascal_case(s, acronyms).split(' ') {
+        if !result.is_empty() {
+            result.push_str(separator);
+        }
+        for c in word.chars() {
+            lowercase.push(&mut result, c);
+        }
+    }
+
+    result
+}
+
+/// Converts a PascalCase name to snake_case. For example, `InQA` becomes `in_qa`.
+pub(crate) fn to_snake_case(s: &str, lowercase: Lowercase, acronyms: &[String]) -> String {
+    join_pascal_words(s, "_", lowercase, acronyms)
+}
+
+/// Converts a PascalCase name to kebab-case. For example, `InQA` becomes `in-qa`.
+pub(crate) fn to_kebab_case(s: &str, lowercase: Lowercase, acronyms: &[String]) -> String {
+    join_pascal_words(s, "-", lowercase, acronyms)
+}
+
+/// Converts a PascalCase name to dot.case. For example, `FinalQA` becomes `final.qa`.
+pub(crate) fn to_dot_case(s: &str, lowercase: Lowercase, acronyms: &[String]) -> String {
+    join_pascal_words(s, ".", lowercase, acronyms)
+}
+
+/// Converts a PascalCase name to Sentence case, keeping the first word as declared and lowercasing the rest.
+/// For example, `InQAReview` becomes `In qa review` and `QAInDev` becomes `QA in dev`. Declared acronyms keep their
+/// capitals, so `InQAReview` becomes `In QA review` with `QA` declared.
+pub(crate) fn to_sentence_case(s: &str, lowercase: Lowercase, acronyms: &[String]) -> String {
+    let spaced = split_pascal_case(s, acronyms);
+    let mut words = spaced.split(' ');
+    let mut result = words.next().unwrap_or_default().to_string();
+    for word in words {
+        result.push(' ');
+        if is_acronym_word(word, acronyms) {
+            result.push_str(word);
+        } else {
+            for c in word.chars() {
+                lowercase.push(&mut result, c);
+            }
+        }
+    }
+    result
+}
+
+/// Returns true if `word`, as split by [`split_pascal_case`], is one of the acronyms, with any digits that follow it.
+fn is_acronym_word(word: &str, acronyms: &[String]) -> bool {
+    acronyms.iter().any(|acronym| {
+        word.strip_prefix(acronym.as_str())
+            .is_some_and(|digits| digits.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+/// Converts a PascalCase name to Title Case, capitalizing the first letter of every word and lowercasing the rest. For
+/// example, `InQAReview` becomes `In Qa Review`. Declared acronyms keep their capitals.
+pub(crate) fn to_title_case(s: &str, lowercase: Lowercase, acronyms: &[String]) -> String {
+    let mut result = String::with_capacity(s.len() + 4);
+    for word in split_pascal_case(s, acronyms).split(' ') {
+        if !result.is_empty() {
             result.push(' ');
         }
-        result.push(c);
+        if is_acronym_word(word, acronyms) {
+            result.push_str(word);
+            continue;
+        }
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            result.push(first);
+        }
+        for c in chars {
+            lowercase.push(&mut result, c);
+        }
+    }
+    result
+}
+
+/// Converts a PascalCase name to SCREAMING_SNAKE_CASE. For example, `InQA` becomes `IN_QA`.
+pub(crate) fn to_screaming_snake_case(
+    s: &str,
+    lowercase: Lowercase,
+    acronyms: &[String],
+) -> String {
+    to_upper_flat_case(&to_snake_case(s, lowercase, acronyms), lowercase)
+}
+
+/// Converts a PascalCase name to flatcase. For example, `InQA` becomes `inqa`.
+pub(crate) fn to_flat_case(s: &str, lowercase: Lowercase) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        lowercase.push(&mut result, c);
     }
+    result
+}
 
+/// Converts a PascalCase name to UPPERFLATCASE. For example, `InQA` becomes `INQA`.
+pub(crate) fn to_upper_flat_case(s: &str, lowercase: Lowercase) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        lowercase.push_upper(&mut result, c);
+    }
     result
 }
 
+/// Wraps `value` into the range of the given integer type, the same way an `as` cast would.
+/// Returns None if the type isn't a supported integer type or the value can't be represented.
+fn wrap_to_int_type(value: i128, int_type: &str) -> Option<i128> {
+    let wrapped = match int_type {
+        "i8" => value as i8 as i128,
+        "u8" => value as u8 as i128,
+        "i16" => value as i16 as i128,
+        "u16" => value as u16 as i128,
+        "i32" => value as i32 as i128,
+        "u32" => value as u32 as i128,
+        "i64" | "isize" => value as i64 as i128,
+        "u64" | "usize" => value as u64 as i128,
+        "i128" => value,
+        "u128" if value >= 0 => value,
+        _ => return None,
+    };
+    Some(wrapped)
+}
+
+/// Evaluates a discriminant expression at macro time.
+///
+/// Handles integer literals, parentheses, unary negation, the usual arithmetic and bitwise
+/// binary operators, and casts to integer types. Paths are looked up with `resolve`, so discriminants
+/// can refer to other variants. Anything else (consts, function calls, etc.) returns None so the
+/// caller can fall back to letting the compiler work out the value.
+pub(crate) fn eval_int_expr(
+    expr: &Expr,
+    resolve: &dyn Fn(&syn::Path) -> Option<i128>,
+) -> Option<i128> {
+    let eval_int_expr = |expr: &Expr| eval_int_expr(expr, resolve);
+    match expr {
+        Expr::Path(expr_path) if expr_path.qself.is_none() => resolve(&expr_path.path),
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            syn::Lit::Int(lit_int) => lit_int.base10_parse::<i128>().ok(),
+            _ => None,
+        },
+        Expr::Paren(paren) => eval_int_expr(&paren.expr),
+        Expr::Group(group) => eval_int_expr(&group.expr),
+        Expr::Unary(unary) => match unary.op {
+            syn::UnOp::Neg(_) => eval_int_expr(&unary.expr)?.checked_neg(),
+            _ => None,
+        },
+        Expr::Binary(binary) => {
+            let left = eval_int_expr(&binary.left)?;
+            let right = eval_int_expr(&binary.right)?;
+            match binary.op {
+                syn::BinOp::Add(_) => left.checked_add(right),
+                syn::BinOp::Sub(_) => left.checked_sub(right),
+                syn::BinOp::Mul(_) => left.checked_mul(right),
+                syn::BinOp::Div(_) => left.checked_div(right),
+                syn::BinOp::Rem(_) => left.checked_rem(right),
+                syn::BinOp::Shl(_) => left.checked_shl(u32::try_from(right).ok()?),
+                syn::BinOp::Shr(_) => left.checked_shr(u32::try_from(right).ok()?),
+                syn::BinOp::BitAnd(_) => Some(left & right),
+                syn::BinOp::BitOr(_) => Some(left | right),
+                syn::BinOp::BitXor(_) => Some(left ^ right),
+                _ => None,
+            }
+        }
+        Expr::Cast(cast) => {
+            let value = eval_int_expr(&cast.expr)?;
+            let ty = cast.ty.to_token_stream().to_string();
+            wrap_to_int_type(value, &ty)
+        }
+        _ => None,
+    }
+}
+
+/// Returns a suffixed integer literal expression such as `20i32`.
+fn int_literal_expr(value: i128, int_type_str: &str) -> Result<Expr, EnumMacroError> {
+    syn::parse_str::<Expr>(&format!("{}{}", value, int_type_str))
+        .map_err(|e| EnumMacroError::VariantError(format!("Invalid discriminant {}: {}", value, e)))
+}
+
+/// Returns the ordinal of each variant, in declaration order.
+///
+/// Ordinals follow the declaration order unless the variants declare `#[ext(order = ...)]`, in which case they follow
+/// the order values. If one variant has an order, every variant must have one, and the values must be unique.
+fn ordinals_by_order(variant_args: &[(Ident, VariantArgs)]) -> Result<Vec<usize>, EnumMacroError> {
+    if variant_args.iter().all(|(_, args)| args.order.is_none()) {
+        return Ok((0..variant_args.len()).collect());
+    }
+
+    let mut orders = Vec::new();
+    for (index, (variant_ident, args)) in variant_args.iter().enumerate() {
+        let order = args
+            .order
+            .as_ref()
+            .and_then(|order| order.base10_parse::<i64>().ok())
+            .ok_or_else(|| {
+                EnumMacroError::VariantError(format!(
+                    "variant '{}' is missing #[ext(order = ...)]: if one variant has an order, all variants must",
+                    variant_ident
+                ))
+            })?;
+        if orders.iter().any(|(o, _)| *o == order) {
+            return Err(EnumMacroError::VariantError(format!(
+                "variant '{}' has a duplicate order {}",
+                variant_ident, order
+            )));
+        }
+        orders.push((order, index));
+    }
+    orders.sort();
+
+    let mut ordinals = vec![0; variant_args.len()];
+    for (ordinal, (_, index)) in orders.into_iter().enumerate() {
+        ordinals[index] = ordinal;
+    }
+    Ok(ordinals)
+}
+
+/// The token streams and lookups produced by [`parse_variants`].
+pub(crate) struct ParsedVariants {
+    /// The variants, re-emitted as the enum body.
+    pub enum_body: TokenStream2,
+    /// `EnumName::Variant,` for each variant, in ordinal order.
+    pub variant_list: TokenStream2,
+    /// The variant identifiers, in ordinal order.
+    pub ordered_variants: Vec<Ident>,
+    /// `EnumName::Variant => ordinal,` match arms.
+    pub variant_ordinals: TokenStream2,
+    /// Each variant identifier with its optional discriminant expression, in declaration order.
+    pub variant_map: Vec<(Ident, Option<(syn::token::Eq, Expr)>)>,
+    /// `EnumName::Variant => "Pascal Spaced",` match arms.
+    pub to_pascal_split: TokenStream2,
+    /// `"Pascal Spaced" => Some(EnumName::Variant),` match arms.
+    pub from_pascal_split: TokenStream2,
+    /// `EnumName::Variant => "snake_case",` match arms.
+    pub to_snake_case: TokenStream2,
+    /// `"snake_case" => Some(EnumName::Variant),` match arms.
+    pub from_snake_case: TokenStream2,
+    /// `EnumName::Variant => "kebab-case",` match arms.
+    pub to_kebab_case: TokenStream2,
+    /// `"kebab-case" => Some(EnumName::Variant),` match arms.
+    pub from_kebab_case: TokenStream2,
+    /// `EnumName::Variant => "Sentence case",` match arms.
+    pub to_sentence_case: TokenStream2,
+    /// `"Sentence case" => Some(EnumName::Variant),` match arms.
+    pub from_sentence_case: TokenStream2,
+    /// `EnumName::Variant => "dot.case",` match arms.
+    pub to_dot_case: TokenStream2,
+    /// `"dot.case" => Some(EnumName::Variant),` match arms.
+    pub from_dot_case: TokenStream2,
+    /// `EnumName::Variant => "Title Case",` match arms.
+    pub to_title_case: TokenStream2,
+    /// `"Title Case" => Some(EnumName::Variant),` match arms.
+    pub from_title_case: TokenStream2,
+    /// `EnumName::Variant => "flatcase",` match arms.
+    pub to_flat_case: TokenStream2,
+    /// `"flatcase" => Some(EnumName::Variant),` match arms.
+    pub from_flat_case: TokenStream2,
+    /// `EnumName::Variant => "UPPERFLATCASE",` match arms.
+    pub to_upper_flat: TokenStream2,
+    /// `"UPPERFLATCASE" => Some(EnumName::Variant),` match arms.
+    pub from_upper_flat: TokenStream2,
+    /// `"name" => Some(EnumName::Variant),` match arms for every distinct name form and alias of every variant.
+    pub parse_names: TokenStream2,
+    /// `#[doc(alias = "...")]` attributes for every alias, for the functions that accept them.
+    pub doc_aliases: TokenStream2,
+    /// `EnumName::Variant => &["alias", ...],` match arms.
+    pub to_aliases: TokenStream2,
+    /// `EnumName::Variant => Some("color"),` match arms.
+    pub to_colors: TokenStream2,
+    /// The number of variants.
+    pub variant_count: usize,
+    /// `ordinal => Some(EnumName::Variant),` match arms.
+    pub variant_from_ordinals: TokenStream2,
+    /// `EnumName::Variant => "Variant",` match arms.
+    pub to_variant_name: TokenStream2,
+    /// The `#[ext(...)]` arguments of each variant, in declaration order.
+    pub variant_args: Vec<(Ident, VariantArgs)>,
+}
+
 /// Parses the variants of an enum.
 ///
 /// This function takes a reference to the enum name and a reference to the punctuated list of variants.
-/// It returns a tuple containing:
-/// - A token stream for the enum body.
-/// - A token stream for the variant list.
-/// - A token stream for the variant ordinals.
-/// - A hashmap mapping variant identifiers to their optional discriminant values.
-/// - The count of variants.
+/// It returns a [`ParsedVariants`] containing the token streams used to build the generated functions.
 ///
 /// # Arguments
 ///
 /// * `enum_name` - The identifier of the enum.
 /// * `variants` - A punctuated list of the variants of the enum.
 ///
-/// # Returns
-///
-/// A tuple containing:
-/// - A token stream for the enum body.
-/// - A token stream for the variant list.
-/// - A token stream for the variant ordinals.
-/// - A hashmap mapping variant identifiers to their optional discriminant values.
-/// - The count of variants.
-/// - A token stream for the variant from ordinals.
-///
 /// # Examples
 ///
 /// ```text
-/// let (enum_body, variant_list, variant_ordinals, variant_map, variant_count) =
-///     parse_variants(&name, &variants);
+/// let parsed = parse_variants(&name, &variants, Lowercase::Unicode, &acronyms)?;
 /// ```
 pub(crate) fn parse_variants(
     enum_name: &Ident,
     variants: &Punctuated<Variant, Comma>,
-    int_type: &TokenStream2,
-) -> Result<
-    (
-        TokenStream2,
-        TokenStream2,
-        TokenStream2,
-        HashMap<Ident, Option<(syn::token::Eq, Expr)>>,
-        TokenStream2,
-        TokenStream2,
-        usize,
-        TokenStream2,
-    ),
-    EnumMacroError,
-> {
+    lowercase: Lowercase,
+    acronyms: &[String],
+) -> Result<ParsedVariants, EnumMacroError> {
     let name = enum_name.clone();
     let mut enum_body = TokenStream2::new();
     let mut variant_count = 0usize;
     let mut variant_list = TokenStream2::new();
     let mut variant_ordinals = TokenStream2::new();
     let mut variant_from_ordinals = TokenStream2::new();
-    let mut variant_ordinal = 0usize;
-    let mut variant_ordinal2 = 0usize;
-    let mut variant_map = HashMap::new();
+    let mut variant_map = Vec::new();
     let mut to_pascal_split = TokenStream2::new();
     let mut from_pascal_split = TokenStream2::new();
+    let mut to_snake = TokenStream2::new();
+    let mut from_snake = TokenStream2::new();
+    let mut to_kebab = TokenStream2::new();
+    let mut from_kebab = TokenStream2::new();
+    let mut to_sentence = TokenStream2::new();
+    let mut from_sentence = TokenStream2::new();
+    let mut to_dot = TokenStream2::new();
+    let mut from_dot = TokenStream2::new();
+    let mut to_title = TokenStream2::new();
+    let mut from_title = TokenStream2::new();
+    let mut to_flat = TokenStream2::new();
+    let mut from_flat = TokenStream2::new();
+    let mut to_upper_flat = TokenStream2::new();
+    let mut from_upper_flat = TokenStream2::new();
+    let mut parse_names = TokenStream2::new();
+    // names already matched by `parse_names`, so a repeated name doesn't become an unreachable arm
+    let mut parse_seen = std::collections::HashSet::new();
+    let mut to_variant_name = TokenStream2::new();
+    let mut doc_aliases = TokenStream2::new();
+    let mut to_aliases = TokenStream2::new();
+    let mut to_colors = TokenStream2::new();
+    let mut variant_args = Vec::new();
 
+    // the #[ext(...)] arguments are needed up front, since they can change the ordinals
+    let mut other_attrs_list = Vec::new();
     for variant in variants {
+        let (args, other_attrs) = process_variant_attributes(variant)?;
+        variant_args.push((variant.ident.clone(), args));
+        other_attrs_list.push(other_attrs);
+    }
+    let ordinals = ordinals_by_order(&variant_args)?;
+    let mut ordered_variants = vec![None; variants.len()];
+
+    for (((variant, other_attrs), variant_ordinal), (_, args)) in variants
+        .iter()
+        .zip(other_attrs_list)
+        .zip(ordinals)
+        .zip(&variant_args)
+    {
         if !variant.fields.is_empty() {
             // Variant has additional data (like `A(String)`)
             return Err(EnumMacroError::VariantError(format!(
@@ -247,117 +1323,281 @@ pub(crate) fn parse_variants(
                 variant.to_token_stream()
             )));
         }
-        let variant_ident = variant.ident.clone();
-        let variant_ident2 = variant.ident.clone();
-        let variant_ident3 = variant.ident.clone();
-        let variant_ident4 = variant.ident.clone();
-        let variant_ident5 = variant.ident.clone();
-        let variant_ident6 = variant.ident.clone();
-
-        let variant_value = if let Some((_eq, expr)) = &variant.discriminant {
-            let new_expr = quote! { #expr }.to_string();
-            let int_type_str = int_type.to_string();
-            let new_expr_with_type = format!("{}{}", new_expr, int_type_str);
-            Some((
-                _eq.clone(),
-                syn::parse_str::<syn::Expr>(&new_expr_with_type).unwrap(),
-            ))
-        } else {
-            None
-        };
+        let variant_ident = &variant.ident;
 
-        variant_map.insert(variant_ident, variant_value);
+        variant_map.push((variant_ident.clone(), variant.discriminant.clone()));
+
+        // re-emit the variant without our own #[ext(...)] attributes, but with rustdoc aliases for its aliases
+        let mut variant = variant.clone();
+        variant.attrs = other_attrs;
+        let aliases = &args.aliases;
+        for alias in aliases.iter().filter(|alias| is_doc_alias(&alias.value())) {
+            variant
+                .attrs
+                .push(syn::parse_quote! { #[doc(alias = #alias)] });
+            doc_aliases.extend(quote! { #[doc(alias = #alias)] });
+        }
+        to_aliases.extend(quote! {
+            #name::#variant_ident => &[#(#aliases),*],
+        });
+        let color = match &args.color {
+            Some(color) => quote! { Some(#color) },
+            None => quote! { None },
+        };
+        to_colors.extend(quote! {
+            #name::#variant_ident => #color,
+        });
 
         let variant_tokens = quote! {
             #variant,
         };
         enum_body.extend(variant_tokens);
 
-        let variant_list_tokens = quote! {
-            #name::#variant_ident2,
-        };
-        variant_list.extend(variant_list_tokens);
+        ordered_variants[variant_ordinal] = Some(variant_ident.clone());
         variant_count += 1;
 
+        // unsuffixed, so the arms work with any OrdinalType
+        let ordinal_lit = proc_macro2::Literal::usize_unsuffixed(variant_ordinal);
         let variant_ordinals_tokens = quote! {
-            #name::#variant_ident3 => #variant_ordinal,
+            #name::#variant_ident => #ordinal_lit,
         };
         variant_ordinals.extend(variant_ordinals_tokens);
-        variant_ordinal += 1;
 
-        let pascal_split_str = split_pascal_case(&variant_ident4.to_string());
+        let pascal_split_str = split_pascal_case(&variant_ident.to_string(), acronyms);
         let variant_pascal_tokens = quote! {
-            #name::#variant_ident4 => #pascal_split_str,
+            #name::#variant_ident => #pascal_split_str,
         };
         to_pascal_split.extend(variant_pascal_tokens);
 
         let variant_pascal_tokens = quote! {
-            #pascal_split_str => Some(#name::#variant_ident5),
+            #pascal_split_str => Some(#name::#variant_ident),
         };
         from_pascal_split.extend(variant_pascal_tokens);
 
         let variant_ordinals_tokens = quote! {
-            #variant_ordinal2 => Some(#name::#variant_ident6),
+            #ordinal_lit => Some(#name::#variant_ident),
         };
         variant_from_ordinals.extend(variant_ordinals_tokens);
-        variant_ordinal2 += 1;
+
+        let variant_name_str = variant_ident.to_string();
+        to_variant_name.extend(quote! {
+            #name::#variant_ident => #variant_name_str,
+        });
+
+        let snake_str = to_snake_case(&variant_name_str, lowercase, acronyms);
+        to_snake.extend(quote! {
+            #name::#variant_ident => #snake_str,
+        });
+        from_snake.extend(quote! {
+            #snake_str => Some(#name::#variant_ident),
+        });
+
+        let kebab_str = to_kebab_case(&variant_name_str, lowercase, acronyms);
+        to_kebab.extend(quote! {
+            #name::#variant_ident => #kebab_str,
+        });
+        from_kebab.extend(quote! {
+            #kebab_str => Some(#name::#variant_ident),
+        });
+
+        let sentence_str = to_sentence_case(&variant_name_str, lowercase, acronyms);
+        to_sentence.extend(quote! {
+            #name::#variant_ident => #sentence_str,
+        });
+        from_sentence.extend(quote! {
+            #sentence_str => Some(#name::#variant_ident),
+        });
+
+        let dot_str = to_dot_case(&variant_name_str, lowercase, acronyms);
+        to_dot.extend(quote! {
+            #name::#variant_ident => #dot_str,
+        });
+        from_dot.extend(quote! {
+            #dot_str => Some(#name::#variant_ident),
+        });
+
+        let title_str = to_title_case(&variant_name_str, lowercase, acronyms);
+        to_title.extend(quote! {
+            #name::#variant_ident => #title_str,
+        });
+        from_title.extend(quote! {
+            #title_str => Some(#name::#variant_ident),
+        });
+
+        let flat_str = to_flat_case(&variant_name_str, lowercase);
+        to_flat.extend(quote! {
+            #name::#variant_ident => #flat_str,
+        });
+        from_flat.extend(quote! {
+            #flat_str => Some(#name::#variant_ident),
+        });
+
+        let upper_flat_str = to_upper_flat_case(&variant_name_str, lowercase);
+        to_upper_flat.extend(quote! {
+            #name::#variant_ident => #upper_flat_str,
+        });
+        from_upper_flat.extend(quote! {
+            #upper_flat_str => Some(#name::#variant_ident),
+        });
+
+        for name_str in [&variant_name_str, &pascal_split_str, &snake_str, &kebab_str] {
+            if parse_seen.insert(name_str.clone()) {
+                parse_names.extend(quote! {
+                    #name_str => Some(#name::#variant_ident),
+                });
+            }
+        }
+    }
+
+    // aliases come after every name, so an alias can't shadow a name of another variant
+    for (variant_ident, args) in &variant_args {
+        for alias in &args.aliases {
+            if !parse_seen.insert(alias.value()) {
+                return Err(EnumMacroError::VariantError(format!(
+                    "alias {:?} of variant '{}' is already a name or alias of a variant",
+                    alias.value(),
+                    variant_ident
+                )));
+            }
+            parse_names.extend(quote! {
+                #alias => Some(#name::#variant_ident),
+            });
+        }
+    }
+
+    let ordered_variants = ordered_variants.into_iter().flatten().collect::<Vec<_>>();
+    for variant_ident in &ordered_variants {
+        variant_list.extend(quote! {
+            #name::#variant_ident,
+        });
     }
 
-    Ok((
+    Ok(ParsedVariants {
         enum_body,
         variant_list,
+        ordered_variants,
         variant_ordinals,
         variant_map,
         to_pascal_split,
         from_pascal_split,
+        to_snake_case: to_snake,
+        from_snake_case: from_snake,
+        to_kebab_case: to_kebab,
+        from_kebab_case: from_kebab,
+        to_sentence_case: to_sentence,
+        from_sentence_case: from_sentence,
+        to_dot_case: to_dot,
+        from_dot_case: from_dot,
+        to_title_case: to_title,
+        from_title_case: from_title,
+        to_flat_case: to_flat,
+        from_flat_case: from_flat,
+        to_upper_flat,
+        from_upper_flat,
+        parse_names,
+        doc_aliases,
+        to_aliases,
+        to_colors,
         variant_count,
         variant_from_ordinals,
-    ))
+        to_variant_name,
+        variant_args,
+    })
+}
+
+/// Returns true if `alias` can be used in `#[doc(alias = "...")]`, which rejects quotes, tabs, newlines and
+/// surrounding spaces.
+fn is_doc_alias(alias: &str) -> bool {
+    !alias.is_empty() && alias.trim() == alias && !alias.contains(['"', '\'', '\t', '\n', '\r'])
+}
+
+/// How `as_<IntType>()` gets the discriminant of `self`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SelfCast {
+    /// `*self as T`, which also makes it a const fn.
+    Copy,
+    /// `self.clone() as T`.
+    Clone,
+    /// A match on the variants, for enums that are neither `Copy` nor `Clone`.
+    Match,
 }
 
 /// Appends integer conversion functions to the enum.
 ///
-/// This function takes mutable references to a token stream for the functions, the enum name, a hashmap mapping variant identifiers to their optional discriminant values, a string for the integer type, and a token stream for the integer type.
+/// This function takes mutable references to a token stream for the functions, the enum name, the variant identifiers with their optional discriminant expressions, a string for the integer type, and a token stream for the integer type.
 /// It returns a boolean indicating whether the integer type was added to the enum.
+/// It can be called once per integer type when more than one `IntType` is specified.
 ///
 /// # Arguments
 ///
 /// * `fns` - A mutable reference to a token stream for the functions.
 /// * `enum_name` - The identifier of the enum.
-/// * `variant_map` - A hashmap mapping variant identifiers to their optional discriminant values.
+/// * `variant_map` - The variant identifiers with their optional discriminant expressions, in declaration order.
+///   Variants without a discriminant are mapped to their implied value (previous + 1).
 /// * `int_type_str` - A string for the integer type.
 /// * `int_type` - A token stream for the integer type.
+/// * `variant_args` - The `#[ext(...)]` arguments of the variants, for their `legacy_value`s.
+/// * `self_cast` - How `as_<IntType>()` gets the discriminant, depending on whether the enum is `Copy` or `Clone`.
 ///
 /// # Returns
 ///
-/// A boolean indicating whether the integer type was added to the enum.
+/// A boolean indicating whether the integer type was added to the enum, or an error if a discriminant
+/// expression can't be converted to the integer type.
 ///
 /// # Examples
 ///
 /// ```text
-/// let int_type_added = append_int_fns(&mut enum_fns, &name, variant_map, &int_type_str, &int_type);
+/// let int_type_added = append_int_fns(&mut enum_fns, &name, &variant_map, &int_type_str, &int_type, &variant_args, SelfCast::Clone)?;
 /// ```
 pub(crate) fn append_int_fns(
     fns: &mut TokenStream2,
     enum_name: &Ident,
-    variant_map: HashMap<Ident, Option<(syn::token::Eq, Expr)>>,
+    variant_map: &[(Ident, Option<(syn::token::Eq, Expr)>)],
     int_type_str: &str,
     int_type: &TokenStream2,
-) -> bool {
+    variant_args: &[(Ident, VariantArgs)],
+    self_cast: SelfCast,
+) -> Result<bool, EnumMacroError> {
     let mut from_int_tokens = TokenStream2::new();
-    let mut int_type_added = false;
-    for (variant_ident, variant_value) in variant_map {
-        match variant_value {
-            Some(v) => {
-                let v = v.1;
-                let variant_tokens = quote! {
+    let mut as_int_tokens = TokenStream2::new();
+    let int_type_added = variant_map.iter().any(|(_, value)| value.is_some());
+    let values = implied_values(
+        enum_name,
+        variant_map
+            .iter()
+            .map(|(ident, value)| (ident, value.as_ref().map(|v| &v.1))),
+    );
+    let legacy_tokens = make_legacy_value_arms(
+        enum_name,
+        variant_args,
+        &values,
+        int_type_str,
+        int_type_added,
+    )?;
+    for ((variant_ident, _), value) in variant_map.iter().zip(values) {
+        as_int_tokens.extend(match value {
+            Some(value) => {
+                let v = int_literal_expr(value, int_type_str)?;
+                quote! { #enum_name::#variant_ident => #v, }
+            }
+            None => {
+                quote! { #enum_name::#variant_ident => #enum_name::#variant_ident as #int_type, }
+            }
+        });
+        let variant_tokens = match value {
+            Some(value) => {
+                let v = int_literal_expr(value, int_type_str)?;
+                quote! {
                     #v => Some(#enum_name::#variant_ident),
-                };
-                from_int_tokens.extend(variant_tokens);
-                int_type_added = true;
+                }
             }
-            None => {}
+            // the discriminant (or the one it's implied from) couldn't be evaluated, e.g. it's a const,
+            // so let the compiler work it out
+            None => quote! {
+                v if v == #enum_name::#variant_ident as #int_type => Some(#enum_name::#variant_ident),
+            },
         };
+        from_int_tokens.extend(variant_tokens);
     }
     if int_type_added {
         // Construct the function name string and parse it into an identifier.
@@ -367,6 +1607,27 @@ pub(crate) fn append_int_fns(
         let as_fn_name_str = format!("as_{}", int_type_str); // Similar for the `to_` function
         let as_fn_name = Ident::new(&as_fn_name_str, Span::call_site());
 
+        let as_fn = match self_cast {
+            SelfCast::Copy => quote! {
+                pub const fn #as_fn_name(&self) -> #int_type {
+                    *self as #int_type
+                }
+            },
+            SelfCast::Clone => quote! {
+                pub fn #as_fn_name(&self) -> #int_type {
+                    self.clone() as #int_type
+                }
+            },
+            // without Clone the variant can't be cast, so match on it
+            SelfCast::Match => quote! {
+                pub const fn #as_fn_name(&self) -> #int_type {
+                    match self {
+                        #as_int_tokens
+                    }
+                }
+            },
+        };
+
         let int_helpers = quote! {
 
             /// Returns the enum variant from the integer value
@@ -374,67 +1635,4997 @@ pub(crate) fn append_int_fns(
             pub const fn #from_fn_name(val: #int_type) -> Option<Self> {
                 match val {
                     #from_int_tokens
+                    #legacy_tokens
                     _ => None,
                 }
             }
             /// Returns the integer value from the enum variant
             #[inline]
-            pub fn #as_fn_name(&self) -> #int_type {
-                self.clone() as #int_type
-            }
-
+            #as_fn
         };
 
         fns.extend(int_helpers);
     }
-    int_type_added
+    Ok(int_type_added)
 }
 
-/// Constructs the pretty print string for the enum.
-pub(crate) fn make_pretty_print(
-    attrs: Vec<Attribute>,
-    needed_derives: TokenStream2,
-    vis: Visibility,
-    name: Ident,
-    enum_body: TokenStream2,
-    repl_value: TokenStream2,
-) -> String {
-    let mut pretty_print_body = Vec::new();
-    let attrs = (quote! { #(#attrs)* }).to_string().trim().to_owned();
-    if !attrs.is_empty() {
-        pretty_print_body.push(attrs);
-        pretty_print_body.push("\n".to_owned());
+/// Returns the `from_<IntType>()` match arms mapping each `#[ext(legacy_value = ...)]` to its variant.
+///
+/// A legacy value must fit in the IntType and can't be a current discriminant or another variant's legacy value.
+fn make_legacy_value_arms(
+    enum_name: &Ident,
+    variant_args: &[(Ident, VariantArgs)],
+    values: &[Option<i128>],
+    int_type_str: &str,
+    int_type_added: bool,
+) -> Result<TokenStream2, EnumMacroError> {
+    let (type_min, type_max) = int_type_bounds(int_type_str);
+    let mut seen = Vec::<i128>::new();
+    let mut arms = TokenStream2::new();
+    for (variant_ident, args) in variant_args {
+        for &legacy in &args.legacy_values {
+            if !int_type_added {
+                return Err(EnumMacroError::VariantError(format!(
+                    "variant '{}': legacy_value needs the enum to have discriminants",
+                    variant_ident
+                )));
+            }
+            if !(type_min..=type_max).contains(&legacy) {
+                return Err(EnumMacroError::VariantError(format!(
+                    "variant '{}': legacy_value {} doesn't fit in {}",
+                    variant_ident, legacy, int_type_str
+                )));
+            }
+            if values.contains(&Some(legacy)) || seen.contains(&legacy) {
+                return Err(EnumMacroError::VariantError(format!(
+                    "variant '{}': legacy_value {} is already in use",
+                    variant_ident, legacy
+                )));
+            }
+            seen.push(legacy);
+            let v = int_literal_expr(legacy, int_type_str)?;
+            arms.extend(quote! {
+                #v => Some(#enum_name::#variant_ident),
+            });
+        }
     }
-    let needed_derives = (quote! { #needed_derives }).to_string().trim().to_owned();
-    if !needed_derives.is_empty() {
+    Ok(arms)
+}
+
+/// Appends the bitmask helpers (`to_mask()`, `from_mask_single()`, `variants_in_mask()` and `mask_of()`) if every
+/// discriminant is a power of two.
+///
+/// Nothing is appended if the enum has no discriminants or a discriminant can't be evaluated at compile time.
+/// Returns true if the helpers were appended.
+pub(crate) fn append_mask_fns(
+    fns: &mut TokenStream2,
+    enum_name: &Ident,
+    variant_map: &[(Ident, Option<(syn::token::Eq, Expr)>)],
+    int_type_str: &str,
+    int_type: &TokenStream2,
+) -> Result<bool, EnumMacroError> {
+    if variant_map.iter().all(|(_, value)| value.is_none()) {
+        return Ok(false);
+    }
+    let values = implied_values(
+        enum_name,
+        variant_map
+            .iter()
+            .map(|(ident, value)| (ident, value.as_ref().map(|v| &v.1))),
+    );
+    if !values
+        .iter()
+        .all(|value| value.is_some_and(|v| v > 0 && v & (v - 1) == 0))
+    {
+        return Ok(false);
+    }
+
+    let mut to_mask = TokenStream2::new();
+    for ((variant_ident, _), value) in variant_map.iter().zip(values) {
+        let v = int_literal_expr(value.unwrap_or_default(), int_type_str)?;
+        to_mask.extend(quote! {
+            #enum_name::#variant_ident => #v,
+        });
+    }
+    let from_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+
+    fns.extend(quote! {
+        /// Returns the bit of the variant, which is its discriminant
+        #[inline]
+        pub const fn to_mask(&self) -> #int_type {
+            match self {
+                #to_mask
+            }
+        }
+        /// Returns the variant if `mask` has exactly the bit of that variant set
+        #[inline]
+        pub const fn from_mask_single(mask: #int_type) -> Option<Self> {
+            Self::#from_fn_name(mask)
+        }
+        /// Returns an iterator over the variants whose bit is set in `mask`, in declaration order.
+        /// * Bits that don't belong to a variant are ignored
+        pub fn variants_in_mask(mask: #int_type) -> impl Iterator<Item = Self> {
+            ::core::iter::IntoIterator::into_iter(Self::list()).filter(move |x| mask & x.to_mask() != 0)
+        }
+        /// Returns the mask with the bit of every given variant set
+        pub fn mask_of<B: ::core::borrow::Borrow<Self>>(items: impl IntoIterator<Item = B>) -> #int_type {
+            items.into_iter().fold(0, |mask, x| mask | x.borrow().to_mask())
+        }
+    });
+
+    Ok(true)
+}
+
+/// Generates `AsRef<str>`, returning the name of the variant as declared.
+///
+/// It's left out if the enum derives strum's `AsRefStr`, which implements the same trait.
+fn make_as_ref_impl(
+    name: &Ident,
+    generics: &Generics,
+    derive_summary: &DeriveSummary,
+) -> TokenStream2 {
+    if derive_summary.has_trait("AsRefStr") {
+        return TokenStream2::new();
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::core::convert::AsRef<str> for #name #ty_generics #where_clause {
+            /// Returns the name of the variant as declared
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self.variant_name()
+            }
+        }
+    }
+}
+
+/// Generates an `is_<variant>()` predicate for every variant, named with the snake_case of the variant name.
+///
+/// The variants are matched with `{ .. }`, so the same predicates work for unit variants and variants with data.
+/// `AsciiCase` doesn't apply, since rustc wants function names in Unicode snake_case. Two variants with the same
+/// snake_case name, e.g. `HttpError` and `HTTPError`, are an error.
+fn make_is_variant_fns(
+    enum_name: &Ident,
+    variants: &[Ident],
+    acronyms: &[String],
+) -> Result<TokenStream2, EnumMacroError> {
+    let mut fns = TokenStream2::new();
+    let mut seen: Vec<(String, &Ident)> = Vec::new();
+    for variant_ident in variants {
+        // without the r# of a raw identifier, e.g. is_type() for r#type
+        let snake = to_snake_case(
+            &variant_ident.unraw().to_string(),
+            Lowercase::Unicode,
+            acronyms,
+        );
+        if let Some((_, other)) = seen.iter().find(|(name, _)| *name == snake) {
+            return Err(EnumMacroError::VariantError(format!(
+                "variants '{}' and '{}' would both get is_{}()",
+                other, variant_ident, snake
+            )));
+        }
+        let fn_name = Ident::new(&format!("is_{}", snake), Span::call_site());
+        let doc = format!(" Returns true if the variant is `{}`", variant_ident);
+        fns.extend(quote! {
+            #[doc = #doc]
+            #[inline]
+            pub const fn #fn_name(&self) -> bool {
+                matches!(self, #enum_name::#variant_ident { .. })
+            }
+        });
+        seen.push((snake, variant_ident));
+    }
+    Ok(fns)
+}
+
+/// Generates `group_by_prefix()`, grouping the variants by the first word of their `pascal_spaced()` name.
+///
+/// Groups are in the order their first variant appears, and each group keeps the ordinal order of its variants.
+fn make_group_by_prefix(
+    enum_name: &Ident,
+    ordered_variants: &[Ident],
+    acronyms: &[String],
+) -> TokenStream2 {
+    let mut groups: Vec<(String, Vec<&Ident>)> = Vec::new();
+    for variant_ident in ordered_variants {
+        let spaced = split_pascal_case(&variant_ident.to_string(), acronyms);
+        let prefix = spaced.split(' ').next().unwrap_or_default().to_string();
+        match groups.iter_mut().find(|(x, _)| *x == prefix) {
+            Some((_, variants)) => variants.push(variant_ident),
+            None => groups.push((prefix, vec![variant_ident])),
+        }
+    }
+    let groups = groups.iter().map(|(prefix, variants)| {
+        quote! { (#prefix, &[#(#enum_name::#variants),*]) }
+    });
+    quote! {
+        /// Returns the variants grouped by the first word of their name, e.g. ("Http", [HttpGet, HttpPost])
+        /// * Groups are in the order their first variant appears, and keep the ordinal order of their variants
+        pub const fn group_by_prefix() -> &'static [(&'static str, &'static [Self])] {
+            const GROUPS: &[(&str, &[#enum_name])] = &[#(#groups),*];
+            GROUPS
+        }
+    }
+}
+
+/// Returns the doc comment of a variant as one line, or an empty string if it has none.
+fn variant_description(variant: &Variant) -> String {
+    variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit_str),
+                        ..
+                    }),
+                ..
+            }) => Some(lit_str.value().trim().to_string()),
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the lines of a markdown table of the variants, with their ordinal, discriminant and case forms, for the
+/// rustdoc of the enum. Discriminants that can't be worked out at macro time are shown as written.
+fn variants_doc_lines(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    ordered_variants: &[Ident],
+    lowercase: Lowercase,
+    acronyms: &[String],
+) -> Vec<String> {
+    let values = discriminant_values(enum_name, variants);
+    let mut lines = vec![
+        String::new(),
+        "# Variant values".to_string(),
+        String::new(),
+        "| Variant | Ordinal | Discriminant | snake_case | kebab-case |".to_string(),
+        "|---|---|---|---|---|".to_string(),
+    ];
+    for (ordinal, variant_ident) in ordered_variants.iter().enumerate() {
+        let Some(index) = variants.iter().position(|x| x.ident == *variant_ident) else {
+            continue;
+        };
+        let discriminant = match (values[index], &variants[index].discriminant) {
+            (Some(value), _) => value.to_string(),
+            (None, Some((_, expr))) => format!("`{}`", expr.to_token_stream()),
+            (None, None) => String::new(),
+        };
+        let name = variant_ident.to_string();
+        lines.push(format!(
+            "| `{}` | {} | {} | `{}` | `{}` |",
+            name,
+            ordinal,
+            discriminant,
+            to_snake_case(&name, lowercase, acronyms),
+            to_kebab_case(&name, lowercase, acronyms)
+        ));
+    }
+    lines
+}
+
+/// The unused discriminants of an enum, worked out at macro time.
+struct DiscriminantGaps {
+    /// The unused values between the smallest and the largest discriminant, as half-open ranges in ascending order.
+    gaps: Vec<(i128, i128)>,
+    /// One past the largest discriminant, or if that doesn't fit the IntType, the first unused value. None if every
+    /// value of the IntType is taken.
+    next_free: Option<i128>,
+}
+
+/// Returns the unused discriminants of the enum, or None if a discriminant can't be worked out at macro time.
+fn discriminant_gaps(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    int_type_str: &str,
+) -> Option<DiscriminantGaps> {
+    let mut values = discriminant_values(enum_name, variants)
+        .into_iter()
+        .map(|value| value.and_then(|value| wrap_to_int_type(value, int_type_str)))
+        .collect::<Option<Vec<_>>>()?;
+    values.sort_unstable();
+    values.dedup();
+    let gaps = values
+        .windows(2)
+        .filter(|pair| pair[0] + 1 < pair[1])
+        .map(|pair| (pair[0] + 1, pair[1]))
+        .collect::<Vec<_>>();
+    let (type_min, type_max) = int_type_bounds(int_type_str);
+    let (min, max) = (*values.first()?, *values.last()?);
+    let next_free = if max < type_max {
+        Some(max + 1)
+    } else if let Some((start, _)) = gaps.first() {
+        Some(*start)
+    } else if min > type_min {
+        Some(min - 1)
+    } else {
+        None
+    };
+    Some(DiscriminantGaps { gaps, next_free })
+}
+
+/// Returns the rustdoc lines listing the unused discriminants and the next free value, after the variants table.
+fn gaps_doc_lines(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    int_type_str: &str,
+) -> Vec<String> {
+    let Some(DiscriminantGaps { gaps, next_free }) =
+        discriminant_gaps(enum_name, variants, int_type_str)
+    else {
+        return Vec::new();
+    };
+    let gaps = if gaps.is_empty() {
+        "none".to_string()
+    } else {
+        gaps.iter()
+            .map(|(start, end)| format!("`{}..{}`", start, end))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut lines = vec![String::new(), format!("Unused discriminants: {}", gaps)];
+    if let Some(next_free) = next_free {
+        lines.push(String::new());
+        lines.push(format!("Next free discriminant: {}", next_free));
+    }
+    lines
+}
+
+/// Generates `gaps()` and `next_free_value()` from the discriminants, worked out at macro time.
+///
+/// Nothing is generated if a discriminant can't be worked out at macro time, and `next_free_value()` is left out if
+/// every value of the `IntType` is taken.
+fn make_gap_fns(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    int_type_str: &str,
+    int_type: &TokenStream2,
+) -> Result<TokenStream2, EnumMacroError> {
+    let Some(DiscriminantGaps { gaps, next_free }) =
+        discriminant_gaps(enum_name, variants, int_type_str)
+    else {
+        return Ok(TokenStream2::new());
+    };
+    let gaps = gaps
+        .into_iter()
+        .map(|(start, end)| {
+            let start = int_literal_expr(start, int_type_str)?;
+            let end = int_literal_expr(end, int_type_str)?;
+            Ok(quote! { #start..#end })
+        })
+        .collect::<Result<Vec<_>, EnumMacroError>>()?;
+    let mut fns = quote! {
+        /// Returns the unused discriminants between the smallest and the largest one, in ascending order
+        /// * For example, MyEnum::gaps() returns &[2..5] for discriminants 1, 5 and 6
+        pub const fn gaps() -> &'static [::core::ops::Range<#int_type>] {
+            const GAPS: &[::core::ops::Range<#int_type>] = &[#(#gaps),*];
+            GAPS
+        }
+    };
+    if let Some(next_free) = next_free {
+        let next_free = int_literal_expr(next_free, int_type_str)?;
+        fns.extend(quote! {
+            /// Returns the value for a new variant: one past the largest discriminant, or the first unused one if that
+            /// doesn't fit the IntType
+            pub const fn next_free_value() -> #int_type {
+                #next_free
+            }
+        });
+    }
+    Ok(fns)
+}
+
+/// Generates `SCHEMA_VERSION`, `encode_versioned()` and `decode_versioned()`, and the `<EnumName>DecodeError` type
+/// `decode_versioned()` returns.
+///
+/// The version folds `schema_hash` into a big-endian `u32`, so services built from different definitions of the enum
+/// fail to decode each other's values, rather than mapping them to the wrong variant. Two definitions still get the
+/// same version about once in 2^32. The discriminant follows it, big-endian, in the width of the `IntType`, which
+/// can't be `isize` or `usize` since their width depends on the platform.
+fn make_versioned_codec(
+    vis: &Visibility,
+    enum_name: &Ident,
+    int_type_str: &str,
+    int_type: &TokenStream2,
+    schema_hash: u64,
+    std_helpers: bool,
+) -> Result<(TokenStream2, TokenStream2), EnumMacroError> {
+    if matches!(int_type_str, "isize" | "usize") {
+        return Err(EnumMacroError::ParseError(format!(
+            "Versioned needs a fixed-width IntType for {}, not {}",
+            enum_name, int_type_str
+        )));
+    }
+    let version = (schema_hash >> 32) as u32 ^ schema_hash as u32;
+    let width = quote! { ::core::mem::size_of::<#int_type>() };
+    let len = quote! { 4 + #width };
+    let as_fn_name = Ident::new(&format!("as_{}", int_type_str), Span::call_site());
+    let from_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+    let error_name = Ident::new(&format!("{}DecodeError", enum_name), enum_name.span());
+    let error_doc = format!(" The error of [`{}::decode_versioned`]", enum_name);
+    let unknown_message = format!("unknown {} discriminant: {{}}", enum_name);
+    let error_impl = if std_helpers {
+        quote! { impl ::std::error::Error for #error_name {} }
+    } else {
+        TokenStream2::new()
+    };
+    let fns = quote! {
+        /// The version header of `encode_versioned()`, derived from `schema_hash()`
+        pub const SCHEMA_VERSION: u32 = #version;
+        /// Encodes the variant as the big-endian `SCHEMA_VERSION` followed by the big-endian discriminant
+        /// * `decode_versioned()` rejects the bytes if they come from a different definition of the enum
+        pub fn encode_versioned(&self) -> [u8; #len] {
+            let mut bytes = [0u8; #len];
+            bytes[..4].copy_from_slice(&Self::SCHEMA_VERSION.to_be_bytes());
+            bytes[4..].copy_from_slice(&self.#as_fn_name().to_be_bytes());
+            bytes
+        }
+        /// Decodes the output of `encode_versioned()`, failing if the version header doesn't match `SCHEMA_VERSION`
+        pub fn decode_versioned(bytes: &[u8]) -> Result<Self, #error_name> {
+            if bytes.len() != #len {
+                return Err(#error_name::WrongLength(bytes.len()));
+            }
+            let mut version = [0u8; 4];
+            version.copy_from_slice(&bytes[..4]);
+            let found = u32::from_be_bytes(version);
+            if found != Self::SCHEMA_VERSION {
+                return Err(#error_name::VersionMismatch {
+                    expected: Self::SCHEMA_VERSION,
+                    found,
+                });
+            }
+            let mut raw = [0u8; #width];
+            raw.copy_from_slice(&bytes[4..]);
+            let value = <#int_type>::from_be_bytes(raw);
+            Self::#from_fn_name(value).ok_or(#error_name::UnknownDiscriminant(value))
+        }
+    };
+    let error_type = quote! {
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis enum #error_name {
+            /// The bytes don't have the length of an encoded variant
+            WrongLength(usize),
+            /// The bytes were encoded by a different definition of the enum
+            VersionMismatch { expected: u32, found: u32 },
+            /// The version matches, but no variant has the discriminant
+            UnknownDiscriminant(#int_type),
+        }
+
+        impl ::core::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #error_name::WrongLength(found) => {
+                        write!(f, "expected {} bytes, found {}", #len, found)
+                    }
+                    #error_name::VersionMismatch { expected, found } => {
+                        write!(f, "schema version mismatch: expected {}, found {}", expected, found)
+                    }
+                    #error_name::UnknownDiscriminant(value) => write!(f, #unknown_message, value),
+                }
+            }
+        }
+
+        #error_impl
+    };
+    Ok((fns, error_type))
+}
+
+/// Generates `TryFrom<IntType>` for `enum_def(TryFrom)`, with `<EnumName>TryFromError` reporting the rejected value
+/// and the range of the discriminants.
+///
+/// The range is worked out at macro time, or from the variants at runtime if a discriminant can't be evaluated.
+fn make_try_from_type(
+    vis: &Visibility,
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    int_type_str: &str,
+    int_type: &TokenStream2,
+    std_helpers: bool,
+) -> Result<TokenStream2, EnumMacroError> {
+    let error_name = Ident::new(&format!("{}TryFromError", enum_name), enum_name.span());
+    let error_doc = format!(
+        " The error of converting a `{}` that isn't a discriminant of [`{}`]",
+        int_type_str, enum_name
+    );
+    let as_fn_name = Ident::new(&format!("as_{}", int_type_str), Span::call_site());
+    let from_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+    let message = format!(
+        "{{}} is not a valid {} discriminant (valid discriminants are in {{}}..={{}})",
+        enum_name
+    );
+    let error_impl = if std_helpers {
+        quote! { impl<T: Copy + ::core::fmt::Debug + ::core::fmt::Display> ::std::error::Error for #error_name<T> {} }
+    } else {
+        TokenStream2::new()
+    };
+    let values = discriminant_values(enum_name, variants);
+    let valid_range = match (values.iter().flatten().min(), values.iter().flatten().max()) {
+        (Some(min), Some(max)) if values.iter().all(Option::is_some) => {
+            let min = int_literal_expr(*min, int_type_str)?;
+            let max = int_literal_expr(*max, int_type_str)?;
+            quote! { #min..=#max }
+        }
+        // a discriminant is a const or an expression, so let the compiler work it out
+        _ => quote! {
+            let values = || #enum_name::iter().map(|variant| variant.#as_fn_name());
+            values().min().unwrap_or_default()..=values().max().unwrap_or_default()
+        },
+    };
+    Ok(quote! {
+        #[doc = #error_doc]
+        ///
+        /// `T` is the integer type that was converted, the `IntType` unless the value came from another width.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #error_name<T = #int_type> {
+            value: T,
+        }
+
+        #[allow(dead_code)]
+        impl<T: Copy> #error_name<T> {
+            /// Returns the value that isn't a discriminant
+            pub const fn value(&self) -> T {
+                self.value
+            }
+            /// Returns the range from the smallest to the largest discriminant. There can be gaps between them.
+            pub fn valid_range(&self) -> ::core::ops::RangeInclusive<#int_type> {
+                #valid_range
+            }
+        }
+
+        impl<T: Copy + ::core::fmt::Display> ::core::fmt::Display for #error_name<T> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let range = self.valid_range();
+                write!(f, #message, self.value, range.start(), range.end())
+            }
+        }
+
+        #error_impl
+
+        impl ::core::convert::TryFrom<#int_type> for #enum_name {
+            type Error = #error_name;
+            /// Returns the enum variant from the integer value, or an error reporting the value and the range of the
+            /// discriminants.
+            #[inline]
+            fn try_from(value: #int_type) -> Result<Self, #error_name> {
+                Self::#from_fn_name(value).ok_or(#error_name { value })
+            }
+        }
+    })
+}
+
+/// Generates `<enum_name>_for_each!`, a `macro_rules!` helper that invokes a macro once per variant in ordinal order
+/// with the variant path, its name and its discriminant.
+///
+/// The discriminant is a literal when it can be worked out at macro time, and a cast of the variant otherwise.
+fn make_for_each_macro(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    ordered_variants: &[Ident],
+    int_type: &TokenStream2,
+) -> TokenStream2 {
+    let macro_name = Ident::new(
+        &format!(
+            "{}_for_each",
+            join_pascal_words(&enum_name.to_string(), "_", Lowercase::Unicode, &[])
+        ),
+        enum_name.span(),
+    );
+    let values = discriminant_values(enum_name, variants);
+    let calls = ordered_variants.iter().map(|variant_ident| {
+        let value = variants
+            .iter()
+            .position(|x| x.ident == *variant_ident)
+            .and_then(|index| values[index]);
+        let discriminant = match value {
+            Some(value) => proc_macro2::Literal::i128_unsuffixed(value).to_token_stream(),
+            None => quote! { (#enum_name::#variant_ident as #int_type) },
+        };
+        let variant_name = variant_ident.to_string();
+        quote! { $callback!(#enum_name::#variant_ident, #variant_name, #discriminant); }
+    });
+    quote! {
+        /// Invokes `$callback!(path, name, discriminant)` once for every variant, in ordinal order
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            ($callback:ident) => {
+                #(#calls)*
+            };
+        }
+    }
+}
+
+/// Returns the smallest and largest values of an integer type. `u128::MAX` is capped at `i128::MAX`.
+fn int_type_bounds(int_type: &str) -> (i128, i128) {
+    match int_type {
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "u8" => (0, u8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "u16" => (0, u16::MAX as i128),
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "u32" => (0, u32::MAX as i128),
+        "i64" | "isize" => (i64::MIN as i128, i64::MAX as i128),
+        "u64" | "usize" => (0, u64::MAX as i128),
+        "i128" => (i128::MIN, i128::MAX),
+        _ => (0, i128::MAX),
+    }
+}
+
+/// Generates `from_range_value()` from the `#[ext(range = ...)]` attributes of the variants.
+///
+/// The bounds must be integer literals within the first `IntType`, and the ranges of different variants can't overlap.
+/// Returns an empty stream if no variant has a range.
+fn make_range_fns(
+    variant_args: &[(Ident, VariantArgs)],
+    int_type_str: &str,
+    int_type: &TokenStream2,
+) -> Result<TokenStream2, EnumMacroError> {
+    let (type_min, type_max) = int_type_bounds(int_type_str);
+    let mut ranges = Vec::new();
+    for (variant_ident, args) in variant_args {
+        let Some(range) = &args.range else {
+            continue;
+        };
+        let bound = |expr: &Option<Box<Expr>>, default: i128| match expr {
+            None => Ok(default),
+            Some(expr) => eval_int_expr(expr, &|_| None)
+                .filter(|value| (type_min..=type_max).contains(value))
+                .ok_or_else(|| {
+                    EnumMacroError::VariantError(format!(
+                        "variant '{}': range bounds must be integer literals that fit in {}",
+                        variant_ident, int_type_str
+                    ))
+                }),
+        };
+        let start = bound(&range.start, type_min)?;
+        let end = match (&range.limits, &range.end) {
+            (syn::RangeLimits::HalfOpen(_), Some(_)) => bound(&range.end, type_max)? - 1,
+            _ => bound(&range.end, type_max)?,
+        };
+        if start > end {
+            return Err(EnumMacroError::VariantError(format!(
+                "variant '{}': range is empty",
+                variant_ident
+            )));
+        }
+        ranges.push((variant_ident, start, end));
+    }
+    if ranges.is_empty() {
+        return Ok(TokenStream2::new());
+    }
+
+    let mut sorted = ranges.clone();
+    sorted.sort_by_key(|(_, start, _)| *start);
+    for pair in sorted.windows(2) {
+        if pair[1].1 <= pair[0].2 {
+            return Err(EnumMacroError::VariantError(format!(
+                "variants '{}' and '{}' have overlapping ranges",
+                pair[0].0, pair[1].0
+            )));
+        }
+    }
+
+    let mut arms = TokenStream2::new();
+    for (variant_ident, start, end) in ranges {
+        let start = int_literal_expr(start, int_type_str)?;
+        let end = int_literal_expr(end, int_type_str)?;
+        arms.extend(quote! { #start..=#end => Some(Self::#variant_ident), });
+    }
+    Ok(quote! {
+        /// Returns the variant whose `#[ext(range = ...)]` contains `val`, or None if no range does
+        #[allow(unreachable_patterns)]
+        pub const fn from_range_value(val: #int_type) -> Option<Self> {
+            match val {
+                #arms
+                _ => None,
+            }
+        }
+    })
+}
+
+/// Generates `as_nonzero_<IntType>()` and `from_nonzero_<IntType>()` if every discriminant is known and non-zero.
+///
+/// Also asserts at compile time that `Option<EnumName>` is no bigger than the enum, since zero is never a valid
+/// discriminant and can stand for None.
+fn make_nonzero_fns(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    int_type_str: &str,
+    int_type: &TokenStream2,
+) -> Result<(TokenStream2, TokenStream2), EnumMacroError> {
+    let values = discriminant_values(enum_name, variants);
+    let all_non_zero = values.iter().all(|value| {
+        value
+            .and_then(|value| wrap_to_int_type(value, int_type_str))
+            .is_some_and(|value| value != 0)
+    });
+    if !all_non_zero {
+        return Ok((TokenStream2::new(), TokenStream2::new()));
+    }
+
+    let mut type_chars = int_type_str.chars();
+    let nonzero_type = Ident::new(
+        &format!(
+            "NonZero{}{}",
+            type_chars.next().unwrap_or_default().to_ascii_uppercase(),
+            type_chars.as_str()
+        ),
+        Span::call_site(),
+    );
+    let as_fn_name = Ident::new(&format!("as_nonzero_{}", int_type_str), Span::call_site());
+    let from_fn_name = Ident::new(&format!("from_nonzero_{}", int_type_str), Span::call_site());
+    let from_int_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+    let mut arms = TokenStream2::new();
+    for (variant, value) in variants.iter().zip(values) {
+        let variant_ident = &variant.ident;
+        let value = int_literal_expr(value.unwrap_or_default(), int_type_str)?;
+        arms.extend(quote! { #enum_name::#variant_ident => #value, });
+    }
+    let fns = quote! {
+        /// Returns the discriminant as a non-zero integer. Every discriminant is non-zero, so `Option<Self>` is no
+        /// bigger than `Self`
+        pub const fn #as_fn_name(&self) -> ::core::num::#nonzero_type {
+            let value: #int_type = match self {
+                #arms
+            };
+            match ::core::num::#nonzero_type::new(value) {
+                Some(value) => value,
+                None => unreachable!(),
+            }
+        }
+        /// Returns the variant with the given non-zero discriminant
+        pub const fn #from_fn_name(value: ::core::num::#nonzero_type) -> Option<Self> {
+            Self::#from_int_fn_name(value.get())
+        }
+    };
+    let size_check = quote! {
+        const _: () = assert!(
+            ::core::mem::size_of::<Option<#enum_name>>() == ::core::mem::size_of::<#enum_name>()
+        );
+    };
+    Ok((fns, size_check))
+}
+
+/// Generates `table_string()`, rendering the name, ordinal, discriminant and doc comment of every variant as an aligned
+/// plain-text table.
+fn make_table_fns(
+    variants: &Punctuated<Variant, Comma>,
+    ordered_variants: &[Ident],
+) -> TokenStream2 {
+    let descriptions = ordered_variants.iter().map(|variant_ident| {
+        variants
+            .iter()
+            .find(|variant| variant.ident == *variant_ident)
+            .map(variant_description)
+            .unwrap_or_default()
+    });
+    let variant_count = ordered_variants.len();
+    quote! {
+        /// Returns the name, ordinal, discriminant and description (doc comment) of every variant as an aligned table
+        /// * For example:
+        /// ```text
+        /// Name | Ordinal | Value | Description
+        /// -----+---------+-------+---------------
+        /// Open | 0       | 1     | Waiting on work
+        /// Done | 1       | 2     |
+        /// ```
+        pub fn table_string() -> ::std::string::String {
+            const DESCRIPTIONS: [&str; #variant_count] = [#(#descriptions),*];
+            let header = ["Name", "Ordinal", "Value", "Description"].map(::std::string::String::from);
+            let rows = Self::ENTRIES
+                .iter()
+                .zip(DESCRIPTIONS)
+                .map(|((name, ordinal, value), description)| {
+                    [
+                        ::std::string::ToString::to_string(name),
+                        ::std::string::ToString::to_string(ordinal),
+                        ::std::string::ToString::to_string(value),
+                        ::std::string::ToString::to_string(description),
+                    ]
+                })
+                .collect::<::std::vec::Vec<_>>();
+            let mut widths = header.clone().map(|cell| cell.chars().count());
+            for row in &rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+            let format_row = |cells: &[::std::string::String; 4]| {
+                // no trailing separator when there's no description
+                let used = if cells[3].is_empty() { 3 } else { 4 };
+                let cells = cells[..used]
+                    .iter()
+                    .zip(widths)
+                    .map(|(cell, width)| ::std::format!("{:<width$}", cell, width = width))
+                    .collect::<::std::vec::Vec<_>>();
+                ::std::string::String::from(cells.join(" | ").trim_end())
+            };
+            let mut lines = ::std::vec![format_row(&header)];
+            lines.push(widths.map(|width| "-".repeat(width)).join("-+-"));
+            lines.extend(rows.iter().map(format_row));
+            lines.join("\n")
+        }
+    }
+}
+
+/// Returns `s` as a quoted JSON string.
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Generates `options_json()`, describing every variant for a UI select component.
+///
+/// The values are the discriminants if the enum has an `IntType` and every discriminant is known at macro time,
+/// otherwise the variant names as declared.
+fn make_options_json(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    ordered_variants: &[Ident],
+    int_type_str: &str,
+    int_type_added: bool,
+    acronyms: &[String],
+) -> TokenStream2 {
+    let values = discriminant_values(enum_name, variants)
+        .into_iter()
+        .map(|value| value.and_then(|value| wrap_to_int_type(value, int_type_str)))
+        .collect::<Vec<_>>();
+    let use_values = int_type_added && values.iter().all(Option::is_some);
+    let options = ordered_variants
+        .iter()
+        .filter_map(|variant_ident| {
+            let index = variants
+                .iter()
+                .position(|variant| variant.ident == *variant_ident)?;
+            let value = match values[index] {
+                Some(value) if use_values => value.to_string(),
+                _ => json_string(&variant_ident.to_string()),
+            };
+            Some(format!(
+                "{{\"value\":{},\"label\":{},\"description\":{}}}",
+                value,
+                json_string(&split_pascal_case(&variant_ident.to_string(), acronyms)),
+                json_string(&variant_description(&variants[index]))
+            ))
+        })
+        .collect::<Vec<_>>();
+    let json = format!("[{}]", options.join(","));
+    quote! {
+        /// Returns every variant as a JSON array of `{value, label, description}` objects, in ordinal order, ready for a
+        /// UI select component
+        /// * The value is the discriminant if the enum has an `IntType`, otherwise the variant name
+        /// * The label is the pascal_spaced name and the description is the doc comment
+        /// * For example, `[{"value":1,"label":"In QA","description":"Being tested"}]`
+        pub const fn options_json() -> &'static str {
+            #json
+        }
+    }
+}
+
+/// Checks the declaration order of the variants for `enum_def(RequireSorted = "name" | "value")`.
+///
+/// Returns a `compile_error!` spanned to the first variant that's out of order, or an empty stream if they're sorted.
+fn check_sorted(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    sort_by: &str,
+) -> TokenStream2 {
+    let values = discriminant_values(enum_name, variants);
+    let variants = variants.iter().collect::<Vec<_>>();
+    let error = if sort_by == "name" {
+        variants.windows(2).find_map(|pair| {
+            let (previous, next) = (pair[0].ident.to_string(), pair[1].ident.to_string());
+            (next.to_lowercase() < previous.to_lowercase()).then(|| {
+                (
+                    &pair[1].ident,
+                    format!(
+                        "{}::{} should be declared before {}, RequireSorted = \"name\" keeps variants in alphabetical order",
+                        enum_name, next, previous
+                    ),
+                )
+            })
+        })
+    } else {
+        let unknown = variants
+            .iter()
+            .zip(&values)
+            .find(|(_, value)| value.is_none());
+        match unknown {
+            Some((variant, _)) => Some((
+                &variant.ident,
+                format!(
+                    "the discriminant of {}::{} can't be worked out at compile time, so RequireSorted = \"value\" can't check it",
+                    enum_name, variant.ident
+                ),
+            )),
+            None => variants.windows(2).zip(values.windows(2)).find_map(|(pair, values)| {
+                (values[1] <= values[0]).then(|| {
+                    (
+                        &pair[1].ident,
+                        format!(
+                            "{}::{} should be declared before {}, RequireSorted = \"value\" keeps discriminants ascending",
+                            enum_name, pair[1].ident, pair[0].ident
+                        ),
+                    )
+                })
+            }),
+        }
+    };
+    match error {
+        Some((variant_ident, message)) => {
+            quote::quote_spanned! { variant_ident.span() => compile_error!(#message); }
+        }
+        None => TokenStream2::new(),
+    }
+}
+
+/// Generates `custom_case()` and `from_custom_case()` for `enum_def(CustomCase = "...")`, joining the lowercased words
+/// of each variant name with the separator.
+fn make_custom_case_fns(
+    enum_name: &Ident,
+    ordered_variants: &[Ident],
+    separator: &str,
+    lowercase: Lowercase,
+    acronyms: &[String],
+) -> TokenStream2 {
+    let mut to_custom = TokenStream2::new();
+    let mut from_custom = TokenStream2::new();
+    for variant_ident in ordered_variants {
+        let custom_str =
+            join_pascal_words(&variant_ident.to_string(), separator, lowercase, acronyms);
+        to_custom.extend(quote! {
+            #enum_name::#variant_ident => #custom_str,
+        });
+        from_custom.extend(quote! {
+            #custom_str => Some(#enum_name::#variant_ident),
+        });
+    }
+    let to_doc = format!(
+        " * For example, MyEnum::InQA.custom_case() returns \"{}\"",
+        join_pascal_words("InQA", separator, lowercase, &[])
+    );
+    quote! {
+        /// Returns the variant name with its lowercased words joined by the `CustomCase` separator
+        #[doc = #to_doc]
+        pub const fn custom_case(&self) -> &'static str {
+            match self {
+                #to_custom
+            }
+        }
+        /// Returns the variant from the name in the `CustomCase` style
+        pub fn from_custom_case(s: &str) -> Option<Self> {
+            match s {
+                #from_custom
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Generates `proto_name()` and `from_proto_name()`, the variant names in the protobuf convention: SCREAMING_SNAKE_CASE
+/// prefixed with the enum name, e.g. `MY_ENUM_IN_QA`, or with `ProtoPrefix`.
+fn make_proto_name_fns(
+    enum_name: &Ident,
+    ordered_variants: &[Ident],
+    prefix: &Option<LitStr>,
+    lowercase: Lowercase,
+    acronyms: &[String],
+) -> TokenStream2 {
+    let prefix = match prefix {
+        Some(prefix) => prefix.value(),
+        None => format!(
+            "{}_",
+            to_screaming_snake_case(&enum_name.to_string(), lowercase, &[])
+        ),
+    };
+    let mut to_proto = TokenStream2::new();
+    let mut from_proto = TokenStream2::new();
+    for variant_ident in ordered_variants {
+        let proto_str = format!(
+            "{}{}",
+            prefix,
+            to_screaming_snake_case(&variant_ident.to_string(), lowercase, acronyms)
+        );
+        to_proto.extend(quote! {
+            #enum_name::#variant_ident => #proto_str,
+        });
+        from_proto.extend(quote! {
+            #proto_str => Some(#enum_name::#variant_ident),
+        });
+    }
+    let to_doc = format!(" * The names are prefixed with `{}`", prefix);
+    quote! {
+        /// Returns the protobuf name of the variant: the SCREAMING_SNAKE_CASE name with the enum's prefix, as used by
+        /// the protobuf JSON mapping and gRPC
+        #[doc = #to_doc]
+        pub const fn proto_name(&self) -> &'static str {
+            match self {
+                #to_proto
+            }
+        }
+        /// Returns the variant from its protobuf name, the reverse of [`Self::proto_name`]
+        pub fn from_proto_name(s: &str) -> Option<Self> {
+            match s {
+                #from_proto
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Appends `code()` and `from_code()` if the variants declare `#[ext(code = '...')]`.
+///
+/// If any variant has a code, every variant must have one, and codes must be unique.
+pub(crate) fn append_code_fns(
+    fns: &mut TokenStream2,
+    enum_name: &Ident,
+    variant_args: &[(Ident, VariantArgs)],
+) -> Result<(), EnumMacroError> {
+    if variant_args.iter().all(|(_, args)| args.code.is_none()) {
+        return Ok(());
+    }
+
+    let mut to_code = TokenStream2::new();
+    let mut from_code = TokenStream2::new();
+    let mut seen = Vec::new();
+    for (variant_ident, args) in variant_args {
+        let code = args.code.as_ref().ok_or_else(|| {
+            EnumMacroError::VariantError(format!(
+                "variant '{}' is missing #[ext(code = ...)]: if one variant has a code, all variants must",
+                variant_ident
+            ))
+        })?;
+        if seen.contains(&code.value()) {
+            return Err(EnumMacroError::VariantError(format!(
+                "variant '{}' has a duplicate code {:?}",
+                variant_ident,
+                code.value()
+            )));
+        }
+        seen.push(code.value());
+        to_code.extend(quote! {
+            #enum_name::#variant_ident => #code,
+        });
+        from_code.extend(quote! {
+            #code => Some(#enum_name::#variant_ident),
+        });
+    }
+
+    fns.extend(quote! {
+        /// Returns the character code of the variant, as declared with `#[ext(code = '...')]`
+        #[inline]
+        pub const fn code(&self) -> char {
+            match self {
+                #to_code
+            }
+        }
+        /// Returns the variant with the given character code
+        #[inline]
+        pub const fn from_code(c: char) -> Option<Self> {
+            match c {
+                #from_code
+                _ => None,
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Generates `sort_key()` and `sorted_by_key()` if the variants declare `#[ext(sort_key = ...)]`.
+///
+/// If one variant has a sort key, every variant must have one, and the keys must be all integers or all strings.
+/// Variants with equal keys keep their ordinal order.
+pub(crate) fn append_sort_key_fns(
+    fns: &mut TokenStream2,
+    enum_name: &Ident,
+    variant_args: &[(Ident, VariantArgs)],
+    ordered_variants: &[Ident],
+) -> Result<(), EnumMacroError> {
+    if variant_args.iter().all(|(_, args)| args.sort_key.is_none()) {
+        return Ok(());
+    }
+
+    let mut keys = Vec::new();
+    for variant_ident in ordered_variants {
+        let key = variant_args
+            .iter()
+            .find(|(ident, _)| ident == variant_ident)
+            .and_then(|(_, args)| args.sort_key.clone())
+            .ok_or_else(|| {
+                EnumMacroError::VariantError(format!(
+                    "variant '{}' is missing #[ext(sort_key = ...)]: if one variant has a sort key, all variants must",
+                    variant_ident
+                ))
+            })?;
+        keys.push((key, variant_ident));
+    }
+    let is_int = matches!(keys[0].0, SortKey::Int(_));
+    if keys
+        .iter()
+        .any(|(key, _)| matches!(key, SortKey::Int(_)) != is_int)
+    {
+        return Err(EnumMacroError::VariantError(
+            "sort keys must be either all integers or all strings".to_string(),
+        ));
+    }
+
+    let mut to_key = TokenStream2::new();
+    for (key, variant_ident) in &keys {
+        let key = match key {
+            SortKey::Int(value) => int_literal_expr(*value, "i64")?.to_token_stream(),
+            SortKey::Str(value) => value.to_token_stream(),
+        };
+        to_key.extend(quote! {
+            #enum_name::#variant_ident => #key,
+        });
+    }
+    keys.sort_by(|a, b| a.0.cmp(&b.0));
+    let sorted = keys.iter().map(|(_, variant_ident)| variant_ident);
+    let count = keys.len();
+    let key_type = if is_int {
+        quote! { i64 }
+    } else {
+        quote! { &'static str }
+    };
+
+    fns.extend(quote! {
+        const SORTED_BY_KEY: [#enum_name; #count] = [#(#enum_name::#sorted),*];
+        /// Returns the sort key of the variant, as declared with `#[ext(sort_key = ...)]`
+        #[inline]
+        pub const fn sort_key(&self) -> #key_type {
+            match self {
+                #to_key
+            }
+        }
+        /// Returns the variants sorted by their `#[ext(sort_key = ...)]`, independent of the declaration order and
+        /// discriminant values
+        pub const fn sorted_by_key() -> &'static [Self] {
+            &Self::SORTED_BY_KEY
+        }
+    });
+
+    Ok(())
+}
+
+/// Generates an enum with variants that carry data, such as `Move { x: i32, y: i32 }` or `Write(String)`.
+///
+/// Variants with data can't be listed, iterated or converted from integers, so only the functions that look at a
+/// value are generated: `count()`, `ordinal()`, the names, and the kind comparisons built on
+/// `core::mem::discriminant`.
+///
+/// With a primitive representation (`#[repr(u8)]`, `#[repr(C, u8)]` or `IntType`), the variants can have explicit
+/// discriminants, which are checked against the type, and a const `as_<IntType>()` is generated. `Acronyms` applies
+/// to the names, and the other `enum_def` arguments are ignored.
+///
+/// Lifetime parameters are carried over to the enum and its impl block, so variants can borrow their data, e.g.
+/// `Token<'a> { Ident(&'a str), Num(i64) }`.
+fn generate_complex_enum(
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    generics: &Generics,
+    variants: &Punctuated<Variant, Comma>,
+    args: EnumDefArgs,
+    define_enum: bool,
+) -> Result<TokenStream2, EnumMacroError> {
+    let acronyms = acronym_list(&args.acronyms);
+    let repr_int = repr_int_type(&attrs);
+    let int_type_str = match (&args.int_type, &repr_int) {
+        (Some(lit_str), repr_int) => {
+            let value = lit_str.value();
+            let int_type_str = match split_int_types(&value)[0] {
+                "auto" => smallest_int_type(&name, variants)?.to_string(),
+                int_type_str => int_type_str.to_string(),
+            };
+            if repr_int
+                .as_ref()
+                .is_some_and(|repr_int| *repr_int != int_type_str)
+            {
+                return Err(EnumMacroError::ParseError(format!(
+                    "IntType {} conflicts with #[repr({})] on {}",
+                    int_type_str,
+                    repr_int.as_deref().unwrap_or_default(),
+                    name
+                )));
+            }
+            Some(int_type_str)
+        }
+        (None, repr_int) => repr_int.clone(),
+    };
+
+    let mut enum_body = TokenStream2::new();
+    let mut to_ordinal = TokenStream2::new();
+    let mut to_variant_name = TokenStream2::new();
+    let mut to_pascal_split = TokenStream2::new();
+    let mut to_snake = TokenStream2::new();
+    let mut to_kebab = TokenStream2::new();
+    for (ordinal, variant) in variants.iter().enumerate() {
+        let (_, other_attrs) = process_variant_attributes(variant)?;
+        let mut variant = variant.clone();
+        variant.attrs = other_attrs;
+        enum_body.extend(quote! { #variant, });
+
+        // `{ .. }` matches unit, tuple and struct variants alike
+        let variant_ident = &variant.ident;
+        let pattern = quote! { #name::#variant_ident { .. } };
+        let variant_name_str = variant_ident.to_string();
+        let pascal_split_str = split_pascal_case(&variant_name_str, &acronyms);
+        let snake_str = to_snake_case(&variant_name_str, Lowercase::Unicode, &acronyms);
+        let kebab_str = to_kebab_case(&variant_name_str, Lowercase::Unicode, &acronyms);
+        to_ordinal.extend(quote! { #pattern => #ordinal, });
+        to_variant_name.extend(quote! { #pattern => #variant_name_str, });
+        to_pascal_split.extend(quote! { #pattern => #pascal_split_str, });
+        to_snake.extend(quote! { #pattern => #snake_str, });
+        to_kebab.extend(quote! { #pattern => #kebab_str, });
+    }
+    let variant_count = variants.len();
+    let variant_idents = variants
+        .iter()
+        .map(|variant| variant.ident.clone())
+        .collect::<Vec<_>>();
+    let is_variant_fns = make_is_variant_fns(&name, &variant_idents, &acronyms)?;
+    let as_ref_impl = make_as_ref_impl(&name, generics, &check_derive_traits(&attrs));
+
+    let mut repr = TokenStream2::new();
+    let mut as_int_fn = TokenStream2::new();
+    if let Some(int_type_str) = &int_type_str {
+        let int_type = syn::parse_str::<syn::Type>(int_type_str)
+            .map_err(|error| EnumMacroError::ParseError(format!("Invalid IntType: {}", error)))?;
+        if repr_int.is_none() {
+            repr = quote! { #[repr(#int_type)] };
+        }
+        let values = discriminant_values(&name, variants);
+        for (variant, value) in variants.iter().zip(&values) {
+            if let Some(value) = value {
+                if wrap_to_int_type(*value, int_type_str) != Some(*value) {
+                    return Err(EnumMacroError::VariantError(format!(
+                        "the discriminant {} of {}::{} doesn't fit in {}",
+                        value, name, variant.ident, int_type_str
+                    )));
+                }
+            }
+        }
+
+        let as_fn_name = Ident::new(&format!("as_{}", int_type_str), Span::call_site());
+        let as_body = if values.iter().all(Option::is_some) {
+            let mut arms = TokenStream2::new();
+            for (variant, value) in variants.iter().zip(values) {
+                let variant_ident = &variant.ident;
+                let value = int_literal_expr(value.unwrap_or_default(), int_type_str)?;
+                arms.extend(quote! { #name::#variant_ident { .. } => #value, });
+            }
+            quote! {
+                match self {
+                    #arms
+                }
+            }
+        } else {
+            quote! {
+                // SAFETY: a primitive representation puts the discriminant first, as that integer type (RFC 2195)
+                unsafe { *(self as *const Self).cast::<#int_type>() }
+            }
+        };
+        as_int_fn = quote! {
+            /// Returns the discriminant of the variant, whatever data it carries
+            #[inline]
+            pub const fn #as_fn_name(&self) -> #int_type {
+                #as_body
+            }
+        };
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut expanded_enum = TokenStream2::new();
+    if define_enum {
+        expanded_enum.extend(quote! {
+            #(#attrs)*
+            #repr
+            #vis enum #name #generics #where_clause {
+                #enum_body
+            }
+        });
+    }
+    expanded_enum.extend(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #as_int_fn
+            #is_variant_fns
+
+            /// Returns the number of variants in the enum
+            #[inline]
+            pub const fn count() -> usize {
+                #variant_count
+            }
+            /// Returns the ordinal of the variant, its position in the declaration
+            pub const fn ordinal(&self) -> usize {
+                match self {
+                    #to_ordinal
+                }
+            }
+            /// Returns the name of the variant as declared
+            pub const fn variant_name(&self) -> &'static str {
+                match self {
+                    #to_variant_name
+                }
+            }
+            /// Returns the name of the variant as declared, the same as `variant_name()`
+            #[inline]
+            pub const fn as_str(&self) -> &'static str {
+                self.variant_name()
+            }
+            /// Returns the variant name in spaced PascalCase
+            /// * For example, MyEnum::InQA(..).pascal_spaced() returns "In QA"
+            pub const fn pascal_spaced(&self) -> &'static str {
+                match self {
+                    #to_pascal_split
+                }
+            }
+            /// Returns the variant name in snake_case
+            pub const fn snake_case(&self) -> &'static str {
+                match self {
+                    #to_snake
+                }
+            }
+            /// Returns the variant name in kebab-case
+            pub const fn kebab_case(&self) -> &'static str {
+                match self {
+                    #to_kebab
+                }
+            }
+            /// Returns true if `self` and `other` are the same variant, whatever data they carry
+            /// * For example, MyEnum::Write("a".into()).matches_kind(&MyEnum::Write("b".into())) returns true
+            #[inline]
+            pub fn matches_kind(&self, other: &Self) -> bool {
+                ::core::mem::discriminant(self) == ::core::mem::discriminant(other)
+            }
+            /// Returns the index of the first item in `list` that's the same variant as `self`, whatever data it carries
+            pub fn position_in(&self, list: &[Self]) -> Option<usize> {
+                list.iter().position(|x| self.matches_kind(x))
+            }
+        }
+
+        #as_ref_impl
+    });
+    Ok(expanded_enum)
+}
+
+/// Generates an enum without variants, such as a placeholder in generated code.
+///
+/// There are no variants to convert, so only `list()`, `count()`, `iter()` and `pretty_print()` are generated.
+/// The `enum_def` arguments are ignored, since a `repr` isn't allowed on an empty enum.
+fn generate_empty_enum(
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    define_enum: bool,
+) -> TokenStream2 {
+    let pretty_print_body = make_pretty_print(
+        attrs.clone(),
+        TokenStream2::new(),
+        vis.clone(),
+        name.clone(),
+        TokenStream2::new(),
+        TokenStream2::new(),
+    );
+
+    let mut expanded_enum = TokenStream2::new();
+    if define_enum {
+        expanded_enum.extend(quote! {
+            #(#attrs)*
+            #vis enum #name {}
+        });
+    }
+    expanded_enum.extend(quote! {
+        impl #name {
+            /// Returns an array of all variants in the enum, which is always empty
+            #[inline]
+            pub const fn list() -> [#name; 0] {
+                []
+            }
+            /// Returns all variants as a `'static` slice, which is always empty
+            #[inline]
+            pub const fn all() -> &'static [#name] {
+                &[]
+            }
+            /// Returns the number of variants in the enum, which is always 0
+            #[inline]
+            pub const fn count() -> usize {
+                0
+            }
+            /// Returns an iterator over the variants in the enum, which never yields anything
+            pub fn iter() -> impl Iterator<Item = &'static #name> {
+                const LIST: [#name; 0] = #name::list();
+                LIST.iter()
+            }
+
+            /// Returns a pretty printed string of the enum definition
+            pub const fn pretty_print() -> &'static str {
+                #pretty_print_body
+            }
+        }
+    });
+    expanded_enum
+}
+
+/// Constructs the pretty print string for the enum.
+pub(crate) fn make_pretty_print(
+    attrs: Vec<Attribute>,
+    needed_derives: TokenStream2,
+    vis: Visibility,
+    name: Ident,
+    enum_body: TokenStream2,
+    repl_value: TokenStream2,
+) -> String {
+    let mut pretty_print_body = Vec::new();
+    let attrs = (quote! { #(#attrs)* }).to_string().trim().to_owned();
+    if !attrs.is_empty() {
+        pretty_print_body.push(attrs);
+        pretty_print_body.push("\n".to_owned());
+    }
+    let needed_derives = (quote! { #needed_derives }).to_string().trim().to_owned();
+    if !needed_derives.is_empty() {
         pretty_print_body.push(needed_derives);
         pretty_print_body.push("\n".to_owned());
     }
-    let repl_value = (quote! { #repl_value }).to_string().trim().to_owned();
-    if !repl_value.is_empty() {
-        pretty_print_body.push(repl_value);
-        pretty_print_body.push("\n".to_owned());
+    let repl_value = (quote! { #repl_value }).to_string().trim().to_owned();
+    if !repl_value.is_empty() {
+        pretty_print_body.push(repl_value);
+        pretty_print_body.push("\n".to_owned());
+    }
+    let decla = (quote! { #vis enum #name }).to_string().trim().to_owned();
+    pretty_print_body.push(decla);
+    if enum_body.is_empty() {
+        pretty_print_body.push(" {}".to_owned());
+        return pretty_print_body.join("");
+    }
+    pretty_print_body.push(" {\n".to_owned());
+
+    let enum_body = (quote! { #enum_body })
+        .to_string()
+        .trim()
+        .split(",")
+        .map(|x| x.trim())
+        .collect::<Vec<&str>>()
+        .join(",\n    ")
+        .trim()
+        .to_owned();
+
+    pretty_print_body.push("    ".to_owned());
+    pretty_print_body.push(enum_body);
+
+    pretty_print_body.push("\n}".to_owned());
+
+    pretty_print_body.join("")
+}
+
+/// Generates `core::iter::Step` so ranges of variants (`MyEnum::A..=MyEnum::C`) can be iterated.
+///
+/// Steps follow the ordinals of the variants. Requires a nightly compiler with `#![feature(step_trait)]`.
+#[cfg(feature = "step_trait")]
+fn make_step_impl(enum_name: &Ident, ordinal_type: &TokenStream2) -> TokenStream2 {
+    quote! {
+        impl ::core::iter::Step for #enum_name {
+            #[inline]
+            fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+                match (end.ordinal() as usize).checked_sub(start.ordinal() as usize) {
+                    Some(steps) => (steps, Some(steps)),
+                    None => (0, None),
+                }
+            }
+            #[inline]
+            fn forward_checked(start: Self, count: usize) -> Option<Self> {
+                match (start.ordinal() as usize).checked_add(count) {
+                    Some(ord) => Self::from_ordinal(<#ordinal_type>::try_from(ord).ok()?),
+                    None => None,
+                }
+            }
+            #[inline]
+            fn backward_checked(start: Self, count: usize) -> Option<Self> {
+                match (start.ordinal() as usize).checked_sub(count) {
+                    Some(ord) => Self::from_ordinal(<#ordinal_type>::try_from(ord).ok()?),
+                    None => None,
+                }
+            }
+        }
+    }
+}
+
+/// What the serde impls write for the variants, in ordinal order.
+#[cfg(any(feature = "ts", feature = "avro"))]
+enum SerdeNames {
+    /// The names as declared, or converted with `Serde = "<case>"`
+    Names(Vec<String>),
+    /// The discriminants, with `Serde = "int"` or `Embedded`. None if one can't be evaluated at macro time
+    // Avro only checks for them
+    #[cfg_attr(not(feature = "ts"), allow(dead_code))]
+    Ints(Option<Vec<i128>>),
+}
+
+/// Returns what the serde impls write for each variant, so the exported schemas match the wire: the discriminants if
+/// `int_serde` (`Serde = "int"` or `Embedded`), or else the names, converted with `Serde = "<case>"` if it's set.
+#[cfg(any(feature = "ts", feature = "avro"))]
+fn serde_names(
+    serde: &Option<LitStr>,
+    int_serde: bool,
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    ordered_variants: &[Ident],
+    lowercase: Lowercase,
+    acronyms: &[String],
+) -> SerdeNames {
+    if int_serde {
+        let values = discriminant_values(enum_name, variants);
+        let ints = ordered_variants
+            .iter()
+            .map(|variant_ident| {
+                let index = variants
+                    .iter()
+                    .position(|variant| variant.ident == *variant_ident)?;
+                values[index]
+            })
+            .collect();
+        return SerdeNames::Ints(ints);
+    }
+    SerdeNames::Names(
+        ordered_variants
+            .iter()
+            .map(|variant_ident| {
+                let name = variant_ident.to_string();
+                match serde {
+                    Some(case) => case_name(&name, &case.value(), lowercase, acronyms),
+                    None => name,
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Converts a PascalCase name with one of the [`NAME_CASES`], the same way the generated `<case>()` does.
+#[cfg(any(feature = "ts", feature = "avro"))]
+fn case_name(s: &str, case: &str, lowercase: Lowercase, acronyms: &[String]) -> String {
+    match case {
+        "pascal_spaced" => split_pascal_case(s, acronyms),
+        "snake_case" => to_snake_case(s, lowercase, acronyms),
+        "kebab_case" => to_kebab_case(s, lowercase, acronyms),
+        "sentence_case" => to_sentence_case(s, lowercase, acronyms),
+        "title_case" => to_title_case(s, lowercase, acronyms),
+        "dot_case" => to_dot_case(s, lowercase, acronyms),
+        "flat_case" => to_flat_case(s, lowercase),
+        "upper_flat" => to_upper_flat_case(s, lowercase),
+        _ => s.to_string(),
+    }
+}
+
+/// Generates `ts_declaration()` and `ts_rs::TS`, exporting the enum as a TypeScript union of what serde writes.
+///
+/// The union holds the variant names, converted with `Serde = "<case>"` if it's set. With `Serde = "int"` or
+/// `Embedded` it holds the discriminants instead, or is just `number` if they can't be evaluated at macro time.
+/// The using crate needs `ts-rs` 10.1 as a dependency.
+#[cfg(feature = "ts")]
+fn make_ts_fns(enum_name: &Ident, serde_names: &SerdeNames) -> (TokenStream2, TokenStream2) {
+    let union = match serde_names {
+        SerdeNames::Names(names) => names
+            .iter()
+            .map(|name| format!("\"{}\"", name))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        SerdeNames::Ints(Some(values)) => values
+            .iter()
+            .map(i128::to_string)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        SerdeNames::Ints(None) => "number".to_string(),
+    };
+    let declaration = format!("type {} = {};", enum_name, union);
+    let type_name = enum_name.to_string();
+    let fns = quote! {
+        /// Returns the TypeScript declaration of the enum as a union of the values serde writes
+        /// * For example, `type MyEnum = "Open" | "Done";`
+        pub const fn ts_declaration() -> &'static str {
+            #declaration
+        }
+    };
+    let impls = quote! {
+        impl ::ts_rs::TS for #enum_name {
+            type WithoutGenerics = Self;
+
+            fn name() -> ::std::string::String {
+                ::std::string::String::from(#type_name)
+            }
+            fn decl() -> ::std::string::String {
+                ::std::string::String::from(Self::ts_declaration())
+            }
+            fn decl_concrete() -> ::std::string::String {
+                Self::decl()
+            }
+            fn inline() -> ::std::string::String {
+                ::std::string::String::from(#union)
+            }
+            fn inline_flattened() -> ::std::string::String {
+                panic!("{} cannot be flattened", #type_name)
+            }
+        }
+    };
+    (fns, impls)
+}
+
+/// Generates `avro_schema()` and `apache_avro::AvroSchema`, describing the enum as an Avro enum of what serde writes.
+///
+/// The symbols are the variant names in ordinal order, converted with `Serde = "<case>"` if it's set. Avro enums
+/// can't hold the discriminants of `Serde = "int"` or `Embedded`, and symbols are limited to `[A-Za-z_][A-Za-z0-9_]*`,
+/// so either is an error.
+/// The using crate needs the `apache-avro` crate as a dependency.
+#[cfg(feature = "avro")]
+fn make_avro_fns(
+    enum_name: &Ident,
+    serde_names: &SerdeNames,
+) -> Result<(TokenStream2, TokenStream2), EnumMacroError> {
+    let SerdeNames::Names(names) = serde_names else {
+        return Err(EnumMacroError::ParseError(format!(
+            "Avro can't describe {}, which serializes the variants as their discriminants",
+            enum_name
+        )));
+    };
+    if let Some(name) = names.iter().find(|name| {
+        let mut chars = name.chars();
+        !chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }) {
+        return Err(EnumMacroError::ParseError(format!(
+            "Avro can't describe {}, whose variant name \"{}\" isn't a valid Avro symbol",
+            enum_name, name
+        )));
+    }
+    let symbols = names
+        .iter()
+        .map(|name| format!("\"{}\"", name))
+        .collect::<Vec<_>>()
+        .join(",");
+    let schema = format!(
+        "{{\"type\":\"enum\",\"name\":\"{}\",\"symbols\":[{}]}}",
+        enum_name, symbols
+    );
+    let fns = quote! {
+        /// Returns the Avro schema of the enum as JSON, with the names serde writes as the symbols
+        /// * For example, `{"type":"enum","name":"MyEnum","symbols":["Open","Done"]}`
+        pub const fn avro_schema() -> &'static str {
+            #schema
+        }
+    };
+    let impls = quote! {
+        impl ::apache_avro::AvroSchema for #enum_name {
+            fn get_schema() -> ::apache_avro::Schema {
+                ::apache_avro::Schema::parse_str(Self::avro_schema())
+                    .expect("the generated Avro schema is valid")
+            }
+        }
+    };
+    Ok((fns, impls))
+}
+
+/// Generates helpers for dictionary-encoded Arrow columns of the enum.
+///
+/// The dictionary values are the variant names in ordinal order and the keys are the ordinals. If the enum has
+/// discriminants of a type Arrow supports, `from_arrow_codes()` also decodes columns of discriminants.
+/// The using crate needs the `arrow` crate as a dependency.
+#[cfg(feature = "arrow")]
+fn make_arrow_fns(
+    int_types: &[(String, TokenStream2)],
+    int_type_added: bool,
+    ordered_variants: &[Ident],
+) -> TokenStream2 {
+    let names = ordered_variants.iter().map(|x| x.to_string());
+    let mut fns = quote! {
+        /// Returns the variant names in ordinal order, as the values of an Arrow dictionary
+        pub fn arrow_dictionary_values() -> ::arrow::array::StringArray {
+            ::arrow::array::StringArray::from(::std::vec![#(#names),*])
+        }
+        /// Dictionary-encodes the variants, with the ordinals as keys into [`Self::arrow_dictionary_values`]
+        pub fn to_arrow_dictionary<'a>(
+            items: impl IntoIterator<Item = &'a Self>,
+        ) -> ::arrow::array::DictionaryArray<::arrow::datatypes::Int32Type>
+        where
+            Self: 'a,
+        {
+            let keys = ::arrow::array::Int32Array::from_iter_values(
+                items.into_iter().map(|x| x.ordinal() as i32),
+            );
+            ::arrow::array::DictionaryArray::new(
+                keys,
+                ::std::sync::Arc::new(Self::arrow_dictionary_values()),
+            )
+        }
+        /// Decodes a dictionary-encoded column by variant name
+        /// * Nulls, unknown names and dictionaries that aren't strings decode as None
+        pub fn from_arrow_dictionary(
+            column: &::arrow::array::DictionaryArray<::arrow::datatypes::Int32Type>,
+        ) -> impl Iterator<Item = Option<Self>> + '_ {
+            let values = ::arrow::array::Array::as_any(column.values().as_ref())
+                .downcast_ref::<::arrow::array::StringArray>();
+            column.keys().iter().map(move |key| {
+                let values = values?;
+                let index = usize::try_from(key?).ok()?;
+                if index >= ::arrow::array::Array::len(values) {
+                    return None;
+                }
+                Self::from_ascii_bytes(values.value(index).as_bytes())
+            })
+        }
+    };
+
+    let int_type_str = &int_types[0].0;
+    let arrow_type = match int_type_str.as_str() {
+        "i8" => Some(quote! { Int8Type }),
+        "u8" => Some(quote! { UInt8Type }),
+        "i16" => Some(quote! { Int16Type }),
+        "u16" => Some(quote! { UInt16Type }),
+        "i32" => Some(quote! { Int32Type }),
+        "u32" => Some(quote! { UInt32Type }),
+        "i64" => Some(quote! { Int64Type }),
+        "u64" => Some(quote! { UInt64Type }),
+        _ => None,
+    };
+    if let (true, Some(arrow_type)) = (int_type_added, arrow_type) {
+        let from_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+        fns.extend(quote! {
+            /// Decodes a column of discriminants, e.g. codes stored as plain integers in Parquet
+            /// * Nulls and unknown discriminants decode as None
+            pub fn from_arrow_codes(
+                codes: &::arrow::array::PrimitiveArray<::arrow::datatypes::#arrow_type>,
+            ) -> impl Iterator<Item = Option<Self>> + '_ {
+                codes.iter().map(|code| Self::#from_fn_name(code?))
+            }
+        });
+    }
+    fns
+}
+
+/// Generates `deku::DekuReader` and `deku::DekuWriter`, so the enum can be a field of a bit-packed deku struct.
+///
+/// The discriminant is read and written big-endian in exactly `bits_required()` bits, so it needs an `IntType`.
+/// The using crate needs `deku` 0.18 as a dependency.
+#[cfg(feature = "deku")]
+fn make_deku_impls(enum_name: &Ident, int_types: &[(String, TokenStream2)]) -> TokenStream2 {
+    let (int_type_str, int_type) = &int_types[0];
+    let as_fn_name = Ident::new(&format!("as_{}", int_type_str), Span::call_site());
+    let from_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+    let enum_name_str = enum_name.to_string();
+    let ctx = quote! {
+        (
+            ::deku::ctx::Endian::Big,
+            ::deku::ctx::BitSize(#enum_name::bits_required() as usize),
+        )
+    };
+    quote! {
+        impl<'a> ::deku::DekuReader<'a, ()> for #enum_name {
+            fn from_reader_with_ctx<R: ::deku::no_std_io::Read + ::deku::no_std_io::Seek>(
+                reader: &mut ::deku::reader::Reader<R>,
+                _: (),
+            ) -> Result<Self, ::deku::DekuError> {
+                let value = <#int_type as ::deku::DekuReader<'a, _>>::from_reader_with_ctx(reader, #ctx)?;
+                Self::#from_fn_name(value).ok_or_else(|| {
+                    ::deku::DekuError::Parse(::std::borrow::Cow::from(::std::format!(
+                        "invalid {} discriminant: {}",
+                        #enum_name_str,
+                        value
+                    )))
+                })
+            }
+        }
+
+        impl ::deku::DekuWriter<()> for #enum_name {
+            fn to_writer<W: ::deku::no_std_io::Write + ::deku::no_std_io::Seek>(
+                &self,
+                writer: &mut ::deku::writer::Writer<W>,
+                _: (),
+            ) -> Result<(), ::deku::DekuError> {
+                ::deku::DekuWriter::to_writer(&self.#as_fn_name(), writer, #ctx)
+            }
+        }
+    }
+}
+
+/// Generates `speedy::Readable` and `speedy::Writable`, encoding the discriminant in the width of the first `IntType`.
+///
+/// The using crate needs the `speedy` crate as a dependency.
+#[cfg(feature = "speedy")]
+fn make_speedy_impls(enum_name: &Ident, int_types: &[(String, TokenStream2)]) -> TokenStream2 {
+    let (int_type_str, int_type) = &int_types[0];
+    let as_fn_name = Ident::new(&format!("as_{}", int_type_str), Span::call_site());
+    let from_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+    let enum_name_str = enum_name.to_string();
+    quote! {
+        impl<'a, C: ::speedy::Context> ::speedy::Readable<'a, C> for #enum_name {
+            fn read_from<R: ::speedy::Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+                let value = reader.read_value::<#int_type>()?;
+                Self::#from_fn_name(value).ok_or_else(|| {
+                    ::speedy::Error::custom(::core::format_args!("invalid {} discriminant: {}", #enum_name_str, value))
+                        .into()
+                })
+            }
+
+            #[inline]
+            fn minimum_bytes_needed() -> usize {
+                ::core::mem::size_of::<#int_type>()
+            }
+        }
+
+        impl<C: ::speedy::Context> ::speedy::Writable<C> for #enum_name {
+            #[inline]
+            fn write_to<W: ?Sized + ::speedy::Writer<C>>(&self, writer: &mut W) -> Result<(), C::Error> {
+                writer.write_value(&self.#as_fn_name())
+            }
+
+            #[inline]
+            fn bytes_needed(&self) -> Result<usize, C::Error> {
+                Ok(::core::mem::size_of::<#int_type>())
+            }
+        }
+    }
+}
+
+/// Generates the conversions between the enum and a flatc-generated FlatBuffers enum, matching variants by name.
+///
+/// flatc enums are newtypes with a const per variant, so converting to one fails to compile if a variant is missing,
+/// and a const assert on `ENUM_VALUES` fails it if the schema has variants the enum doesn't. Converting back is a
+/// `TryFrom`, since a buffer can hold any value; the error is the unknown value.
+#[cfg(feature = "flatbuffers")]
+fn make_flatbuffers_impls(
+    enum_name: &Ident,
+    fb_type: &syn::Path,
+    ordered_variants: &[Ident],
+) -> TokenStream2 {
+    let count_message = format!(
+        "{} and {} don't have the same variants",
+        enum_name,
+        fb_type.to_token_stream().to_string().replace(' ', "")
+    );
+    quote! {
+        const _: () = assert!(#fb_type::ENUM_VALUES.len() == #enum_name::count(), #count_message);
+
+        impl From<#enum_name> for #fb_type {
+            fn from(val: #enum_name) -> Self {
+                match val {
+                    #(#enum_name::#ordered_variants => #fb_type::#ordered_variants,)*
+                }
+            }
+        }
+
+        impl TryFrom<#fb_type> for #enum_name {
+            type Error = #fb_type;
+            fn try_from(val: #fb_type) -> Result<Self, Self::Error> {
+                match val {
+                    #(#fb_type::#ordered_variants => Ok(#enum_name::#ordered_variants),)*
+                    _ => Err(val),
+                }
+            }
+        }
+    }
+}
+
+/// Generates the conversions both ways between the enum and a capnpc-generated Cap'n Proto enum, matching variants by
+/// name.
+///
+/// Both matches are exhaustive, so a variant missing on either side fails to compile.
+#[cfg(feature = "capnp")]
+fn make_capnp_impls(
+    enum_name: &Ident,
+    capnp_type: &syn::Path,
+    ordered_variants: &[Ident],
+) -> TokenStream2 {
+    quote! {
+        impl From<#enum_name> for #capnp_type {
+            fn from(val: #enum_name) -> Self {
+                match val {
+                    #(#enum_name::#ordered_variants => #capnp_type::#ordered_variants,)*
+                }
+            }
+        }
+
+        impl From<#capnp_type> for #enum_name {
+            fn from(val: #capnp_type) -> Self {
+                match val {
+                    #(#capnp_type::#ordered_variants => #enum_name::#ordered_variants,)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `to_jobject()`, `from_jobject()`, `to_jint()` and `from_jint()`, converting to and from the same enum on
+/// the Java side.
+///
+/// Java enum constants are matched by the SCREAMING_SNAKE_CASE name of the variant (`InQA` is `IN_QA`), and the ints
+/// are the ordinals, the same as Java's `ordinal()`. The using crate needs `jni` 0.21 as a dependency.
+#[cfg(feature = "jni")]
+fn make_jni_fns(
+    ordered_variants: &[Ident],
+    lowercase: Lowercase,
+    acronyms: &[String],
+) -> TokenStream2 {
+    let java_names = ordered_variants
+        .iter()
+        .map(|variant_ident| {
+            to_screaming_snake_case(&variant_ident.to_string(), lowercase, acronyms)
+        })
+        .collect::<Vec<_>>();
+    let ordinals = (0..ordered_variants.len() as i32).collect::<Vec<_>>();
+    quote! {
+        /// Returns the constant of the Java enum `class` (e.g. "com/example/Status") with the variant's
+        /// SCREAMING_SNAKE_CASE name
+        /// * For example, MyEnum::InQA.to_jobject(env, "com/example/MyEnum") returns MyEnum.IN_QA
+        pub fn to_jobject<'local>(
+            &self,
+            env: &mut ::jni::JNIEnv<'local>,
+            class: &str,
+        ) -> ::jni::errors::Result<::jni::objects::JObject<'local>> {
+            let name = match self {
+                #(Self::#ordered_variants => #java_names,)*
+            };
+            env.get_static_field(class, name, ::std::format!("L{};", class))?.l()
+        }
+        /// Returns the variant from a Java enum constant by its `name()`, or None if no variant has that name
+        pub fn from_jobject(
+            env: &mut ::jni::JNIEnv<'_>,
+            obj: &::jni::objects::JObject<'_>,
+        ) -> ::jni::errors::Result<Option<Self>> {
+            let name = env.call_method(obj, "name", "()Ljava/lang/String;", &[])?.l()?;
+            let name: ::std::string::String = env.get_string(&::jni::objects::JString::from(name))?.into();
+            Ok(match name.as_str() {
+                #(#java_names => Some(Self::#ordered_variants),)*
+                _ => None,
+            })
+        }
+        /// Returns the ordinal of the variant as a Java int, matching Java's `ordinal()`
+        #[inline]
+        pub const fn to_jint(&self) -> ::jni::sys::jint {
+            self.ordinal() as ::jni::sys::jint
+        }
+        /// Returns the variant from a Java enum ordinal, or None if it's out of range
+        pub const fn from_jint(val: ::jni::sys::jint) -> Option<Self> {
+            match val {
+                #(#ordinals => Some(Self::#ordered_variants),)*
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Generates the gdext `GodotConvert`, `ToGodot`, `FromGodot`, `Var` and `Export` impls, so the enum is an int property
+/// shown as a dropdown of the `pascal_spaced()` names in the Godot editor.
+///
+/// The ints are the discriminants if the enum has an `IntType` and every discriminant is known at macro time and fits
+/// an `i64`, otherwise the ordinals. The using crate needs `godot` 0.2 as a dependency.
+#[cfg(feature = "godot")]
+fn make_godot_impls(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+    ordered_variants: &[Ident],
+    int_type_str: &str,
+    int_type_added: bool,
+    acronyms: &[String],
+) -> TokenStream2 {
+    let values = discriminant_values(enum_name, variants)
+        .into_iter()
+        .map(|value| value.and_then(|value| wrap_to_int_type(value, int_type_str)))
+        .map(|value| value.and_then(|value| i64::try_from(value).ok()))
+        .collect::<Vec<_>>();
+    let use_values = int_type_added && values.iter().all(Option::is_some);
+    let godot_values = ordered_variants
+        .iter()
+        .enumerate()
+        .map(|(ordinal, variant_ident)| {
+            let index = variants
+                .iter()
+                .position(|variant| variant.ident == *variant_ident)
+                .unwrap_or_default();
+            match values[index] {
+                Some(value) if use_values => value,
+                _ => ordinal as i64,
+            }
+        })
+        .collect::<Vec<_>>();
+    let hint_string = ordered_variants
+        .iter()
+        .zip(&godot_values)
+        .map(|(variant_ident, value)| {
+            format!(
+                "{}:{}",
+                split_pascal_case(&variant_ident.to_string(), acronyms),
+                value
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let godot_values = godot_values
+        .into_iter()
+        .map(proc_macro2::Literal::i64_unsuffixed)
+        .collect::<Vec<_>>();
+    let invalid = format!("invalid {} value: {{}}", enum_name);
+    quote! {
+        impl ::godot::meta::GodotConvert for #enum_name {
+            type Via = i64;
+        }
+
+        impl ::godot::meta::ToGodot for #enum_name {
+            type ToVia<'v> = i64;
+            fn to_godot(&self) -> Self::ToVia<'_> {
+                match self {
+                    #(#enum_name::#ordered_variants => #godot_values,)*
+                }
+            }
+        }
+
+        impl ::godot::meta::FromGodot for #enum_name {
+            fn try_from_godot(via: Self::Via) -> Result<Self, ::godot::meta::error::ConvertError> {
+                match via {
+                    #(#godot_values => Ok(#enum_name::#ordered_variants),)*
+                    _ => Err(::godot::meta::error::ConvertError::new(::std::format!(#invalid, via))),
+                }
+            }
+        }
+
+        impl ::godot::register::property::Var for #enum_name {
+            fn get_property(&self) -> Self::Via {
+                ::godot::meta::ToGodot::to_godot(self)
+            }
+            /// Sets the variant from the int, keeping the current one if the int isn't a variant
+            fn set_property(&mut self, value: Self::Via) {
+                if let Ok(value) = ::godot::meta::FromGodot::try_from_godot(value) {
+                    *self = value;
+                }
+            }
+            fn var_hint() -> ::godot::meta::PropertyHintInfo {
+                ::godot::meta::PropertyHintInfo {
+                    hint: ::godot::global::PropertyHint::ENUM,
+                    hint_string: ::godot::builtin::GString::from(#hint_string),
+                }
+            }
+        }
+
+        impl ::godot::register::property::Export for #enum_name {
+            fn export_hint() -> ::godot::meta::PropertyHintInfo {
+                <Self as ::godot::register::property::Var>::var_hint()
+            }
+        }
+    }
+}
+
+/// Generates `find_closest()` and `search()`, fuzzy matching a string against every case form of the variant names.
+///
+/// Scores are the Jaro-Winkler similarity (0.0 to 1.0) of the lowercased strings, taking the best case form.
+/// The using crate needs the `strsim` crate as a dependency.
+#[cfg(feature = "fuzzy")]
+fn make_fuzzy_fns(lowercase: Lowercase) -> TokenStream2 {
+    let lowercase_method = lowercase.str_method();
+    quote! {
+        /// Returns the variant whose name is most similar to `s`, with its score from 0.0 to 1.0
+        /// * For example, MyEnum::find_closest("in-q") returns Some((&MyEnum::InQA, 0.95))
+        pub fn find_closest(s: &str) -> Option<(&'static Self, f64)> {
+            Self::search(s, 0.0).into_iter().next()
+        }
+        /// Returns the variants scoring at least `min_score` (0.0 to 1.0) against `s`, best match first
+        /// * Every case form is compared: the variant name, pascal_spaced, snake_case and kebab-case
+        pub fn search(s: &str, min_score: f64) -> ::std::vec::Vec<(&'static Self, f64)> {
+            let needle = s.#lowercase_method();
+            let mut matches = Self::iter()
+                .map(|x| {
+                    let score = [x.variant_name(), x.pascal_spaced(), x.snake_case(), x.kebab_case()]
+                        .iter()
+                        .map(|form| ::strsim::jaro_winkler(&needle, &form.#lowercase_method()))
+                        .fold(0.0, f64::max);
+                    (x, score)
+                })
+                .filter(|(_, score)| *score >= min_score)
+                .collect::<::std::vec::Vec<_>>();
+            // stable, so equal scores stay in ordinal order
+            matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+            matches
+        }
+    }
+}
+
+/// Generates `ansi_colored_name()`, the variant name wrapped in the ANSI escape codes of its `#[ext(color = "...")]`.
+#[cfg(feature = "ansi")]
+fn make_ansi_fns(variant_args: &[(Ident, VariantArgs)]) -> TokenStream2 {
+    let mut color_codes = TokenStream2::new();
+    for (color, code) in ANSI_COLORS {
+        if variant_args
+            .iter()
+            .any(|(_, args)| args.color.as_ref().is_some_and(|c| c.value() == color))
+        {
+            let code = code.to_string();
+            color_codes.extend(quote! { Some(#color) => #code, });
+        }
+    }
+    quote! {
+        /// Returns the variant name wrapped in the ANSI escape codes of its color, or the plain name without a color
+        /// * For example, MyEnum::InQA.ansi_colored_name() returns "\x1b[33mInQA\x1b[0m" for `#[ext(color = "yellow")]`
+        pub fn ansi_colored_name(&self) -> ::std::string::String {
+            let code = match self.color() {
+                #color_codes
+                _ => return ::std::string::ToString::to_string(self.variant_name()),
+            };
+            ::std::format!("\x1b[{}m{}\x1b[0m", code, self.variant_name())
+        }
+    }
+}
+
+/// Generates `from_config_value()` and, unless the enum already derives it, a `serde::Deserialize` impl that
+/// accepts both the string and the integer forms of a variant.
+///
+/// Config layers disagree on types (an environment override of a TOML integer is a string), so both are accepted.
+/// Integers are the discriminant, or the ordinal if the enum has no discriminants.
+/// The using crate needs the `serde` crate as a dependency.
+#[cfg(feature = "config")]
+fn make_config_fns(
+    enum_name: &Ident,
+    int_types: &[(String, TokenStream2)],
+    int_type_added: bool,
+    variant_from_ordinals: &TokenStream2,
+    derive_deserialize: bool,
+    fallback: &Option<TokenStream2>,
+) -> (TokenStream2, TokenStream2) {
+    let fns = quote! {
+        /// Returns the variant from a config value, accepting anything [`Self::parse`] does and ignoring case and
+        /// surrounding whitespace
+        /// * For example, MyEnum::from_config_value(" IN_QA ") returns Some(MyEnum::InQA)
+        pub fn from_config_value(s: &str) -> Option<Self> {
+            let s = s.trim();
+            if let Some(value) = Self::parse(s) {
+                return Some(value);
+            }
+            let ord = Self::iter().position(|x| {
+                [x.variant_name(), x.pascal_spaced(), x.snake_case(), x.kebab_case()]
+                    .iter()
+                    .any(|form| form.eq_ignore_ascii_case(s))
+            })?;
+            match ord {
+                #variant_from_ordinals
+                _ => None,
+            }
+        }
+    };
+    if derive_deserialize {
+        return (fns, TokenStream2::new());
+    }
+
+    let from_int = if int_type_added {
+        let (int_type_str, int_type) = &int_types[0];
+        let from_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+        quote! { <#int_type>::try_from(v).ok().and_then(#enum_name::#from_fn_name) }
+    } else {
+        quote! {
+            match usize::try_from(v) {
+                Ok(ord) => match ord {
+                    #variant_from_ordinals
+                    _ => None,
+                },
+                Err(_) => None,
+            }
+        }
+    };
+    let expecting = format!("a {} variant name or number", enum_name);
+    let str_result = unknown_value_or(
+        fallback,
+        quote! { E::invalid_value(::serde::de::Unexpected::Str(v), &self) },
+    );
+    let u64_result = unknown_value_or(
+        fallback,
+        quote! { E::invalid_value(::serde::de::Unexpected::Unsigned(v), &self) },
+    );
+    let i64_result = unknown_value_or(
+        fallback,
+        quote! { E::invalid_value(::serde::de::Unexpected::Signed(v), &self) },
+    );
+    let impls = quote! {
+        impl<'de> ::serde::Deserialize<'de> for #enum_name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct ConfigVisitor;
+
+                impl ::serde::de::Visitor<'_> for ConfigVisitor {
+                    type Value = #enum_name;
+
+                    fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                        f.write_str(#expecting)
+                    }
+                    fn visit_str<E: ::serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                        let value = #enum_name::from_config_value(v);
+                        #str_result
+                    }
+                    fn visit_u64<E: ::serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                        let value: Option<#enum_name> = #from_int;
+                        #u64_result
+                    }
+                    fn visit_i64<E: ::serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                        let value: Option<#enum_name> = #from_int;
+                        #i64_result
+                    }
+                }
+
+                deserializer.deserialize_any(ConfigVisitor)
+            }
+        }
+    };
+    (fns, impls)
+}
+
+/// Returns the end of a generated `Deserialize` for `value: Option<EnumName>`: `error` if it's None, or with
+/// `SerdeMode = "lenient"`, the `fallback` variant.
+#[cfg(any(feature = "serde", feature = "config"))]
+fn unknown_value_or(fallback: &Option<TokenStream2>, error: TokenStream2) -> TokenStream2 {
+    match fallback {
+        Some(fallback) => quote! { Ok(value.unwrap_or_else(|| #fallback)) },
+        None => quote! { value.ok_or_else(|| #error) },
+    }
+}
+
+/// Generates `Serialize` and `Deserialize` for the `Collections` companion types: `<EnumName>Map<T>` as an object keyed
+/// by the variant names as declared, and `<EnumName>Set` as an array of variant names.
+///
+/// The using crate needs the `serde` crate as a dependency.
+#[cfg(feature = "serde")]
+fn make_serde_collection_impls(enum_name: &Ident, ordered_variants: &[Ident]) -> TokenStream2 {
+    let names = ordered_variants.iter().map(|x| x.to_string());
+    let names = quote! { [#(#names),*] };
+    let map_name = Ident::new(&format!("{}Map", enum_name), enum_name.span());
+    let set_name = Ident::new(&format!("{}Set", enum_name), enum_name.span());
+    let map_expecting = format!("a map keyed by {} variant names", enum_name);
+    let set_expecting = format!("a list of {} variant names", enum_name);
+    let key_expecting = format!("a {} variant name", enum_name);
+    // the keys are looked up by name as they're read, so no String is needed to hold them
+    let key_type = quote! {
+        const NAMES: &[&str] = &#names;
+        struct Key(usize);
+
+        impl<'de> ::serde::Deserialize<'de> for Key {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct KeyVisitor;
+
+                impl<'de> ::serde::de::Visitor<'de> for KeyVisitor {
+                    type Value = Key;
+
+                    fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        f.write_str(#key_expecting)
+                    }
+                    fn visit_str<E: ::serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                        NAMES.iter().position(|name| *name == v).map(Key).ok_or_else(|| E::unknown_variant(v, NAMES))
+                    }
+                }
+
+                deserializer.deserialize_str(KeyVisitor)
+            }
+        }
+    };
+    quote! {
+        impl<T: ::serde::Serialize> ::serde::Serialize for #map_name<T> {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_map(#enum_name::iter().zip(self.values.iter()).map(|(key, value)| (key.variant_name(), value)))
+            }
+        }
+
+        impl<'de, T: ::serde::Deserialize<'de>> ::serde::Deserialize<'de> for #map_name<T> {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct MapVisitor<T>(::core::marker::PhantomData<T>);
+                #key_type
+
+                impl<'de, T: ::serde::Deserialize<'de>> ::serde::de::Visitor<'de> for MapVisitor<T> {
+                    type Value = #map_name<T>;
+
+                    fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        f.write_str(#map_expecting)
+                    }
+                    fn visit_map<A: ::serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                        let mut values: [Option<T>; #enum_name::count()] = ::core::array::from_fn(|_| None);
+                        while let Some((Key(ord), value)) = map.next_entry::<Key, T>()? {
+                            if values[ord].is_some() {
+                                return Err(<A::Error as ::serde::de::Error>::duplicate_field(NAMES[ord]));
+                            }
+                            values[ord] = Some(value);
+                        }
+                        // every variant needs a value
+                        if let Some(ord) = values.iter().position(Option::is_none) {
+                            return Err(<A::Error as ::serde::de::Error>::missing_field(NAMES[ord]));
+                        }
+                        Ok(#map_name::new(values.map(|value| value.unwrap())))
+                    }
+                }
+
+                deserializer.deserialize_map(MapVisitor(::core::marker::PhantomData))
+            }
+        }
+
+        impl ::serde::Serialize for #set_name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.iter().map(|x| x.variant_name()))
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for #set_name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct SetVisitor;
+                #key_type
+
+                impl<'de> ::serde::de::Visitor<'de> for SetVisitor {
+                    type Value = #set_name;
+
+                    fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        f.write_str(#set_expecting)
+                    }
+                    fn visit_seq<A: ::serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                        let mut set = #set_name::new();
+                        while let Some(Key(ord)) = seq.next_element::<Key>()? {
+                            set.insert_ordinal(ord);
+                        }
+                        Ok(set)
+                    }
+                }
+
+                deserializer.deserialize_seq(SetVisitor)
+            }
+        }
+    }
+}
+
+/// Generates `Display` for `enum_def(Display = "<case>")`, writing the name from the `<case>()` function.
+fn make_display_impl(enum_name: &Ident, case: &str) -> TokenStream2 {
+    let case_fn_name = Ident::new(case, Span::call_site());
+    let doc = format!(
+        " Writes the name of the variant as returned by `{}()`",
+        case
+    );
+    quote! {
+        impl ::core::fmt::Display for #enum_name {
+            #[doc = #doc]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                // pad rather than write_str, so width and alignment such as {:>10} are honored
+                f.pad(self.#case_fn_name())
+            }
+        }
+    }
+}
+
+/// Generates `<EnumName>ParseError` and the `Display` and `FromStr` impls for `enum_def(Web)`.
+///
+/// `Display` writes the name of the variant as declared and `FromStr` accepts everything `parse()` does, so the two
+/// round-trip. `Display` is left out if the enum already derives one, such as with `derive_more`, or sets
+/// `Display = "..."`.
+fn make_web_types(vis: &Visibility, enum_name: &Ident, has_display: bool) -> TokenStream2 {
+    let error_name = Ident::new(&format!("{}ParseError", enum_name), enum_name.span());
+    let error_doc = format!(
+        " The error of parsing a [`{}`] from a string, holding the rejected input",
+        enum_name
+    );
+    let error_prefix = format!("invalid {} {{:?}}, expected one of: ", enum_name);
+    let display_impl = if has_display {
+        TokenStream2::new()
+    } else {
+        make_display_impl(enum_name, "variant_name")
+    };
+    quote! {
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct #error_name {
+            input: ::std::string::String,
+        }
+
+        #[allow(dead_code)]
+        impl #error_name {
+            /// Returns the string that didn't match a variant
+            pub fn input(&self) -> &str {
+                &self.input
+            }
+        }
+
+        impl ::std::fmt::Display for #error_name {
+            /// Writes the rejected input and the names of the variants
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                write!(f, #error_prefix, self.input)?;
+                for (i, x) in #enum_name::iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(x.variant_name())?;
+                }
+                Ok(())
+            }
+        }
+
+        impl ::std::error::Error for #error_name {}
+
+        #display_impl
+
+        impl ::std::str::FromStr for #enum_name {
+            type Err = #error_name;
+            /// Returns the variant from any of its names, aliases or its discriminant, the same as `parse()`
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::parse(s).ok_or_else(|| #error_name {
+                    input: ::std::string::String::from(s),
+                })
+            }
+        }
+    }
+}
+
+/// Generates the serde impls for `enum_def(Web)`, serializing the variants as their names as declared and
+/// deserializing them with `FromStr`.
+///
+/// Traits the enum already derives are left out. `Deserialize` is also left out for `enum_def(Config)`, whose
+/// `Deserialize` accepts the names too.
+/// The using crate needs the `serde` crate as a dependency.
+#[cfg(feature = "serde")]
+fn make_serde_web_impls(
+    enum_name: &Ident,
+    derive_summary: &DeriveSummary,
+    config: bool,
+    fallback: &Option<TokenStream2>,
+) -> TokenStream2 {
+    let expecting = format!("a {} variant name", enum_name);
+    let str_result = match fallback {
+        Some(fallback) => quote! { Ok(#enum_name::parse(v).unwrap_or_else(|| #fallback)) },
+        None => quote! { ::std::str::FromStr::from_str(v).map_err(E::custom) },
+    };
+    let mut impls = TokenStream2::new();
+    if !derive_summary.has_trait("Serialize") {
+        impls.extend(quote! {
+            impl ::serde::Serialize for #enum_name {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.variant_name())
+                }
+            }
+        });
+    }
+    if !derive_summary.has_trait("Deserialize") && !config {
+        impls.extend(quote! {
+            impl<'de> ::serde::Deserialize<'de> for #enum_name {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    struct WebVisitor;
+
+                    impl<'de> ::serde::de::Visitor<'de> for WebVisitor {
+                        type Value = #enum_name;
+
+                        fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                            f.write_str(#expecting)
+                        }
+                        fn visit_str<E: ::serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                            #str_result
+                        }
+                    }
+
+                    deserializer.deserialize_str(WebVisitor)
+                }
+            }
+        });
+    }
+    impls
+}
+
+/// Generates serde impls for `Serde = "<case>"`, serializing the variants with the `<case>()` name conversion and
+/// deserializing them with `from_<case>()`.
+///
+/// Traits the enum already derives are left out, and so is `Deserialize` for `enum_def(Config)`.
+/// The using crate needs the `serde` crate as a dependency.
+#[cfg(feature = "serde")]
+fn make_serde_case_impls(
+    enum_name: &Ident,
+    case: &str,
+    derive_summary: &DeriveSummary,
+    config: bool,
+    fallback: &Option<TokenStream2>,
+) -> TokenStream2 {
+    let to_fn_name = Ident::new(case, Span::call_site());
+    let from_fn_name = Ident::new(&format!("from_{}", case), Span::call_site());
+    let expecting = format!("a {} name of {}", case, enum_name);
+    let result = unknown_value_or(
+        fallback,
+        quote! { E::invalid_value(::serde::de::Unexpected::Str(v), &self) },
+    );
+    let mut impls = TokenStream2::new();
+    if !derive_summary.has_trait("Serialize") {
+        impls.extend(quote! {
+            impl ::serde::Serialize for #enum_name {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.#to_fn_name())
+                }
+            }
+        });
+    }
+    if !derive_summary.has_trait("Deserialize") && !config {
+        impls.extend(quote! {
+            impl<'de> ::serde::Deserialize<'de> for #enum_name {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    struct CaseVisitor;
+
+                    impl<'de> ::serde::de::Visitor<'de> for CaseVisitor {
+                        type Value = #enum_name;
+
+                        fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                            f.write_str(#expecting)
+                        }
+                        fn visit_str<E: ::serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                            let value = #enum_name::#from_fn_name(v);
+                            #result
+                        }
+                    }
+
+                    deserializer.deserialize_str(CaseVisitor)
+                }
+            }
+        });
+    }
+    impls
+}
+
+/// Generates serde impls that store the variants as their discriminants, for compact formats such as postcard.
+///
+/// `int_type_str` is the first `IntType`, whose `as_<IntType>()` and `from_<IntType>()` are used. Traits the enum
+/// already derives are left out, and so is `Deserialize` for `enum_def(Config)`.
+/// The using crate needs the `serde` crate as a dependency.
+#[cfg(feature = "serde")]
+fn make_serde_int_impls(
+    enum_name: &Ident,
+    int_type_str: &str,
+    int_type: &TokenStream2,
+    derive_summary: &DeriveSummary,
+    config: bool,
+    fallback: &Option<TokenStream2>,
+) -> TokenStream2 {
+    let as_fn_name = Ident::new(&format!("as_{}", int_type_str), Span::call_site());
+    let from_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+    let invalid = format!("invalid {} discriminant {{}}", enum_name);
+    let result = unknown_value_or(
+        fallback,
+        quote! { <D::Error as ::serde::de::Error>::custom(::core::format_args!(#invalid, raw)) },
+    );
+    let mut impls = TokenStream2::new();
+    if !derive_summary.has_trait("Serialize") {
+        impls.extend(quote! {
+            impl ::serde::Serialize for #enum_name {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    ::serde::Serialize::serialize(&self.#as_fn_name(), serializer)
+                }
+            }
+        });
+    }
+    if !derive_summary.has_trait("Deserialize") && !config {
+        impls.extend(quote! {
+            impl<'de> ::serde::Deserialize<'de> for #enum_name {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let raw = <#int_type as ::serde::Deserialize>::deserialize(deserializer)?;
+                    let value = Self::#from_fn_name(raw);
+                    #result
+                }
+            }
+        });
+    }
+    impls
+}
+
+/// Generates `<EnumName>Mask`, a serde `with` module that stores a collection of variants as a single integer bitmask.
+///
+/// The using crate needs the `serde` crate as a dependency.
+#[cfg(feature = "serde")]
+fn make_serde_mask_type(
+    vis: &Visibility,
+    enum_name: &Ident,
+    int_type: &TokenStream2,
+    integration_cfg: &Option<TokenStream2>,
+) -> TokenStream2 {
+    let mask_name = Ident::new(&format!("{}Mask", enum_name), enum_name.span());
+    let mask_doc = format!(
+        " Serializes a collection of [`{}`] as a single integer bitmask, for use with `#[serde(with = \"{}\")]`",
+        enum_name, mask_name
+    );
+    quote! {
+        #[doc = #mask_doc]
+        /// * Works with any collection that can be iterated by reference and collected from the variants, such as
+        ///   `Vec` or `BTreeSet`
+        /// * Bits that don't belong to a variant are ignored when deserializing
+        #integration_cfg
+        #[allow(dead_code)]
+        #vis struct #mask_name;
+
+        #integration_cfg
+        #[allow(dead_code)]
+        impl #mask_name {
+            /// Serializes the variants as the mask with the bit of every variant set
+            pub fn serialize<'a, C, S>(items: &'a C, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                &'a C: IntoIterator<Item = &'a #enum_name>,
+                S: ::serde::Serializer,
+            {
+                ::serde::Serialize::serialize(&#enum_name::mask_of(items), serializer)
+            }
+            /// Deserializes a mask into the variants whose bit is set, in declaration order
+            pub fn deserialize<'de, C, D>(deserializer: D) -> Result<C, D::Error>
+            where
+                C: ::core::iter::FromIterator<#enum_name>,
+                D: ::serde::Deserializer<'de>,
+            {
+                let mask = <#int_type as ::serde::Deserialize>::deserialize(deserializer)?;
+                Ok(#enum_name::variants_in_mask(mask).collect())
+            }
+        }
+    }
+}
+
+/// Generates `metric_label()` and `From<Enum> for metrics::SharedString`, using the snake_case names of the variants.
+///
+/// The using crate needs the `metrics` crate as a dependency.
+#[cfg(feature = "metrics")]
+fn make_metrics_fns(enum_name: &Ident) -> (TokenStream2, TokenStream2) {
+    let fns = quote! {
+        /// Returns the snake_case name of the variant as a metrics label value, without allocating
+        /// * For example, MyEnum::InQA.metric_label() returns "in_qa"
+        #[inline]
+        pub fn metric_label(&self) -> ::metrics::SharedString {
+            ::metrics::SharedString::from(self.snake_case())
+        }
+    };
+    let impls = quote! {
+        impl From<#enum_name> for ::metrics::SharedString {
+            /// Returns the snake_case name of the variant, without allocating
+            #[inline]
+            fn from(val: #enum_name) -> Self {
+                val.metric_label()
+            }
+        }
+    };
+    (fns, impls)
+}
+
+/// Generates `otel_value()`, `otel_key_value()` and `From<Enum> for opentelemetry::Value`, using the snake_case names
+/// of the variants.
+///
+/// The key is `OtelKey`, or the snake_case enum name. The using crate needs the `opentelemetry` crate as a dependency.
+#[cfg(feature = "otel")]
+fn make_otel_fns(
+    enum_name: &Ident,
+    otel_key: &Option<LitStr>,
+    lowercase: Lowercase,
+) -> (TokenStream2, TokenStream2) {
+    let otel_key = match otel_key {
+        Some(key) => key.value(),
+        None => to_snake_case(&enum_name.to_string(), lowercase, &[]),
+    };
+    let fns = quote! {
+        /// The OpenTelemetry attribute key of [`Self::otel_key_value`]
+        pub const OTEL_KEY: &'static str = #otel_key;
+        /// Returns the snake_case name of the variant as an OpenTelemetry attribute value, without allocating
+        /// * For example, MyEnum::InQA.otel_value() returns Value::from("in_qa")
+        #[inline]
+        pub fn otel_value(&self) -> ::opentelemetry::Value {
+            ::opentelemetry::Value::from(self.snake_case())
+        }
+        /// Returns the variant as an OpenTelemetry attribute under [`Self::OTEL_KEY`], for tagging spans and metrics
+        /// * For example, MyEnum::InQA.otel_key_value() returns KeyValue::new("my_enum", "in_qa")
+        #[inline]
+        pub fn otel_key_value(&self) -> ::opentelemetry::KeyValue {
+            ::opentelemetry::KeyValue::new(Self::OTEL_KEY, self.otel_value())
+        }
+    };
+    let impls = quote! {
+        impl From<#enum_name> for ::opentelemetry::Value {
+            /// Returns the snake_case name of the variant, without allocating
+            #[inline]
+            fn from(val: #enum_name) -> Self {
+                val.otel_value()
+            }
+        }
+    };
+    (fns, impls)
+}
+
+/// Generates the `Atomic<EnumName>` companion type for `enum_def(Atomic)`.
+///
+/// The atomic holds the discriminant of the variant if the enum has discriminants, and its ordinal otherwise.
+/// `raw_type_str` and `raw_type` are the `IntType` or the `OrdinalType` to match.
+fn make_atomic_type(
+    vis: &Visibility,
+    enum_name: &Ident,
+    raw_type_str: &str,
+    raw_type: &TokenStream2,
+    has_discriminants: bool,
+    variant_from_ordinals: &TokenStream2,
+) -> Result<TokenStream2, EnumMacroError> {
+    let atomic_inner = match raw_type_str {
+        "i8" => quote! { AtomicI8 },
+        "u8" => quote! { AtomicU8 },
+        "i16" => quote! { AtomicI16 },
+        "u16" => quote! { AtomicU16 },
+        "i32" => quote! { AtomicI32 },
+        "u32" => quote! { AtomicU32 },
+        "i64" => quote! { AtomicI64 },
+        "u64" => quote! { AtomicU64 },
+        "isize" => quote! { AtomicIsize },
+        "usize" => quote! { AtomicUsize },
+        _ => {
+            return Err(EnumMacroError::ParseError(format!(
+                "Atomic isn't supported for IntType {}: there's no atomic integer of that size",
+                raw_type_str
+            )))
+        }
+    };
+    let atomic_name = Ident::new(&format!("Atomic{}", enum_name), enum_name.span());
+    let (to_raw, from_raw) = if has_discriminants {
+        let from_fn_name = Ident::new(&format!("from_{}", raw_type_str), Span::call_site());
+        (
+            quote! { value as #raw_type },
+            quote! { #enum_name::#from_fn_name(raw) },
+        )
+    } else {
+        (
+            quote! { value.ordinal() },
+            quote! {
+                match raw {
+                    #variant_from_ordinals
+                    _ => None,
+                }
+            },
+        )
+    };
+    let atomic_doc = format!(
+        " An atomic [`{}`], stored as its {}",
+        enum_name,
+        if has_discriminants {
+            "discriminant"
+        } else {
+            "ordinal"
+        }
+    );
+
+    Ok(quote! {
+        #[doc = #atomic_doc]
+        #[allow(dead_code)]
+        #vis struct #atomic_name(::core::sync::atomic::#atomic_inner);
+
+        #[allow(dead_code)]
+        impl #atomic_name {
+            #[inline]
+            const fn to_raw(value: #enum_name) -> #raw_type {
+                #to_raw
+            }
+            #[inline]
+            fn from_raw(raw: #raw_type) -> #enum_name {
+                match #from_raw {
+                    Some(value) => value,
+                    None => unreachable!("only valid variants are stored"),
+                }
+            }
+            /// Creates a new atomic holding `value`
+            #[inline]
+            pub const fn new(value: #enum_name) -> Self {
+                Self(::core::sync::atomic::#atomic_inner::new(Self::to_raw(value)))
+            }
+            /// Loads the current variant
+            #[inline]
+            pub fn load(&self, order: ::core::sync::atomic::Ordering) -> #enum_name {
+                Self::from_raw(self.0.load(order))
+            }
+            /// Stores `value`
+            #[inline]
+            pub fn store(&self, value: #enum_name, order: ::core::sync::atomic::Ordering) {
+                self.0.store(Self::to_raw(value), order)
+            }
+            /// Stores `value`, returning the previous variant
+            #[inline]
+            pub fn swap(&self, value: #enum_name, order: ::core::sync::atomic::Ordering) -> #enum_name {
+                Self::from_raw(self.0.swap(Self::to_raw(value), order))
+            }
+            /// Stores `new` if the current variant is `current`.
+            /// * Returns the previous variant, as Ok if it was `current` and Err otherwise
+            #[inline]
+            pub fn compare_exchange(
+                &self,
+                current: #enum_name,
+                new: #enum_name,
+                success: ::core::sync::atomic::Ordering,
+                failure: ::core::sync::atomic::Ordering,
+            ) -> ::core::result::Result<#enum_name, #enum_name> {
+                match self.0.compare_exchange(Self::to_raw(current), Self::to_raw(new), success, failure) {
+                    Ok(raw) => Ok(Self::from_raw(raw)),
+                    Err(raw) => Err(Self::from_raw(raw)),
+                }
+            }
+            /// Consumes the atomic and returns the variant
+            #[inline]
+            pub fn into_inner(self) -> #enum_name {
+                Self::from_raw(self.0.into_inner())
+            }
+        }
+
+        impl From<#enum_name> for #atomic_name {
+            #[inline]
+            fn from(value: #enum_name) -> Self {
+                Self::new(value)
+            }
+        }
+    })
+}
+
+/// Generates the `inventory` submission that registers the enum in the `EnumExtInfo` registry declared by
+/// `enum_ext_registry!()` at the crate root.
+///
+/// The discriminants are cast to `u128` for unsigned `IntType`s and to `i128` for signed ones, so neither wraps.
+#[cfg(feature = "registry")]
+fn make_registry_entry(
+    enum_name: &Ident,
+    ordered_variants: &[Ident],
+    int_type_str: &str,
+) -> TokenStream2 {
+    let enum_name_str = enum_name.to_string();
+    let variants = ordered_variants.iter().map(|variant_ident| {
+        let variant_name_str = variant_ident.to_string();
+        if int_type_str.starts_with('u') {
+            quote! { (#variant_name_str, crate::EnumExtValue::Unsigned(#enum_name::#variant_ident as u128)) }
+        } else {
+            quote! { (#variant_name_str, crate::EnumExtValue::Signed(#enum_name::#variant_ident as i128)) }
+        }
+    });
+    quote! {
+        ::inventory::submit! {
+            crate::EnumExtInfo {
+                name: #enum_name_str,
+                pretty_print: #enum_name::pretty_print(),
+                variants: &[#(#variants),*],
+            }
+        }
+    }
+}
+
+/// Generates `compare_schema()` and the `<EnumName>SchemaDiff` type it returns.
+fn make_compare_schema(vis: &Visibility, enum_name: &Ident) -> (TokenStream2, TokenStream2) {
+    let diff_name = Ident::new(&format!("{}SchemaDiff", enum_name), enum_name.span());
+    let diff_doc = format!(
+        " The differences between [`{}`] and another definition of it, from [`{}::compare_schema`]",
+        enum_name, enum_name
+    );
+    let fns = quote! {
+        /// Compares this definition of the enum with another one, given as its `schema()` output.
+        /// * `added` are the variants only this definition has, and `removed` the ones only the other one has
+        /// * `renumbered` are the variants with different discriminants, as (name, this value, other value)
+        /// * `unknown` are the variants whose discriminants couldn't be compared, because one side lists an expression
+        ///   it couldn't evaluate at compile time instead of a number
+        pub fn compare_schema(other: &str) -> #diff_name {
+            // "Name=value;" items, as written by schema()
+            fn parse(s: &str) -> ::std::vec::Vec<(&str, &str)> {
+                s.split(';')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| match item.split_once('=') {
+                        Some((name, value)) => (name.trim(), value.trim()),
+                        None => (item, ""),
+                    })
+                    .collect()
+            }
+
+            let ours = parse(Self::schema());
+            let theirs = parse(other);
+            let mut diff = #diff_name::default();
+            for (name, value) in &ours {
+                match theirs.iter().find(|(other_name, _)| other_name == name) {
+                    None => diff.added.push(::std::string::String::from(*name)),
+                    Some((_, other_value)) => match (value.parse::<i128>(), other_value.parse::<i128>()) {
+                        (Ok(value), Ok(other_value)) => {
+                            if value != other_value {
+                                diff.renumbered.push((::std::string::String::from(*name), value, other_value));
+                            }
+                        }
+                        // the same expression on both sides is the same discriminant
+                        _ => {
+                            if value != other_value {
+                                diff.unknown.push(::std::string::String::from(*name));
+                            }
+                        }
+                    },
+                }
+            }
+            for (name, _) in &theirs {
+                if !ours.iter().any(|(our_name, _)| our_name == name) {
+                    diff.removed.push(::std::string::String::from(*name));
+                }
+            }
+            diff
+        }
+    };
+    let diff_type = quote! {
+        #[doc = #diff_doc]
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        #[allow(dead_code)]
+        #vis struct #diff_name {
+            /// The variants only this definition has
+            pub added: ::std::vec::Vec<::std::string::String>,
+            /// The variants only the other definition has
+            pub removed: ::std::vec::Vec<::std::string::String>,
+            /// The variants with different discriminants, as (name, this value, other value)
+            pub renumbered: ::std::vec::Vec<(::std::string::String, i128, i128)>,
+            /// The variants in both definitions whose discriminants couldn't be compared
+            pub unknown: ::std::vec::Vec<::std::string::String>,
+        }
+
+        #[allow(dead_code)]
+        impl #diff_name {
+            /// Returns true if the definitions match
+            pub fn is_empty(&self) -> bool {
+                self.added.is_empty() && self.removed.is_empty() && self.renumbered.is_empty() && self.unknown.is_empty()
+            }
+        }
+    };
+    (fns, diff_type)
+}
+
+/// Generates the `Collections` companion types: `<EnumName>Map<T>`, which holds a value for every variant, and
+/// `<EnumName>Set`, a bit set of variants. Both are indexed by ordinal, so lookups don't hash or search.
+fn make_collection_types(
+    vis: &Visibility,
+    enum_name: &Ident,
+    variant_count: usize,
+) -> TokenStream2 {
+    let map_name = Ident::new(&format!("{}Map", enum_name), enum_name.span());
+    let set_name = Ident::new(&format!("{}Set", enum_name), enum_name.span());
+    let map_doc = format!(
+        " A value for every variant of [`{}`], stored in an array indexed by ordinal",
+        enum_name
+    );
+    let set_doc = format!(
+        " A set of [`{}`] variants, stored as bits indexed by ordinal",
+        enum_name
+    );
+    let words = variant_count.div_ceil(64);
+    quote! {
+        #[doc = #map_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[allow(dead_code)]
+        #vis struct #map_name<T> {
+            values: [T; #variant_count],
+        }
+
+        #[allow(dead_code)]
+        impl<T> #map_name<T> {
+            /// Returns a map with the given values, in ordinal order
+            pub const fn new(values: [T; #variant_count]) -> Self {
+                Self { values }
+            }
+            /// Returns a map with the value `f` returns for each variant
+            /// * For example, MyEnumMap::from_fn(|x| x.variant_name().len())
+            pub fn from_fn(mut f: impl FnMut(&'static #enum_name) -> T) -> Self {
+                Self {
+                    values: ::core::array::from_fn(|ord| f(&#enum_name::all()[ord])),
+                }
+            }
+            /// Returns a map with the default value for every variant
+            pub fn with_default() -> Self
+            where
+                T: Default,
+            {
+                Self::from_fn(|_| T::default())
+            }
+            /// Returns the value of `key`
+            #[inline]
+            pub fn get(&self, key: &#enum_name) -> &T {
+                &self.values[key.ordinal() as usize]
+            }
+            /// Returns the value of `key` for changing
+            #[inline]
+            pub fn get_mut(&mut self, key: &#enum_name) -> &mut T {
+                &mut self.values[key.ordinal() as usize]
+            }
+            /// Returns the values, in ordinal order
+            pub fn into_values(self) -> [T; #variant_count] {
+                self.values
+            }
+            /// Returns an iterator over the variants, in ordinal order
+            pub fn keys(&self) -> impl Iterator<Item = &'static #enum_name> {
+                #enum_name::iter()
+            }
+            /// Returns an iterator over the values, in ordinal order
+            pub fn values(&self) -> ::core::slice::Iter<'_, T> {
+                self.values.iter()
+            }
+            /// Returns an iterator over the values for changing, in ordinal order
+            pub fn values_mut(&mut self) -> ::core::slice::IterMut<'_, T> {
+                self.values.iter_mut()
+            }
+            /// Returns an iterator over the variants and their values, in ordinal order
+            pub fn iter(&self) -> impl Iterator<Item = (&'static #enum_name, &T)> {
+                #enum_name::iter().zip(self.values.iter())
+            }
+            /// Returns an iterator over the variants and their values for changing, in ordinal order
+            pub fn iter_mut(&mut self) -> impl Iterator<Item = (&'static #enum_name, &mut T)> {
+                #enum_name::iter().zip(self.values.iter_mut())
+            }
+        }
+
+        impl<T: Default> Default for #map_name<T> {
+            fn default() -> Self {
+                Self::with_default()
+            }
+        }
+
+        /// Variants missing from the iterator get the default value, and later pairs replace earlier ones
+        impl<T: Default> ::core::iter::FromIterator<(#enum_name, T)> for #map_name<T> {
+            fn from_iter<I: IntoIterator<Item = (#enum_name, T)>>(iter: I) -> Self {
+                let mut map = Self::with_default();
+                for (key, value) in iter {
+                    map[key] = value;
+                }
+                map
+            }
+        }
+
+        impl<T> ::core::ops::Index<#enum_name> for #map_name<T> {
+            type Output = T;
+            #[inline]
+            fn index(&self, key: #enum_name) -> &T {
+                self.get(&key)
+            }
+        }
+
+        impl<T> ::core::ops::IndexMut<#enum_name> for #map_name<T> {
+            #[inline]
+            fn index_mut(&mut self, key: #enum_name) -> &mut T {
+                self.get_mut(&key)
+            }
+        }
+
+        #[doc = #set_doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #[allow(dead_code)]
+        #vis struct #set_name {
+            bits: [u64; #words],
+        }
+
+        #[allow(dead_code)]
+        impl #set_name {
+            /// Returns an empty set
+            pub const fn new() -> Self {
+                Self { bits: [0; #words] }
+            }
+            /// Adds the variant with the given ordinal, returning true if it wasn't in the set
+            fn insert_ordinal(&mut self, ord: usize) -> bool {
+                let bit = 1u64 << (ord % 64);
+                let inserted = self.bits[ord / 64] & bit == 0;
+                self.bits[ord / 64] |= bit;
+                inserted
+            }
+            /// Adds `value`, returning true if it wasn't in the set
+            pub fn insert(&mut self, value: #enum_name) -> bool {
+                self.insert_ordinal(value.ordinal() as usize)
+            }
+            /// Removes `value`, returning true if it was in the set
+            pub fn remove(&mut self, value: &#enum_name) -> bool {
+                let ord = value.ordinal() as usize;
+                let bit = 1u64 << (ord % 64);
+                let removed = self.bits[ord / 64] & bit != 0;
+                self.bits[ord / 64] &= !bit;
+                removed
+            }
+            /// Returns true if `value` is in the set
+            pub fn contains(&self, value: &#enum_name) -> bool {
+                let ord = value.ordinal() as usize;
+                self.bits[ord / 64] & (1u64 << (ord % 64)) != 0
+            }
+            /// Returns the number of variants in the set
+            pub fn len(&self) -> usize {
+                self.bits.iter().map(|x| x.count_ones() as usize).sum()
+            }
+            /// Returns true if the set has no variants
+            pub fn is_empty(&self) -> bool {
+                self.bits.iter().all(|x| *x == 0)
+            }
+            /// Returns an iterator over the variants in the set, in ordinal order
+            pub fn iter(&self) -> impl Iterator<Item = &'static #enum_name> + '_ {
+                #enum_name::iter().filter(move |x| self.contains(x))
+            }
+        }
+
+        impl ::core::fmt::Debug for #set_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_set().entries(self.iter().map(|x| x.variant_name())).finish()
+            }
+        }
+    }
+}
+
+/// Generates `from_prefix()`, matching the start of the variant names without allocating.
+///
+/// The candidates are returned as an iterator rather than a companion type, so nothing is added next to the enum.
+fn make_from_prefix() -> TokenStream2 {
+    quote! {
+        /// Returns the variant whose name as declared, snake_case or kebab-case name starts with `s`
+        /// * For example, MyEnum::from_prefix("in-q") returns Ok(&MyEnum::InQA), the way Unix tools accept
+        ///   shortened commands
+        /// * A name that matches exactly wins even if it's also the start of other names
+        /// * Otherwise the error iterates over the variants that match, in ordinal order: none, or several if `s`
+        ///   is ambiguous, so a CLI can suggest them
+        pub fn from_prefix(s: &str) -> Result<&'static Self, impl Iterator<Item = &'static Self> + '_> {
+            let names = |x: &Self| [x.variant_name(), x.snake_case(), x.kebab_case()];
+            if let Some(exact) = Self::iter().find(|x| names(x).contains(&s)) {
+                return Ok(exact);
+            }
+            let matches = move || Self::iter().filter(move |x| names(x).iter().any(|name| name.starts_with(s)));
+            let mut probe = matches();
+            match (probe.next(), probe.next()) {
+                (Some(x), None) => Ok(x),
+                _ => Err(matches()),
+            }
+        }
+    }
+}
+
+/// Generates `ui_select()`, an egui ComboBox over the variants.
+///
+/// The using crate needs the `egui` crate as a dependency.
+#[cfg(feature = "egui")]
+fn make_egui_fns(variant_from_ordinals: &TokenStream2) -> TokenStream2 {
+    quote! {
+        /// Shows a ComboBox of every variant, labeled with `pascal_spaced()`, and stores the chosen variant in `current`.
+        /// * Returns true if `current` changed
+        pub fn ui_select(ui: &mut ::egui::Ui, current: &mut Self) -> bool {
+            let before = current.ordinal();
+            let mut selected = before;
+            // the address of `current` keeps several selects of the same enum apart
+            let id_salt = (::core::any::type_name::<Self>(), current as *const Self as usize);
+            ::egui::ComboBox::from_id_salt(id_salt)
+                .selected_text(current.pascal_spaced())
+                .show_ui(ui, |ui| {
+                    for x in Self::iter() {
+                        ui.selectable_value(&mut selected, x.ordinal(), x.pascal_spaced());
+                    }
+                });
+            if selected == before {
+                return false;
+            }
+            if let Some(value) = match selected {
+                #variant_from_ordinals
+                _ => None,
+            } {
+                *current = value;
+            }
+            true
+        }
+    }
+}
+
+/// Generates `list_items()` and the `<EnumName>Selector` companion type, the state of a ratatui list for picking a
+/// variant.
+///
+/// The using crate needs the `ratatui` crate as a dependency.
+#[cfg(feature = "ratatui")]
+fn make_ratatui_fns(
+    vis: &Visibility,
+    enum_name: &Ident,
+    integration_cfg: &Option<TokenStream2>,
+) -> (TokenStream2, TokenStream2) {
+    let selector_name = Ident::new(&format!("{}Selector", enum_name), enum_name.span());
+    let selector_doc = format!(
+        " The selected [`{}`] of a ratatui list of [`{}::list_items`], moved with `up()` and `down()`",
+        enum_name, enum_name
+    );
+    let fns = quote! {
+        /// Returns a ratatui `ListItem` of every variant, labeled with `pascal_spaced()`, in ordinal order
+        pub fn list_items() -> ::std::vec::Vec<::ratatui::widgets::ListItem<'static>> {
+            Self::iter()
+                .map(|x| ::ratatui::widgets::ListItem::new(x.pascal_spaced()))
+                .collect()
+        }
+    };
+    let selector_type = quote! {
+        #[doc = #selector_doc]
+        /// * The selection wraps around at either end
+        #integration_cfg
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        #vis struct #selector_name {
+            selected: usize,
+        }
+
+        #integration_cfg
+        #[allow(dead_code)]
+        impl #selector_name {
+            /// Returns a selector with the first variant selected
+            pub const fn new() -> Self {
+                Self { selected: 0 }
+            }
+            /// Returns a selector with `value` selected
+            pub const fn with_selected(value: &#enum_name) -> Self {
+                Self {
+                    selected: value.ordinal() as usize,
+                }
+            }
+            /// Selects the previous variant, or the last one if the first one is selected
+            pub fn up(&mut self) {
+                self.selected = self.selected.checked_sub(1).unwrap_or(#enum_name::count() - 1);
+            }
+            /// Selects the next variant, or the first one if the last one is selected
+            pub fn down(&mut self) {
+                self.selected = (self.selected + 1) % #enum_name::count();
+            }
+            /// Returns the selected variant
+            pub fn selected(&self) -> &'static #enum_name {
+                &#enum_name::all()[self.selected]
+            }
+            /// Selects `value`
+            pub fn select(&mut self, value: &#enum_name) {
+                self.selected = value.ordinal() as usize;
+            }
+            /// Returns the ordinal of the selected variant
+            pub const fn selected_index(&self) -> usize {
+                self.selected
+            }
+            /// Returns a ratatui `ListState` with the selected variant selected, for rendering `list_items()`
+            pub fn list_state(&self) -> ::ratatui::widgets::ListState {
+                ::ratatui::widgets::ListState::default().with_selected(Some(self.selected))
+            }
+        }
+    };
+    (fns, selector_type)
+}
+
+/// Generates `TryFrom<T>` for every supported integer type that doesn't already have a `From` impl.
+///
+/// The value is range-checked against the primary `IntType` and then handed to its `from_<IntType>` function.
+/// The error is the original value, or with `TryFrom` the `<EnumName>TryFromError` holding it, so every width fails
+/// the same way.
+fn make_try_from_impls(
+    enum_name: &Ident,
+    int_types: &[(String, TokenStream2)],
+    try_from: bool,
+) -> TokenStream2 {
+    let (int_type_str, int_type) = &int_types[0];
+    let from_fn_name = Ident::new(&format!("from_{}", int_type_str), Span::call_site());
+    let error_name = Ident::new(&format!("{}TryFromError", enum_name), enum_name.span());
+    let mut impls = TokenStream2::new();
+    for other_str in [
+        "i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "i128", "u128", "isize", "usize",
+    ] {
+        if int_types.iter().any(|(x, _)| x == other_str) {
+            // From<T> is implemented, so TryFrom<T> comes from the blanket impl
+            continue;
+        }
+        let other = Ident::new(other_str, Span::call_site());
+        if try_from {
+            impls.extend(quote! {
+                impl ::core::convert::TryFrom<#other> for #enum_name {
+                    type Error = #error_name<#other>;
+                    /// Returns the enum variant from the integer value, or an error reporting the value and the
+                    /// range of the discriminants if it is out of range or not a valid discriminant.
+                    #[inline]
+                    fn try_from(value: #other) -> Result<Self, #error_name<#other>> {
+                        <#int_type as ::core::convert::TryFrom<#other>>::try_from(value)
+                            .ok()
+                            .and_then(Self::#from_fn_name)
+                            .ok_or(#error_name { value })
+                    }
+                }
+            });
+            continue;
+        }
+        impls.extend(quote! {
+            impl ::core::convert::TryFrom<#other> for #enum_name {
+                type Error = #other;
+                /// Returns the enum variant from the integer value, or the value itself if it is out of range
+                /// or not a valid discriminant.
+                #[inline]
+                fn try_from(val: #other) -> Result<Self, #other> {
+                    match <#int_type as ::core::convert::TryFrom<#other>>::try_from(val) {
+                        Ok(v) => Self::#from_fn_name(v).ok_or(val),
+                        Err(_) => Err(val),
+                    }
+                }
+            }
+        });
+    }
+    impls
+}
+
+/// Returns the discriminant value of every variant, in declaration order.
+///
+/// Variants without an explicit discriminant take the previous value + 1 (starting at 0), the same way rustc
+/// assigns them. A value is None if it (or the explicit discriminant it follows) can't be evaluated at macro time.
+pub(crate) fn discriminant_values(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+) -> Vec<Option<i128>> {
+    implied_values(
+        enum_name,
+        variants.iter().map(|variant| {
+            (
+                &variant.ident,
+                variant.discriminant.as_ref().map(|(_, expr)| expr),
+            )
+        }),
+    )
+}
+
+/// Same as [`discriminant_values`], for a sequence of variants with their optional discriminant expressions.
+///
+/// Discriminants can refer to earlier variants, e.g. `B = Self::A as isize + 1` or `B = MyEnum::A as isize + 1`.
+pub(crate) fn implied_values<'a>(
+    enum_name: &Ident,
+    discriminants: impl Iterator<Item = (&'a Ident, Option<&'a Expr>)>,
+) -> Vec<Option<i128>> {
+    let mut known: Vec<(&Ident, i128)> = Vec::new();
+    let mut values = Vec::new();
+    let mut previous: Option<Option<i128>> = None;
+    for (variant_ident, discriminant) in discriminants {
+        let value = match discriminant {
+            Some(expr) => eval_int_expr(expr, &|path| {
+                let mut segments = path.segments.iter();
+                match (segments.next(), segments.next(), segments.next()) {
+                    (Some(owner), Some(variant), None)
+                        if owner.ident == "Self" || owner.ident == *enum_name =>
+                    {
+                        known
+                            .iter()
+                            .find(|(ident, _)| **ident == variant.ident)
+                            .map(|(_, value)| *value)
+                    }
+                    _ => None,
+                }
+            }),
+            None => match previous {
+                None => Some(0),
+                Some(prev) => prev.and_then(|v| v.checked_add(1)),
+            },
+        };
+        if let Some(value) = value {
+            known.push((variant_ident, value));
+        }
+        values.push(value);
+        previous = Some(value);
+    }
+    values
+}
+
+/// The 64-bit FNV-1a hash of `bytes`. It's stable across compiler versions and platforms, unlike `DefaultHasher`.
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Lists the variant names and discriminants in ordinal order, e.g. `Open=1;Done=2;`, for `schema()` and
+/// `schema_hash()`.
+///
+/// Discriminants that can't be evaluated at compile time are listed by their expression.
+pub(crate) fn schema_string(
+    enum_name: &Ident,
+    variant_map: &[(Ident, Option<(syn::token::Eq, Expr)>)],
+    ordered_variants: &[Ident],
+) -> String {
+    let values = implied_values(
+        enum_name,
+        variant_map
+            .iter()
+            .map(|(ident, value)| (ident, value.as_ref().map(|v| &v.1))),
+    );
+    let mut schema = String::new();
+    for variant_ident in ordered_variants {
+        let (index, (_, discriminant)) = variant_map
+            .iter()
+            .enumerate()
+            .find(|(_, (ident, _))| ident == variant_ident)
+            .expect("ordered_variants are all in variant_map");
+        let value = match (values[index], discriminant) {
+            (Some(value), _) => value.to_string(),
+            (None, Some((_, expr))) => expr.to_token_stream().to_string(),
+            // implied from a discriminant that couldn't be evaluated
+            (None, None) => "+1".to_string(),
+        };
+        schema.push_str(&format!("{}={};", variant_ident, value));
+    }
+    schema
+}
+
+/// Picks the smallest integer type that can hold every discriminant, preferring unsigned types.
+pub(crate) fn smallest_int_type(
+    enum_name: &Ident,
+    variants: &Punctuated<Variant, Comma>,
+) -> Result<&'static str, EnumMacroError> {
+    let mut min = 0i128;
+    let mut max = 0i128;
+    for (variant, value) in variants
+        .iter()
+        .zip(discriminant_values(enum_name, variants))
+    {
+        let value = value.ok_or_else(|| {
+            EnumMacroError::VariantError(format!(
+                "IntType = \"auto\" requires discriminants that can be evaluated at compile time, but '{}' can't be",
+                variant.to_token_stream()
+            ))
+        })?;
+        min = min.min(value);
+        max = max.max(value);
+    }
+
+    let candidates: &[(&'static str, i128, i128)] = if min >= 0 {
+        &[
+            ("u8", 0, u8::MAX as i128),
+            ("u16", 0, u16::MAX as i128),
+            ("u32", 0, u32::MAX as i128),
+            ("u64", 0, u64::MAX as i128),
+            ("u128", 0, i128::MAX),
+        ]
+    } else {
+        &[
+            ("i8", i8::MIN as i128, i8::MAX as i128),
+            ("i16", i16::MIN as i128, i16::MAX as i128),
+            ("i32", i32::MIN as i128, i32::MAX as i128),
+            ("i64", i64::MIN as i128, i64::MAX as i128),
+            ("i128", i128::MIN, i128::MAX),
+        ]
+    };
+
+    Ok(candidates
+        .iter()
+        .find(|(_, lo, hi)| min >= *lo && max <= *hi)
+        .map(|(ty, _, _)| *ty)
+        .unwrap_or("i128"))
+}
+
+/// Generates the enum definition along with all the helper functions and trait impls.
+///
+/// This is shared by both `enum_ext!` and `#[enum_extend]`; they only differ in how they collect the arguments.
+/// `enum_ext_impl!` also uses it, with `define_enum` set to false so only the impls are generated for an enum
+/// that's defined elsewhere.
+///
+/// # Arguments
+///
+/// * `attrs` - The attributes of the enum, excluding any `enum_def` attribute.
+/// * `vis` - The visibility of the enum.
+/// * `name` - The identifier of the enum.
+/// * `generics` - The generic parameters of the enum. Only lifetimes are supported, on enums with data.
+/// * `variants` - A punctuated list of the variants of the enum.
+/// * `args` - The parsed `enum_def` arguments.
+/// * `define_enum` - Whether to emit the enum definition itself.
+pub(crate) fn generate_expanded_enum(
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    generics: &Generics,
+    variants: &Punctuated<Variant, Comma>,
+    args: EnumDefArgs,
+    define_enum: bool,
+) -> Result<TokenStream2, EnumMacroError> {
+    if generics.type_params().next().is_some() || generics.const_params().next().is_some() {
+        return Err(EnumMacroError::ParseError(format!(
+            "{} can't have type or const parameters, only lifetimes are supported",
+            name
+        )));
+    }
+    let is_complex = variants.iter().any(|variant| !variant.fields.is_empty());
+    if !generics.params.is_empty() && !is_complex {
+        return Err(EnumMacroError::ParseError(format!(
+            "{} has lifetime parameters but no variant carries data to use them",
+            name
+        )));
+    }
+    if variants.is_empty() {
+        return Ok(generate_empty_enum(attrs, vis, name, define_enum));
+    }
+    if is_complex {
+        return generate_complex_enum(attrs, vis, name, generics, variants, args, define_enum);
+    }
+
+    let mut args = args;
+    if args.serde.is_some() && (args.web || args.embedded) {
+        return Err(EnumMacroError::ParseError(format!(
+            "Serde can't be combined with {}, which already serializes the variants",
+            if args.web { "Web" } else { "Embedded" }
+        )));
+    }
+    if args.embedded {
+        if args.web {
+            return Err(EnumMacroError::ParseError(
+                "Embedded and Web can't be combined, they serialize the variants differently"
+                    .to_string(),
+            ));
+        }
+        // the smallest types that fit, unless they're set explicitly
+        if args.int_type.is_none() && repr_int_type(&attrs).is_none() {
+            args.int_type = Some(LitStr::new("auto", Span::call_site()));
+        }
+        if args.ordinal_type.is_none() {
+            let ordinal_type = if variants.len() <= 256 { "u8" } else { "u16" };
+            args.ordinal_type = Some(LitStr::new(ordinal_type, Span::call_site()));
+        }
+    }
+
+    let no_alloc = if args.embedded {
+        Some("Embedded")
+    } else if args.heapless {
+        Some("Heapless")
+    } else {
+        None
+    };
+    if let Some(no_alloc) = no_alloc {
+        for (set, option) in [
+            (args.web, "Web"),
+            (args.into_string, "IntoString"),
+            (args.compare_schema, "CompareSchema"),
+        ] {
+            if set {
+                return Err(EnumMacroError::ParseError(format!(
+                    "{} can't be combined with {}, which leaves out the allocating helpers",
+                    option, no_alloc
+                )));
+            }
+        }
+    }
+    if args.otel_key.is_some() && !args.otel {
+        return Err(EnumMacroError::ParseError(
+            "OtelKey sets the key of otel_key_value(), which needs Otel".to_string(),
+        ));
+    }
+    // the helpers that need std (and an allocator), left out without the std feature and for Embedded and Heapless
+    let std_helpers = cfg!(feature = "std") && no_alloc.is_none();
+
+    // placeholders
+    let mut _other_type_str = "".to_string();
+
+    // the first IntType is the repr of the enum, any others only get conversion functions.
+    let mut int_types = Vec::new();
+    if let Some(lit_str) = args.int_type {
+        for int_type_str in split_int_types(&lit_str.value()) {
+            let int_type_str = if int_type_str == "auto" {
+                smallest_int_type(&name, variants)?
+            } else {
+                int_type_str
+            };
+            if !valid_int_type(int_type_str) {
+                return Err(EnumMacroError::ParseError(format!(
+                    "Invalid IntType: {}",
+                    int_type_str
+                )));
+            }
+
+            let int_type = syn::parse_str::<syn::Type>(int_type_str)
+                .map_err(|error| EnumMacroError::ParseError(format!("Invalid IntType: {}", error)))?
+                .to_token_stream();
+            int_types.push((int_type_str.to_string(), int_type));
+        }
+    }
+    // a #[repr(u8)] written on the enum is its IntType, and can't disagree with the IntType argument
+    let repr_int = repr_int_type(&attrs);
+    match (int_types.first(), &repr_int) {
+        (Some((int_type_str, _)), Some(repr_int)) if int_type_str != repr_int => {
+            return Err(EnumMacroError::ParseError(format!(
+                "IntType {} conflicts with #[repr({})] on {}",
+                int_type_str, repr_int, name
+            )));
+        }
+        (None, Some(repr_int)) => {
+            let int_type = syn::parse_str::<syn::Type>(repr_int)
+                .map_err(|error| EnumMacroError::ParseError(format!("Invalid IntType: {}", error)))?
+                .to_token_stream();
+            int_types.push((repr_int.clone(), int_type));
+        }
+        _ => {}
+    }
+    if int_types.is_empty() {
+        int_types.push(("usize".to_string(), quote! { usize }));
+    }
+    let int_type = int_types[0].1.clone();
+
+    if let Some(lit_str) = args.other_type {
+        _other_type_str = lit_str.value();
+    }
+
+    let lowercase = if args.ascii_case {
+        Lowercase::Ascii
+    } else {
+        Lowercase::Unicode
+    };
+    let lowercase_method = lowercase.str_method();
+    let acronyms = acronym_list(&args.acronyms);
+
+    let mut derive_summary = check_derive_traits(&attrs);
+
+    // DeriveExtra derives are treated as if they were written on the enum, skipping the ones that already are
+    let mut extra_derives = Vec::<syn::Path>::new();
+    if let Some(lit_str) = &args.derive_extra {
+        if !define_enum {
+            return Err(EnumMacroError::ParseError(
+                "DeriveExtra can't be used with enum_ext_impl!".to_string(),
+            ));
+        }
+        let paths = lit_str
+            .parse_with(Punctuated::<syn::Path, Comma>::parse_terminated)
+            .map_err(|error| {
+                EnumMacroError::ParseError(format!("Invalid DeriveExtra: {}", error))
+            })?;
+        for path in paths {
+            let trait_name = path
+                .segments
+                .last()
+                .map(|x| x.ident.to_string())
+                .unwrap_or_default();
+            if !derive_summary.has_trait(&trait_name) {
+                derive_summary.record_trait(&trait_name);
+                extra_derives.push(path);
+            }
+        }
+    }
+
+    // derives can't be added to an enum that's defined elsewhere
+    let no_auto_derives = args.no_auto_derives || !define_enum;
+    if args.auto_copy && no_auto_derives {
+        return Err(EnumMacroError::ParseError(
+            "AutoCopy can't be used with NoAutoDerives or enum_ext_impl!".to_string(),
+        ));
+    }
+    if args.bevy && !define_enum {
+        return Err(EnumMacroError::ParseError(
+            "Bevy can't be used with enum_ext_impl!, which can't add derives".to_string(),
+        ));
+    }
+    let self_cast = if derive_summary.has_copy || args.auto_copy {
+        SelfCast::Copy
+    } else if derive_summary.has_clone || !no_auto_derives {
+        SelfCast::Clone
+    } else {
+        SelfCast::Match
+    };
+
+    // Prepare the enum body with variants
+    let ParsedVariants {
+        enum_body,
+        variant_list,
+        ordered_variants,
+        variant_ordinals,
+        variant_map,
+        to_pascal_split,
+        from_pascal_split,
+        to_snake_case,
+        from_snake_case,
+        to_kebab_case,
+        from_kebab_case,
+        to_sentence_case,
+        from_sentence_case,
+        to_dot_case,
+        from_dot_case,
+        to_title_case,
+        from_title_case,
+        to_flat_case,
+        from_flat_case,
+        to_upper_flat,
+        from_upper_flat,
+        parse_names,
+        doc_aliases,
+        to_aliases,
+        to_colors,
+        variant_count,
+        variant_from_ordinals,
+        to_variant_name,
+        variant_args,
+    } = parse_variants(&name, variants, lowercase, &acronyms)?;
+
+    let ordinal_type_str = args
+        .ordinal_type
+        .as_ref()
+        .map(|lit_str| lit_str.value())
+        .unwrap_or_else(|| "usize".to_string());
+    if variant_count > 0 {
+        let last_ordinal = (variant_count - 1) as i128;
+        if wrap_to_int_type(last_ordinal, &ordinal_type_str) != Some(last_ordinal) {
+            return Err(EnumMacroError::ParseError(format!(
+                "OrdinalType {} can't hold the ordinals of {} variants",
+                ordinal_type_str, variant_count
+            )));
+        }
+    }
+    let ordinal_type = Ident::new(&ordinal_type_str, Span::call_site()).to_token_stream();
+
+    // module level statics, e.g. MY_ENUM_NAMES, for callers that can't call the functions. Only on request, since
+    // two enums with the same SCREAMING_SNAKE_CASE name in one module would collide
+    let statics = if args.ffi {
+        let statics_prefix =
+            join_pascal_words(&name.to_string(), "_", Lowercase::Unicode, &[]).to_uppercase();
+        let names_static = Ident::new(&format!("{}_NAMES", statics_prefix), name.span());
+        let values_static = Ident::new(&format!("{}_VALUES", statics_prefix), name.span());
+        let static_names = ordered_variants.iter().map(|x| x.to_string());
+        let static_values = ordered_variants
+            .iter()
+            .map(|variant_ident| quote! { #name::#variant_ident as #int_type });
+        quote! {
+            /// The names of the variants as declared, in ordinal order
+            #[allow(dead_code)]
+            #vis static #names_static: [&str; #variant_count] = [#(#static_names),*];
+            /// The discriminants of the variants, in ordinal order
+            #[allow(dead_code)]
+            #vis static #values_static: [#int_type; #variant_count] = [#(#static_values),*];
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let entries = ordered_variants
+        .iter()
+        .enumerate()
+        .map(|(ordinal, variant_ident)| {
+            let variant_name_str = variant_ident.to_string();
+            let ordinal = proc_macro2::Literal::usize_unsuffixed(ordinal);
+            quote! { (#variant_name_str, #ordinal, #name::#variant_ident as #int_type) }
+        });
+
+    // the ordinals are the discriminants when they're 0, 1, 2... in declaration order, so a Copy enum can just be
+    // cast instead of matching on every variant
+    let sequential = ordered_variants
+        .iter()
+        .zip(variants)
+        .all(|(ordered, variant)| *ordered == variant.ident)
+        && discriminant_values(&name, variants)
+            .iter()
+            .enumerate()
+            .all(|(ordinal, value)| *value == Some(ordinal as i128));
+    let ordinal_body = if sequential && self_cast == SelfCast::Copy {
+        quote! { *self as #ordinal_type }
+    } else {
+        quote! {
+            match self {
+                #variant_ordinals
+            }
+        }
+    };
+
+    // negative ordinals would wrap around when cast to usize
+    let valid_ordinal_check = if ordinal_type_str.starts_with('i') {
+        quote! { ordinal >= 0 && (ordinal as usize) < #variant_count }
+    } else {
+        quote! { (ordinal as usize) < #variant_count }
+    };
+
+    let negative_check = if int_types[0].0.starts_with('i') {
+        quote! {
+            if value < 0 {
+                return #int_type::BITS;
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let schema = schema_string(&name, &variant_map, &ordered_variants);
+    let schema_hash = fnv1a_64(schema.as_bytes());
+
+    let from_name_bytes = ordered_variants.iter().map(|variant_ident| {
+        let name_bytes =
+            syn::LitByteStr::new(variant_ident.to_string().as_bytes(), variant_ident.span());
+        quote! { #name_bytes => Some(#name::#variant_ident), }
+    });
+    let from_name_bytes = quote! { #(#from_name_bytes)* };
+
+    let mut enum_fns = quote! {
+        /// The name, ordinal, and discriminant of every variant, in ordinal order
+        pub const ENTRIES: [(&'static str, #ordinal_type, #int_type); #variant_count] = [#(#entries),*];
+
+        /// Returns the discriminant of the variant with the given ordinal, without constructing the variant
+        /// * For example, MyEnum::discriminant_of(1) returns Some(20) when the second variant is B = 20
+        pub const fn discriminant_of(ord: usize) -> Option<#int_type> {
+            if ord < #variant_count {
+                Some(Self::ENTRIES[ord].2)
+            } else {
+                None
+            }
+        }
+        /// Returns the name as declared of the variant with the given ordinal, without constructing the variant
+        pub const fn name_of(ord: usize) -> Option<&'static str> {
+            if ord < #variant_count {
+                Some(Self::ENTRIES[ord].0)
+            } else {
+                None
+            }
+        }
+
+        /// Returns the variant names and discriminants in ordinal order, e.g. "Open=1;Done=2;"
+        pub const fn schema() -> &'static str {
+            #schema
+        }
+        /// Returns a hash of the variant names and discriminants in ordinal order, computed at compile time.
+        /// * Two definitions of the enum have the same hash only if their variants match, which makes it cheap to detect
+        ///   an outdated definition, e.g. during a handshake between services
+        pub const fn schema_hash() -> u64 {
+            #schema_hash
+        }
+
+        /// Returns the number of bits needed to store any discriminant (at least 1), e.g. 3 for discriminants 0 to 7
+        /// * Useful for bit-packed formats. A negative discriminant needs the full width of the IntType
+        pub const fn bits_required() -> u32 {
+            let mut bits = 1;
+            let mut i = 0;
+            while i < #variant_count {
+                let value = Self::ENTRIES[i].2;
+                #negative_check
+                let needed = #int_type::BITS - value.leading_zeros();
+                if needed > bits {
+                    bits = needed;
+                }
+                i += 1;
+            }
+            bits
+        }
+
+        /// Returns an array of all variants in the enum
+        #[inline]
+        pub const fn list() -> [#name; #variant_count] {
+            [#variant_list]
+        }
+        /// The variants in ordinal order, shared by the functions returning `&'static Self`
+        const LIST: [#name; #variant_count] = #name::list();
+        /// Returns all variants as a `'static` slice, in ordinal order. Unlike [`Self::list`] nothing is copied, so
+        /// it can be passed straight to APIs taking `&[Self]`
+        #[inline]
+        pub const fn all() -> &'static [Self] {
+            &Self::LIST
+        }
+        /// Returns the number of variants in the enum
+        #[inline]
+        pub const fn count() -> usize {
+            #variant_count
+        }
+        /// Returns the ordinal of the variant
+        #[inline]
+        pub const fn ordinal(&self) -> #ordinal_type {
+            #ordinal_body
+        }
+        /// Returns true if the ordinal is valid for the enum
+        #[inline]
+        pub const fn valid_ordinal(ordinal : #ordinal_type) -> bool {
+            #valid_ordinal_check
+        }
+        /// Returns &Self from the ordinal.
+        pub const fn ref_from_ordinal(ord: #ordinal_type) -> Option<&'static Self> {
+            if !Self::valid_ordinal(ord) {
+                return None;
+            }
+            Some(&Self::LIST[ord as usize])
+        }
+        /// Returns an iterator over the variants in the enum
+        pub fn iter() -> impl Iterator<Item = &'static #name> {
+            Self::LIST.iter()
+        }
+
+        /// Returns the variants from `from` to `to` (inclusive) by ordinal.
+        /// * Returns an empty slice if `from` comes after `to`
+        pub const fn range_inclusive(from: &Self, to: &Self) -> &'static [Self] {
+            let start = from.ordinal() as usize;
+            let end = to.ordinal() as usize;
+            if start > end {
+                return &[];
+            }
+            let (_, tail) = Self::LIST.split_at(start);
+            let (range, _) = tail.split_at(end - start + 1);
+            range
+        }
+        /// Returns an iterator over the variants from `from` to `to` (inclusive) by ordinal.
+        pub fn iter_between(from: &Self, to: &Self) -> impl Iterator<Item = &'static #name> {
+            Self::range_inclusive(from, to).iter()
+        }
+
+        /// Returns the variant after this one by ordinal, or None if this is the last variant
+        pub const fn next(&self) -> Option<&'static Self> {
+            let ord = self.ordinal() as usize + 1;
+            if ord >= #variant_count {
+                return None;
+            }
+            Some(&Self::LIST[ord])
+        }
+        /// Returns the variant before this one by ordinal, or None if this is the first variant
+        pub const fn previous(&self) -> Option<&'static Self> {
+            match self.ordinal().checked_sub(1) {
+                Some(ord) => Self::ref_from_ordinal(ord),
+                None => None,
+            }
+        }
+        /// Returns the variant `n` steps after this one by ordinal, wrapping around after the last variant
+        #[allow(clippy::modulo_one)]
+        pub const fn wrapping_add(&self, n: usize) -> &'static Self {
+            &Self::LIST[(self.ordinal() as usize + n % #variant_count) % #variant_count]
+        }
+        /// Returns the variant `n` steps before this one by ordinal, wrapping around before the first variant
+        #[allow(clippy::modulo_one)]
+        pub const fn wrapping_sub(&self, n: usize) -> &'static Self {
+            &Self::LIST[(self.ordinal() as usize + #variant_count - n % #variant_count) % #variant_count]
+        }
+
+        /// Returns every ordered pair of variants, e.g. (A, A), (A, B), (B, A), (B, B), for table driven tests
+        pub fn all_pairs() -> impl Iterator<Item = (Self, Self)> {
+            fn nth(ord: usize) -> #name {
+                match ord {
+                    #variant_from_ordinals
+                    _ => None,
+                }
+                .unwrap()
+            }
+            (0..#variant_count).flat_map(|a| (0..#variant_count).map(move |b| (nth(a), nth(b))))
+        }
+        /// Returns every variant paired with every value, e.g. (A, 1), (A, 2), (B, 1), (B, 2), for table driven tests
+        pub fn all_with<T: Clone>(values: &[T]) -> impl Iterator<Item = (Self, T)> + '_ {
+            fn nth(ord: usize) -> #name {
+                match ord {
+                    #variant_from_ordinals
+                    _ => None,
+                }
+                .unwrap()
+            }
+            (0..#variant_count).flat_map(move |a| values.iter().map(move |value| (nth(a), value.clone())))
+        }
+
+        /// Returns the name of the variant as declared
+        /// * For example, MyEnum::InQA.variant_name() returns "InQA"
+        pub const fn variant_name(&self) -> &'static str {
+            match self {
+                #to_variant_name
+            }
+        }
+
+        /// Returns the name of the variant as declared, the same as `variant_name()`, for APIs expecting `as_str()`
+        /// * For example, MyEnum::InQA.as_str() returns "InQA"
+        #[inline]
+        pub const fn as_str(&self) -> &'static str {
+            self.variant_name()
+        }
+
+        /// Returns the variant from its name as declared, given as bytes, without UTF-8 validation or allocation
+        /// * For example, MyEnum::from_ascii_bytes(b"InQA") returns Some(MyEnum::InQA)
+        pub const fn from_ascii_bytes(b: &[u8]) -> Option<Self> {
+            match b {
+                #from_name_bytes
+                _ => None,
+            }
+        }
+
+        /// Returns the variant name in spaced PascalCase
+        /// * For example, MyEnum::InQA.pascal_spaced() returns "In QA"
+        pub const fn pascal_spaced(&self) -> &'static str {
+            match self {
+                #to_pascal_split
+            }
+        }
+
+        /// Returns the variant from the spaced PascalCase name
+        /// * For example, MyEnum::from_pascal_spaced("In QA") returns Some(MyEnum::InQA)
+        pub fn from_pascal_spaced(s: &str) -> Option<Self> {
+            match s {
+                #from_pascal_split
+                _ => None,
+            }
+        }
+
+        /// Returns the variant name in snake_case
+        /// * For example, MyEnum::InQA.snake_case() returns "in_qa"
+        pub const fn snake_case(&self) -> &'static str {
+            match self {
+                #to_snake_case
+            }
+        }
+
+        /// Returns the variant from the snake_case name
+        /// * For example, MyEnum::from_snake_case("in_qa") returns Some(MyEnum::InQA)
+        pub fn from_snake_case(s: &str) -> Option<Self> {
+            match s {
+                #from_snake_case
+                _ => None,
+            }
+        }
+
+        /// Returns the variant name in kebab-case
+        /// * For example, MyEnum::InQA.kebab_case() returns "in-qa"
+        pub const fn kebab_case(&self) -> &'static str {
+            match self {
+                #to_kebab_case
+            }
+        }
+
+        /// Returns the variant from the kebab-case name
+        /// * For example, MyEnum::from_kebab_case("in-qa") returns Some(MyEnum::InQA)
+        pub fn from_kebab_case(s: &str) -> Option<Self> {
+            match s {
+                #from_kebab_case
+                _ => None,
+            }
+        }
+
+        /// Returns the variant name in Sentence case, for user-facing text. Only the first word is capitalized
+        /// * For example, MyEnum::InQAReview.sentence_case() returns "In qa review"
+        pub const fn sentence_case(&self) -> &'static str {
+            match self {
+                #to_sentence_case
+            }
+        }
+
+        /// Returns the variant from the Sentence case name
+        /// * For example, MyEnum::from_sentence_case("In qa review") returns Some(MyEnum::InQAReview)
+        pub fn from_sentence_case(s: &str) -> Option<Self> {
+            match s {
+                #from_sentence_case
+                _ => None,
+            }
+        }
+
+        /// Returns the variant name in dot.case, for config keys and metric names
+        /// * For example, MyEnum::FinalQA.dot_case() returns "final.qa"
+        pub const fn dot_case(&self) -> &'static str {
+            match self {
+                #to_dot_case
+            }
+        }
+
+        /// Returns the variant from the dot.case name
+        /// * For example, MyEnum::from_dot_case("final.qa") returns Some(MyEnum::FinalQA)
+        pub fn from_dot_case(s: &str) -> Option<Self> {
+            match s {
+                #from_dot_case
+                _ => None,
+            }
+        }
+
+        /// Returns the variant name in Title Case, with every word capitalized and the rest of it lowercased
+        /// * For example, MyEnum::InQAReview.title_case() returns "In Qa Review"
+        pub const fn title_case(&self) -> &'static str {
+            match self {
+                #to_title_case
+            }
+        }
+
+        /// Returns the variant from the Title Case name
+        /// * For example, MyEnum::from_title_case("In Qa Review") returns Some(MyEnum::InQAReview)
+        pub fn from_title_case(s: &str) -> Option<Self> {
+            match s {
+                #from_title_case
+                _ => None,
+            }
+        }
+
+        /// Returns the variant name in flatcase
+        /// * For example, MyEnum::FinalCodeReview.flat_case() returns "finalcodereview"
+        #[doc(alias = "lowercase")]
+        pub const fn flat_case(&self) -> &'static str {
+            match self {
+                #to_flat_case
+            }
+        }
+
+        /// Returns the variant from the flatcase name
+        /// * For example, MyEnum::from_flat_case("finalcodereview") returns Some(MyEnum::FinalCodeReview)
+        #[doc(alias = "from_lowercase")]
+        pub fn from_flat_case(s: &str) -> Option<Self> {
+            match s {
+                #from_flat_case
+                _ => None,
+            }
+        }
+
+        /// Returns the variant name in UPPERFLATCASE
+        /// * For example, MyEnum::FinalCodeReview.upper_flat() returns "FINALCODEREVIEW"
+        #[doc(alias = "uppercase")]
+        pub const fn upper_flat(&self) -> &'static str {
+            match self {
+                #to_upper_flat
+            }
+        }
+
+        /// Returns the variant from the UPPERFLATCASE name
+        /// * For example, MyEnum::from_upper_flat("FINALCODEREVIEW") returns Some(MyEnum::FinalCodeReview)
+        #[doc(alias = "from_uppercase")]
+        pub fn from_upper_flat(s: &str) -> Option<Self> {
+            match s {
+                #from_upper_flat
+                _ => None,
+            }
+        }
+
+    };
+
+    if std_helpers {
+        enum_fns.extend(quote! {
+            /// Returns the ordinal of the first variant whose `form` of the name matches `s`, ignoring case, surrounding
+            /// whitespace and repeated inner whitespace
+            fn lenient_ordinal(s: &str, form: fn(&Self) -> &'static str) -> Option<usize> {
+                let s = s.split_whitespace().collect::<::std::vec::Vec<_>>().join(" ").#lowercase_method();
+                Self::iter().position(|x| form(x).#lowercase_method() == s)
+            }
+            /// Returns the variant from the spaced PascalCase name, ignoring case and extra whitespace
+            /// * For example, MyEnum::from_pascal_spaced_lenient("  in   qa ") returns Some(MyEnum::InQA)
+            pub fn from_pascal_spaced_lenient(s: &str) -> Option<Self> {
+                match Self::lenient_ordinal(s, Self::pascal_spaced)? {
+                    #variant_from_ordinals
+                    _ => None,
+                }
+            }
+            /// Returns the variant from the snake_case name, ignoring case and surrounding whitespace
+            /// * For example, MyEnum::from_snake_case_lenient(" IN_QA ") returns Some(MyEnum::InQA)
+            pub fn from_snake_case_lenient(s: &str) -> Option<Self> {
+                match Self::lenient_ordinal(s, Self::snake_case)? {
+                    #variant_from_ordinals
+                    _ => None,
+                }
+            }
+            /// Returns the variant from the kebab-case name, ignoring case and surrounding whitespace
+            /// * For example, MyEnum::from_kebab_case_lenient(" In-QA ") returns Some(MyEnum::InQA)
+            pub fn from_kebab_case_lenient(s: &str) -> Option<Self> {
+                match Self::lenient_ordinal(s, Self::kebab_case)? {
+                    #variant_from_ordinals
+                    _ => None,
+                }
+            }
+        });
+    }
+
+    append_code_fns(&mut enum_fns, &name, &variant_args)?;
+    let collection_types = if args.collections {
+        make_collection_types(&vis, &name, variant_count)
+    } else {
+        TokenStream2::new()
+    };
+    let display_impl = match &args.display {
+        Some(case) => {
+            if derive_summary.has_trait("Display") {
+                return Err(EnumMacroError::ParseError(format!(
+                    "Display = \"{}\" conflicts with the Display derived for {}",
+                    case.value(),
+                    name
+                )));
+            }
+            make_display_impl(&name, &case.value())
+        }
+        None => TokenStream2::new(),
+    };
+    let web_types = if args.web {
+        make_web_types(
+            &vis,
+            &name,
+            derive_summary.has_trait("Display") || args.display.is_some(),
+        )
+    } else {
+        TokenStream2::new()
+    };
+    let sorted_check = match &args.require_sorted {
+        Some(sort_by) => check_sorted(&name, variants, &sort_by.value()),
+        None => TokenStream2::new(),
+    };
+    if let Some(separator) = &args.custom_case {
+        enum_fns.extend(make_custom_case_fns(
+            &name,
+            &ordered_variants,
+            &separator.value(),
+            lowercase,
+            &acronyms,
+        ));
+    }
+    enum_fns.extend(make_proto_name_fns(
+        &name,
+        &ordered_variants,
+        &args.proto_prefix,
+        lowercase,
+        &acronyms,
+    ));
+    append_sort_key_fns(&mut enum_fns, &name, &variant_args, &ordered_variants)?;
+    enum_fns.extend(make_is_variant_fns(&name, &ordered_variants, &acronyms)?);
+    enum_fns.extend(make_group_by_prefix(&name, &ordered_variants, &acronyms));
+    if std_helpers {
+        enum_fns.extend(make_table_fns(variants, &ordered_variants));
     }
-    let decla = (quote! { #vis enum #name }).to_string().trim().to_owned();
-    pretty_print_body.push(decla);
-    pretty_print_body.push(" {\n".to_owned());
 
-    let enum_body = (quote! { #enum_body })
-        .to_string()
-        .trim()
-        .split(",")
-        .map(|x| x.trim())
-        .collect::<Vec<&str>>()
-        .join(",\n    ")
-        .trim()
-        .to_owned();
+    let mut needed_derives = TokenStream2::new();
+    if !extra_derives.is_empty() {
+        needed_derives.extend(quote! {
+            #[derive(#(#extra_derives),*)]
+        });
+    }
 
-    pretty_print_body.push("    ".to_owned());
-    pretty_print_body.push(enum_body);
+    let mut int_type_added = false;
+    for (int_type_str, int_type) in &int_types {
+        int_type_added |= append_int_fns(
+            &mut enum_fns,
+            &name,
+            &variant_map,
+            int_type_str,
+            int_type,
+            &variant_args,
+            self_cast,
+        )?;
+    }
+    let mask_fns_added = append_mask_fns(
+        &mut enum_fns,
+        &name,
+        &variant_map,
+        &int_types[0].0,
+        &int_types[0].1,
+    )?;
+    enum_fns.extend(make_range_fns(
+        &variant_args,
+        &int_types[0].0,
+        &int_types[0].1,
+    )?);
+    if int_type_added {
+        enum_fns.extend(make_gap_fns(
+            &name,
+            variants,
+            &int_types[0].0,
+            &int_types[0].1,
+        )?);
+    }
+    let versioned_type = match (args.versioned, int_type_added) {
+        (true, true) => {
+            let (versioned_fns, versioned_type) = make_versioned_codec(
+                &vis,
+                &name,
+                &int_types[0].0,
+                &int_types[0].1,
+                schema_hash,
+                std_helpers,
+            )?;
+            enum_fns.extend(versioned_fns);
+            versioned_type
+        }
+        (true, false) => {
+            return Err(EnumMacroError::ParseError(format!(
+                "Versioned needs {} to have discriminants",
+                name
+            )))
+        }
+        _ => TokenStream2::new(),
+    };
+    let try_from_type = match (args.try_from, int_type_added) {
+        (true, true) => make_try_from_type(
+            &vis,
+            &name,
+            variants,
+            &int_types[0].0,
+            &int_types[0].1,
+            std_helpers,
+        )?,
+        (true, false) => {
+            return Err(EnumMacroError::ParseError(format!(
+                "TryFrom needs {} to have discriminants",
+                name
+            )))
+        }
+        _ => TokenStream2::new(),
+    };
+    enum_fns.extend(make_options_json(
+        &name,
+        variants,
+        &ordered_variants,
+        &int_types[0].0,
+        int_type_added,
+        &acronyms,
+    ));
 
-    pretty_print_body.push("\n}".to_owned());
+    // names first, then the discriminant (or the ordinal when there are no discriminants)
+    let parse_number = if int_type_added {
+        let from_fn_name = Ident::new(&format!("from_{}", int_types[0].0), Span::call_site());
+        quote! {
+            match s.parse::<#int_type>() {
+                Ok(value) => Self::#from_fn_name(value),
+                Err(_) => None,
+            }
+        }
+    } else {
+        quote! {
+            match s.parse::<usize>() {
+                Ok(ord) => match ord {
+                    #variant_from_ordinals
+                    _ => None,
+                },
+                Err(_) => None,
+            }
+        }
+    };
+    #[allow(unused_mut)]
+    let mut parse_list_fn = if !std_helpers {
+        TokenStream2::new()
+    } else {
+        quote! {
+            /// Parses a `sep` separated list of variants with [`Self::parse`], trimming whitespace around each item
+            /// * For example, MyEnum::parse_list("open, in_qa,done", ',') returns Ok(vec![MyEnum::Open, MyEnum::InQA, MyEnum::Done])
+            /// * An empty (or all whitespace) string is an empty list
+            /// * The error is the first item that isn't a variant
+            pub fn parse_list(s: &str, sep: char) -> ::core::result::Result<::std::vec::Vec<Self>, &str> {
+                if s.trim().is_empty() {
+                    return Ok(::std::vec::Vec::new());
+                }
+                s.split(sep)
+                    .map(|item| {
+                        let item = item.trim();
+                        Self::parse(item).ok_or(item)
+                    })
+                    .collect()
+            }
+        }
+    };
+    // no_std friendly version, without an allocator
+    #[cfg(feature = "heapless")]
+    if args.heapless {
+        parse_list_fn = quote! {
+            /// Parses a `sep` separated list of variants with [`Self::parse`], trimming whitespace around each item
+            /// * For example, MyEnum::parse_list("open, in_qa,done", ',') returns Ok([MyEnum::Open, MyEnum::InQA, MyEnum::Done])
+            /// * An empty (or all whitespace) string is an empty list
+            /// * The list has room for `count()` items
+            /// * The error is the first item that isn't a variant, or doesn't fit
+            pub fn parse_list(
+                s: &str,
+                sep: char,
+            ) -> ::core::result::Result<::heapless::Vec<Self, #variant_count>, &str> {
+                let mut list = ::heapless::Vec::new();
+                if s.trim().is_empty() {
+                    return Ok(list);
+                }
+                for item in s.split(sep) {
+                    let item = item.trim();
+                    match Self::parse(item) {
+                        Some(value) => {
+                            if list.push(value).is_err() {
+                                return Err(item);
+                            }
+                        }
+                        None => return Err(item),
+                    }
+                }
+                Ok(list)
+            }
+        };
+    }
+    let format_list_fn = if !std_helpers {
+        TokenStream2::new()
+    } else {
+        quote! {
+            /// Joins the names of `items` as declared with `sep`, the reverse of [`Self::parse_list`]
+            /// * For example, MyEnum::format_list(&[MyEnum::Open, MyEnum::InQA], ',') returns "Open,InQA"
+            pub fn format_list<'a>(items: impl IntoIterator<Item = &'a Self>, sep: char) -> ::std::string::String
+            where
+                Self: 'a,
+            {
+                let mut result = ::std::string::String::new();
+                for (i, item) in items.into_iter().enumerate() {
+                    if i > 0 {
+                        result.push(sep);
+                    }
+                    result.push_str(item.variant_name());
+                }
+                result
+            }
+        }
+    };
+    enum_fns.extend(quote! {
+        /// Returns the variant from any of its names (as declared, spaced PascalCase, snake_case or kebab-case),
+        /// falling back to parsing `s` as the discriminant
+        /// * For example, MyEnum::parse("in_qa") and MyEnum::parse("3") both return Some(MyEnum::InQA) when InQA = 3
+        /// * Aliases declared with `#[ext(alias = "...")]` are accepted too
+        #doc_aliases
+        pub fn parse(s: &str) -> Option<Self> {
+            match s {
+                #parse_names
+                _ => #parse_number,
+            }
+        }
 
-    pretty_print_body.join("")
+        #parse_list_fn
+
+        /// Returns the aliases of the variant, as declared with `#[ext(alias = "...")]`
+        pub const fn aliases(&self) -> &'static [&'static str] {
+            match self {
+                #to_aliases
+            }
+        }
+
+        /// Returns the terminal color of the variant, as declared with `#[ext(color = "...")]`
+        /// * For example, MyEnum::InQA.color() returns Some("yellow")
+        pub const fn color(&self) -> Option<&'static str> {
+            match self {
+                #to_colors
+            }
+        }
+
+        #format_list_fn
+    });
+
+    if args.auto_copy && !derive_summary.has_copy {
+        match derive_summary.cfg_predicate("Copy") {
+            // Copy is already derived when the predicate holds, so only add it when it doesn't.
+            Some(predicate) => needed_derives.extend(quote! {
+                #[cfg_attr(not(#predicate), derive(Copy))]
+            }),
+            None => needed_derives.extend(quote! {
+                #[derive(Copy)]
+            }),
+        }
+    }
+
+    let mut clone_added = false;
+    if (int_type_added || args.auto_copy) && !derive_summary.has_clone && !no_auto_derives {
+        clone_added = true;
+        match derive_summary.cfg_predicate("Clone") {
+            // Clone is already derived when the predicate holds, so only add it when it doesn't.
+            Some(predicate) => needed_derives.extend(quote! {
+                #[cfg_attr(not(#predicate), derive(Clone))]
+            }),
+            None => needed_derives.extend(quote! {
+                #[derive(Clone)]
+            }),
+        }
+    }
+
+    #[cfg(feature = "bevy")]
+    if args.bevy && !derive_summary.other_derives.iter().any(|x| x == "Reflect") {
+        needed_derives.extend(match &args.integration_cfg {
+            Some(predicate) => quote! {
+                #[cfg_attr(#predicate, derive(::bevy_reflect::Reflect))]
+            },
+            None => quote! {
+                #[derive(::bevy_reflect::Reflect)]
+            },
+        });
+    }
+
+    let mut repl_value = TokenStream2::new();
+    if int_type_added {
+        if repr_int.is_none() {
+            repl_value.extend(quote! {
+                #[repr(#int_type)]
+            });
+        }
+
+        let as_fn_name = Ident::new(&format!("as_{}", int_types[0].0), Span::call_site());
+        enum_fns.extend(quote! {
+            /// Compares two variants by their discriminant values rather than their ordinals
+            #[inline]
+            pub fn cmp_discriminant(&self, other: &Self) -> ::core::cmp::Ordering {
+                self.#as_fn_name().cmp(&other.#as_fn_name())
+            }
+            /// Returns the variant with the largest discriminant value
+            pub fn max_by_value() -> &'static Self {
+                Self::iter().max_by_key(|x| x.#as_fn_name()).unwrap()
+            }
+            /// Returns the variant with the smallest discriminant value
+            pub fn min_by_value() -> &'static Self {
+                Self::iter().min_by_key(|x| x.#as_fn_name()).unwrap()
+            }
+
+            /// Returns the ordinal of the variant whose discriminant is closest to `val`
+            fn closest_ordinal(val: #int_type, prefer_higher: bool) -> usize {
+                let mut best = 0;
+                let mut best_value = Self::ENTRIES[0].2;
+                for (ordinal, (_, _, value)) in Self::ENTRIES.iter().enumerate() {
+                    let distance = value.abs_diff(val);
+                    let best_distance = best_value.abs_diff(val);
+                    let wins_tie = if prefer_higher { *value > best_value } else { *value < best_value };
+                    if distance < best_distance || (distance == best_distance && wins_tie) {
+                        best = ordinal;
+                        best_value = *value;
+                    }
+                }
+                best
+            }
+            /// Returns the variant whose discriminant is closest to `val`, preferring the smaller discriminant on a tie
+            /// * For example, with B9600 = 9600 and B19200 = 19200, closest_to(10000) returns B9600
+            pub fn closest_to(val: #int_type) -> Self {
+                match Self::closest_ordinal(val, false) {
+                    #variant_from_ordinals
+                    _ => None,
+                }
+                .unwrap()
+            }
+            /// Returns the variant whose discriminant is closest to `val`, preferring the larger discriminant on a tie
+            pub fn closest_to_prefer_higher(val: #int_type) -> Self {
+                match Self::closest_ordinal(val, true) {
+                    #variant_from_ordinals
+                    _ => None,
+                }
+                .unwrap()
+            }
+        });
+    }
+
+    let nonzero_size_check = if int_type_added {
+        let (nonzero_fns, nonzero_size_check) =
+            make_nonzero_fns(&name, variants, &int_types[0].0, &int_type)?;
+        enum_fns.extend(nonzero_fns);
+        nonzero_size_check
+    } else {
+        TokenStream2::new()
+    };
+
+    let clone_cfg = if derive_summary.has_clone || clone_added {
+        Some(TokenStream2::new())
+    } else {
+        derive_summary
+            .cfg_predicate("Clone")
+            .map(|predicate| quote! { #[cfg(#predicate)] })
+    };
+    if let Some(clone_cfg) = clone_cfg {
+        // fn's that require Clone
+        enum_fns.extend(quote! {
+            /// Returns Self from the ordinal.
+            #clone_cfg
+            pub const fn from_ordinal(ord: #ordinal_type) -> Option<Self> {
+                match ord {
+                    #variant_from_ordinals
+                    _ => None,
+                }
+            }
+        });
+    }
+
+    // fns and impls for other crates, which IntegrationCfg can gate behind a cfg of the using crate
+    #[allow(unused_mut)]
+    let mut integration_fns = TokenStream2::new();
+    #[allow(unused_mut)]
+    let mut integration_impls = TokenStream2::new();
+
+    #[cfg(feature = "egui")]
+    if args.egui {
+        integration_fns.extend(make_egui_fns(&variant_from_ordinals));
+    }
+
+    let schema_diff_type = if args.compare_schema {
+        let (compare_schema_fns, schema_diff_type) = make_compare_schema(&vis, &name);
+        enum_fns.extend(compare_schema_fns);
+        schema_diff_type
+    } else {
+        TokenStream2::new()
+    };
+
+    enum_fns.extend(make_from_prefix());
+
+    #[cfg(feature = "registry")]
+    if args.registry {
+        integration_impls.extend(make_registry_entry(
+            &name,
+            &ordered_variants,
+            &int_types[0].0,
+        ));
+    }
+
+    let atomic_type = if args.atomic {
+        let (raw_type_str, raw_type) = if int_type_added {
+            (int_types[0].0.as_str(), &int_type)
+        } else {
+            (ordinal_type_str.as_str(), &ordinal_type)
+        };
+        make_atomic_type(
+            &vis,
+            &name,
+            raw_type_str,
+            raw_type,
+            int_type_added,
+            &variant_from_ordinals,
+        )?
+    } else {
+        TokenStream2::new()
+    };
+
+    let other_variants = variant_args
+        .iter()
+        .filter(|(_, args)| args.other)
+        .map(|(variant_ident, _)| variant_ident.to_string())
+        .collect::<Vec<_>>();
+    if other_variants.len() > 1 {
+        return Err(EnumMacroError::VariantError(format!(
+            "only one variant of {} can be #[ext(other)], found {}",
+            name,
+            other_variants.join(", ")
+        )));
+    }
+    // what unknown values deserialize to with SerdeMode = "lenient"
+    #[cfg(any(feature = "serde", feature = "config"))]
+    let fallback = match &args.serde_mode {
+        Some(mode) if mode.value() == "lenient" => Some(match other_variants.first() {
+            Some(other) => {
+                let other = Ident::new(other, Span::call_site());
+                quote! { #name::#other }
+            }
+            None => quote! { <#name as ::core::default::Default>::default() },
+        }),
+        _ => None,
+    };
+
+    #[cfg(feature = "metrics")]
+    if args.metrics {
+        let (metrics_fns, metrics_impls) = make_metrics_fns(&name);
+        integration_fns.extend(metrics_fns);
+        integration_impls.extend(metrics_impls);
+    }
+
+    #[cfg(feature = "otel")]
+    if args.otel {
+        let (otel_fns, otel_impls) = make_otel_fns(&name, &args.otel_key, lowercase);
+        integration_fns.extend(otel_fns);
+        integration_impls.extend(otel_impls);
+    }
+
+    // Serde = "int" and Embedded write the discriminants
+    #[cfg(any(feature = "ts", feature = "avro"))]
+    let serde_names = serde_names(
+        &args.serde,
+        cfg!(feature = "serde")
+            && (args.embedded
+                || args
+                    .serde
+                    .as_ref()
+                    .is_some_and(|serde| serde.value() == "int"))
+            && int_type_added,
+        &name,
+        variants,
+        &ordered_variants,
+        lowercase,
+        &acronyms,
+    );
+
+    #[cfg(feature = "ts")]
+    if args.ts {
+        let (ts_fns, ts_impls) = make_ts_fns(&name, &serde_names);
+        integration_fns.extend(ts_fns);
+        integration_impls.extend(ts_impls);
+    }
+
+    #[cfg(feature = "fuzzy")]
+    if args.fuzzy {
+        integration_fns.extend(make_fuzzy_fns(lowercase));
+    }
+
+    #[cfg(feature = "ansi")]
+    if args.ansi {
+        integration_fns.extend(make_ansi_fns(&variant_args));
+    }
+
+    #[cfg(feature = "jni")]
+    if args.jni {
+        integration_fns.extend(make_jni_fns(&ordered_variants, lowercase, &acronyms));
+    }
+
+    #[cfg(feature = "config")]
+    if args.config {
+        let (config_fns, config_impls) = make_config_fns(
+            &name,
+            &int_types,
+            int_type_added,
+            &variant_from_ordinals,
+            derive_summary.has_trait("Deserialize"),
+            &fallback,
+        );
+        integration_fns.extend(config_fns);
+        integration_impls.extend(config_impls);
+    }
+
+    #[cfg(feature = "arrow")]
+    if args.arrow {
+        integration_fns.extend(make_arrow_fns(
+            &int_types,
+            int_type_added,
+            &ordered_variants,
+        ));
+    }
+
+    #[cfg(feature = "deku")]
+    if args.deku && int_type_added {
+        integration_impls.extend(make_deku_impls(&name, &int_types));
+    }
+
+    #[cfg(feature = "speedy")]
+    if args.speedy && int_type_added {
+        integration_impls.extend(make_speedy_impls(&name, &int_types));
+    }
+
+    #[cfg(feature = "godot")]
+    if args.godot {
+        integration_impls.extend(make_godot_impls(
+            &name,
+            variants,
+            &ordered_variants,
+            &int_types[0].0,
+            int_type_added,
+            &acronyms,
+        ));
+    }
+
+    #[cfg(feature = "flatbuffers")]
+    if let Some(fb_type) = &args.flatbuffers {
+        integration_impls.extend(make_flatbuffers_impls(&name, fb_type, &ordered_variants));
+    }
+
+    #[cfg(feature = "capnp")]
+    if let Some(capnp_type) = &args.capnp {
+        integration_impls.extend(make_capnp_impls(&name, capnp_type, &ordered_variants));
+    }
+
+    #[cfg(feature = "serde")]
+    if args.collections {
+        integration_impls.extend(make_serde_collection_impls(&name, &ordered_variants));
+    }
+
+    #[cfg(feature = "serde")]
+    let serde_case = args
+        .serde
+        .as_ref()
+        .map(LitStr::value)
+        .filter(|serde| serde != "int");
+    #[cfg(feature = "serde")]
+    if args.serde.is_some() && serde_case.is_none() && !int_type_added {
+        return Err(EnumMacroError::ParseError(format!(
+            "Serde = \"int\" needs {} to have discriminants",
+            name
+        )));
+    }
+
+    #[cfg(feature = "serde")]
+    if (args.embedded || (args.serde.is_some() && serde_case.is_none())) && int_type_added {
+        integration_impls.extend(make_serde_int_impls(
+            &name,
+            &int_types[0].0,
+            &int_types[0].1,
+            &derive_summary,
+            args.config,
+            &fallback,
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    if let Some(case) = &serde_case {
+        integration_impls.extend(make_serde_case_impls(
+            &name,
+            case,
+            &derive_summary,
+            args.config,
+            &fallback,
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    if args.web {
+        integration_impls.extend(make_serde_web_impls(
+            &name,
+            &derive_summary,
+            args.config,
+            &fallback,
+        ));
+    }
+
+    #[cfg(feature = "avro")]
+    if args.avro {
+        let (avro_fns, avro_impls) = make_avro_fns(&name, &serde_names)?;
+        integration_fns.extend(avro_fns);
+        integration_impls.extend(avro_impls);
+    }
+
+    let integration_cfg = args
+        .integration_cfg
+        .as_ref()
+        .map(|predicate| quote! { #[cfg(#predicate)] });
+    let mut integrations = TokenStream2::new();
+    if !integration_fns.is_empty() {
+        integrations.extend(quote! {
+            #integration_cfg
+            impl #name {
+                #integration_fns
+            }
+        });
+    }
+    if !integration_impls.is_empty() {
+        integrations.extend(match &integration_cfg {
+            Some(integration_cfg) => quote! {
+                #integration_cfg
+                const _: () = {
+                    #integration_impls
+                };
+            },
+            None => integration_impls,
+        });
+    }
+
+    #[cfg(feature = "step_trait")]
+    let step_impl = {
+        let copy_cfg = derive_summary.cfg_predicate("Copy");
+        let partial_ord_cfg = derive_summary.cfg_predicate("PartialOrd");
+        let has_copy = derive_summary.has_copy || copy_cfg.is_some() || args.auto_copy;
+        let has_partial_ord = derive_summary.has_partial_ord || partial_ord_cfg.is_some();
+        if has_copy && has_partial_ord {
+            let step_impl = make_step_impl(&name, &ordinal_type);
+            let predicates = [copy_cfg, partial_ord_cfg]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            if predicates.is_empty() {
+                step_impl
+            } else {
+                quote! {
+                    #[cfg(all(#(#predicates),*))]
+                    #step_impl
+                }
+            }
+        } else {
+            TokenStream2::new()
+        }
+    };
+    #[cfg(not(feature = "step_trait"))]
+    let step_impl = TokenStream2::new();
+
+    #[cfg(feature = "ratatui")]
+    let selector_type = if args.ratatui {
+        let (ratatui_fns, selector_type) = make_ratatui_fns(&vis, &name, &integration_cfg);
+        integrations.extend(quote! {
+            #integration_cfg
+            impl #name {
+                #ratatui_fns
+            }
+        });
+        selector_type
+    } else {
+        TokenStream2::new()
+    };
+    #[cfg(not(feature = "ratatui"))]
+    let selector_type = TokenStream2::new();
+
+    #[cfg(feature = "serde")]
+    let serde_mask_type = if mask_fns_added {
+        make_serde_mask_type(&vis, &name, &int_types[0].1, &integration_cfg)
+    } else {
+        TokenStream2::new()
+    };
+    #[cfg(not(feature = "serde"))]
+    let serde_mask_type = {
+        let _ = mask_fns_added;
+        TokenStream2::new()
+    };
+
+    let pretty_print_body = make_pretty_print(
+        attrs.clone(),
+        needed_derives.clone(),
+        vis.clone(),
+        name.clone(),
+        enum_body.clone(),
+        repl_value.clone(),
+    );
+
+    let mut expanded_enum = TokenStream2::new();
+    if define_enum {
+        let mut variants_doc =
+            variants_doc_lines(&name, variants, &ordered_variants, lowercase, &acronyms);
+        if int_type_added {
+            variants_doc.extend(gaps_doc_lines(&name, variants, &int_types[0].0));
+        }
+        let variants_doc = variants_doc.into_iter().map(|line| {
+            let line = if line.is_empty() {
+                line
+            } else {
+                format!(" {}", line)
+            };
+            quote! { #[doc = #line] }
+        });
+        // a macro_rules! helper can't be reached from an impl-only expansion, so it needs the definition
+        expanded_enum.extend(make_for_each_macro(
+            &name,
+            variants,
+            &ordered_variants,
+            &int_type,
+        ));
+        expanded_enum.extend(quote! {
+            #(#attrs)*
+            #(#variants_doc)*
+            #needed_derives
+            #repl_value
+            #vis enum #name {
+                #enum_body
+            }
+        });
+    }
+
+    let as_ref_impl = make_as_ref_impl(&name, generics, &derive_summary);
+    let into_string_impls = if args.into_string {
+        quote! {
+            impl From<#name> for ::std::string::String {
+                /// Returns the name of the variant as declared
+                #[inline]
+                fn from(val: #name) -> Self {
+                    ::std::string::String::from(val.variant_name())
+                }
+            }
+
+            impl From<&#name> for ::std::string::String {
+                /// Returns the name of the variant as declared
+                #[inline]
+                fn from(val: &#name) -> Self {
+                    ::std::string::String::from(val.variant_name())
+                }
+            }
+
+            impl From<#name> for ::std::borrow::Cow<'static, str> {
+                /// Returns the name of the variant as declared, without allocating
+                #[inline]
+                fn from(val: #name) -> Self {
+                    ::std::borrow::Cow::Borrowed(val.variant_name())
+                }
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+    expanded_enum.extend(quote! {
+        impl #name {
+            #enum_fns
+
+            /// Returns a pretty printed string of the enum definition
+            pub const fn pretty_print() -> &'static str {
+                #pretty_print_body
+            }
+        }
+
+        #into_string_impls
+
+        #as_ref_impl
+
+        #step_impl
+
+        #nonzero_size_check
+
+        #integrations
+
+        #atomic_type
+
+        #schema_diff_type
+
+        #serde_mask_type
+        #selector_type
+        #versioned_type
+        #try_from_type
+        #collection_types
+        #web_types
+        #display_impl
+        #sorted_check
+
+        #statics
+    });
+
+    if int_type_added {
+        // with TryFrom, the first IntType gets TryFrom<IntType> from make_try_from_type instead
+        let skip = usize::from(args.try_from);
+        for (int_type_str, int_type) in int_types.iter().skip(skip) {
+            let from_fn_name_str = format!("from_{}", int_type_str);
+            let from_fn_name = Ident::new(&from_fn_name_str, Span::call_site());
+            let impl_from = quote! {
+                impl From<#int_type> for #name {
+                    /// Returns the enum variant from the integer value.
+                    /// <br><br>
+                    /// This will panic if the integer value is not a valid discriminant. Use the #from_fn_name or `try_from` functions
+                    /// instead if you want to handle invalid values.
+                    #[inline]
+                    fn from(val: #int_type) -> Self {
+                        Self::#from_fn_name(val).unwrap()
+                    }
+                }
+            };
+
+            expanded_enum.extend(impl_from);
+        }
+
+        expanded_enum.extend(make_try_from_impls(&name, &int_types, args.try_from));
+    }
+
+    Ok(expanded_enum)
 }
 
 #[cfg(test)]
@@ -442,7 +6633,235 @@ mod test {
 
     #[test]
     fn pascal_case() {
-        assert_eq!(super::split_pascal_case("MyEnum"), "My Enum");
-        assert_eq!(super::split_pascal_case("InQA"), "In QA");
+        assert_eq!(super::split_pascal_case("MyEnum", &[]), "My Enum");
+        assert_eq!(super::split_pascal_case("InQA", &[]), "In QA");
+        assert_eq!(super::split_pascal_case("QAInDev", &[]), "QA In Dev");
+        assert_eq!(super::split_pascal_case("HTTPServer", &[]), "HTTP Server");
+        assert_eq!(super::split_pascal_case("IOError2", &[]), "IO Error2");
+        assert_eq!(super::split_pascal_case("A", &[]), "A");
+    }
+
+    #[test]
+    fn snake_and_kebab_case() {
+        use super::Lowercase::{Ascii, Unicode};
+        assert_eq!(super::to_snake_case("MyEnum", Unicode, &[]), "my_enum");
+        assert_eq!(super::to_snake_case("InQA", Unicode, &[]), "in_qa");
+        assert_eq!(
+            super::to_kebab_case("FinalCodeReview", Unicode, &[]),
+            "final-code-review"
+        );
+        assert_eq!(super::to_snake_case("QAInDev", Unicode, &[]), "qa_in_dev");
+        assert_eq!(
+            super::to_kebab_case("HTTPServerError", Unicode, &[]),
+            "http-server-error"
+        );
+        // 'İ' lowercases to two characters, which must both be kept
+        assert_eq!(
+            super::to_snake_case("İstanbulTrip", Unicode, &[]),
+            "i\u{307}stanbul_trip"
+        );
+        assert_eq!(
+            super::to_snake_case("İstanbulTrip", Ascii, &[]),
+            "İstanbul_trip"
+        );
+        assert_eq!(
+            super::to_sentence_case("InQAReview", Unicode, &[]),
+            "In qa review"
+        );
+        assert_eq!(
+            super::to_sentence_case("QAInDev", Unicode, &[]),
+            "QA in dev"
+        );
+        assert_eq!(super::to_sentence_case("Done", Unicode, &[]), "Done");
+        assert_eq!(
+            super::to_title_case("FinalCodeReview", Unicode, &[]),
+            "Final Code Review"
+        );
+        assert_eq!(super::to_title_case("QAInDev", Unicode, &[]), "Qa In Dev");
+        assert_eq!(super::to_dot_case("FinalQA", Unicode, &[]), "final.qa");
+        let acronyms = ["HTTP", "QA", "ID"].map(String::from);
+        assert_eq!(
+            super::split_pascal_case("HTTPServerID", &acronyms),
+            "HTTP Server ID"
+        );
+        assert_eq!(super::split_pascal_case("QAIDList", &[]), "QAID List");
+        assert_eq!(
+            super::split_pascal_case("QAIDList", &acronyms),
+            "QA ID List"
+        );
+        assert_eq!(
+            super::split_pascal_case("MyHTTP2Client", &acronyms),
+            "My HTTP2 Client"
+        );
+        // an acronym that starts a longer word isn't split off
+        assert_eq!(super::split_pascal_case("IDentity", &acronyms), "I Dentity");
+        assert_eq!(
+            super::to_snake_case("QAIDList", Unicode, &acronyms),
+            "qa_id_list"
+        );
+        assert_eq!(
+            super::to_title_case("InQAReview", Unicode, &acronyms),
+            "In QA Review"
+        );
+        assert_eq!(
+            super::to_sentence_case("HTTPServerID", Unicode, &acronyms),
+            "HTTP server ID"
+        );
+        assert_eq!(
+            super::to_dot_case("HTTPServerError", Unicode, &[]),
+            "http.server.error"
+        );
+        assert_eq!(super::to_title_case("KÖLNTrip", Unicode, &[]), "Köln Trip");
+        assert_eq!(super::to_title_case("KÖLNTrip", Ascii, &[]), "KÖln Trip");
+        assert_eq!(
+            super::to_flat_case("FinalCodeReview", Unicode),
+            "finalcodereview"
+        );
+        assert_eq!(super::to_upper_flat_case("InQA", Unicode), "INQA");
+        assert_eq!(super::to_upper_flat_case("Straße", Unicode), "STRASSE");
+        assert_eq!(super::to_upper_flat_case("Straße", Ascii), "STRAßE");
+        assert_eq!(
+            super::to_screaming_snake_case("InQAReview", Unicode, &[]),
+            "IN_QA_REVIEW"
+        );
+        assert_eq!(super::to_kebab_case("ÜberQA", Ascii, &[]), "Über-qa");
+    }
+
+    #[test]
+    fn variants_doc_lines() {
+        let name = syn::parse_str::<syn::Ident>("Status").unwrap();
+        let variants =
+            syn::parse_str::<syn::ItemEnum>("enum Status { Open = 1, InQA, Done = BASE }")
+                .unwrap()
+                .variants;
+        let ordered = variants.iter().map(|x| x.ident.clone()).collect::<Vec<_>>();
+        let lines =
+            super::variants_doc_lines(&name, &variants, &ordered, super::Lowercase::Unicode, &[]);
+        assert_eq!(
+            lines[3..],
+            [
+                "| Variant | Ordinal | Discriminant | snake_case | kebab-case |",
+                "|---|---|---|---|---|",
+                "| `Open` | 0 | 1 | `open` | `open` |",
+                "| `InQA` | 1 | 2 | `in_qa` | `in-qa` |",
+                "| `Done` | 2 | `BASE` | `done` | `done` |",
+            ]
+        );
+    }
+
+    #[test]
+    fn is_variant_fns() {
+        let name = syn::parse_str::<syn::Ident>("MyEnum").unwrap();
+        let is_variant_fns = |variants: &str, acronyms: &[&str]| {
+            let variants = variants
+                .split(',')
+                .map(|x| syn::parse_str::<syn::Ident>(x).unwrap())
+                .collect::<Vec<_>>();
+            let acronyms = acronyms.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+            super::make_is_variant_fns(&name, &variants, &acronyms).map(|x| x.to_string())
+        };
+        assert!(is_variant_fns("r#type", &[])
+            .unwrap()
+            .contains("fn is_type"));
+        // the same snake_case name twice
+        assert!(is_variant_fns("HttpError,HTTPError", &[]).is_err());
+        assert!(is_variant_fns("Io,IO", &[]).is_err());
+        assert!(is_variant_fns("IoError,IOError", &["IO"]).is_err());
+        assert!(is_variant_fns("Open,InQA", &[]).is_ok());
+    }
+
+    #[test]
+    fn fnv1a_64() {
+        assert_eq!(super::fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(super::fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(super::fnv1a_64(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn json_string() {
+        assert_eq!(super::json_string("In QA"), r#""In QA""#);
+        assert_eq!(super::json_string(r#"a "b" \ c"#), r#""a \"b\" \\ c""#);
+        assert_eq!(super::json_string("a\nb\u{1}"), r#""a\nb\u0001""#);
+    }
+
+    #[test]
+    fn eval_int_expr() {
+        let eval = |s: &str| super::eval_int_expr(&syn::parse_str(s).unwrap(), &|_| None);
+        assert_eq!(eval("10"), Some(10));
+        assert_eq!(eval("-10"), Some(-10));
+        assert_eq!(eval("0x10 + 1"), Some(17));
+        assert_eq!(eval("(1 << 4) | 1"), Some(17));
+        assert_eq!(eval("300 as u8"), Some(44));
+        assert_eq!(eval("-1 as u8"), Some(255));
+        assert_eq!(eval("SOME_CONST"), None);
+        assert_eq!(eval("1 / 0"), None);
+    }
+
+    #[test]
+    fn implied_values() {
+        let name = syn::parse_str::<syn::Ident>("MyEnum").unwrap();
+        let variants = syn::parse_str::<syn::ItemEnum>(
+            "enum MyEnum { A = 5, B = Self::A as isize + 1, C, D = MyEnum::C * 2, E = Other::A, F = SOME_CONST }",
+        )
+        .unwrap()
+        .variants;
+        assert_eq!(
+            super::discriminant_values(&name, &variants),
+            vec![Some(5), Some(6), Some(7), Some(14), None, None]
+        );
+    }
+
+    #[test]
+    fn discriminant_gaps() {
+        let name = syn::parse_str::<syn::Ident>("MyEnum").unwrap();
+        let gaps = |variants: &str, int_type_str: &str| {
+            let variants = syn::parse_str::<syn::ItemEnum>(variants).unwrap().variants;
+            super::discriminant_gaps(&name, &variants, int_type_str).map(|x| (x.gaps, x.next_free))
+        };
+        assert_eq!(
+            gaps("enum MyEnum { A = 1, B = 4, C }", "u8"),
+            Some((vec![(2, 4)], Some(6)))
+        );
+        // the whole i128 range, where the distance between the values doesn't fit an i128
+        assert_eq!(
+            gaps("enum MyEnum { A = -170141183460469231731687303715884105727 - 1, B = 170141183460469231731687303715884105727 }", "i128"),
+            Some((vec![(i128::MIN + 1, i128::MAX)], Some(i128::MIN + 1)))
+        );
+    }
+
+    #[test]
+    fn versioned_codec() {
+        let name = syn::parse_str::<syn::Ident>("MyEnum").unwrap();
+        let vis = syn::parse_str::<syn::Visibility>("pub").unwrap();
+        let versioned_codec = |int_type_str: &str, schema_hash: u64| {
+            let int_type = syn::parse_str::<proc_macro2::TokenStream>(int_type_str).unwrap();
+            super::make_versioned_codec(&vis, &name, int_type_str, &int_type, schema_hash, true)
+                .map(|(fns, _)| fns.to_string())
+        };
+        assert!(versioned_codec("u16", 1).is_ok());
+        // the width of the discriminant would depend on the platform
+        assert!(versioned_codec("isize", 1).is_err());
+        assert!(versioned_codec("usize", 1).is_err());
+        // hashes that only differ in one byte get different versions
+        assert_ne!(
+            versioned_codec("u8", 0x0100).unwrap(),
+            versioned_codec("u8", 0x0001).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "avro")]
+    fn avro_symbols() {
+        use super::SerdeNames::{Ints, Names};
+        let name = syn::parse_str::<syn::Ident>("MyEnum").unwrap();
+        let names = |names: &[&str]| Names(names.iter().map(|x| x.to_string()).collect());
+        assert!(super::make_avro_fns(&name, &names(&["in_qa", "_Done2"])).is_ok());
+        // Serde = "int"
+        assert!(super::make_avro_fns(&name, &Ints(Some(vec![1, 2]))).is_err());
+        // Serde = "kebab_case", "pascal_spaced" and "dot_case"
+        assert!(super::make_avro_fns(&name, &names(&["in-qa"])).is_err());
+        assert!(super::make_avro_fns(&name, &names(&["In QA"])).is_err());
+        assert!(super::make_avro_fns(&name, &names(&["in.qa"])).is_err());
+        assert!(super::make_avro_fns(&name, &names(&["2fa"])).is_err());
     }
 }
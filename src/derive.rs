@@ -0,0 +1,72 @@
+use super::core::{generate_expanded_enum, EnumDefArgs};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::Parse;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput};
+
+/// Splits the attributes of a derived enum into the `#[enum_ext(...)]` helper, which takes the same options as
+/// `#[enum_def(...)]`, and the rest.
+fn process_helper_attributes(attrs: &[Attribute]) -> syn::Result<(EnumDefArgs, Vec<Attribute>)> {
+    let mut not_mine = Vec::<Attribute>::new();
+    let mut my_args = None;
+    for attr in attrs {
+        if attr.path().is_ident("enum_ext") {
+            if my_args.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "only one #[enum_ext(...)] attribute is allowed",
+                ));
+            }
+            my_args = Some(attr.parse_args_with(EnumDefArgs::parse)?);
+        } else {
+            not_mine.push(attr.clone());
+        }
+    }
+    Ok((my_args.unwrap_or_default(), not_mine))
+}
+
+/// Generates the enum_ext helper impls for a `#[derive(EnumExt)]` enum. The enum is left as written, so this works the
+/// same as `enum_ext_impl!`.
+pub fn derive_enum_ext(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return syn::Error::new_spanned(&input.ident, "EnumExt can only be derived for enums")
+                .to_compile_error()
+                .into()
+        }
+    };
+    if input.generics.type_params().next().is_some()
+        || input.generics.const_params().next().is_some()
+    {
+        return syn::Error::new_spanned(
+            &input.generics,
+            "EnumExt can't be derived for generic enums, only lifetimes are supported",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let (my_args, derives_etc) = match process_helper_attributes(&input.attrs) {
+        Ok(result) => result,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    match generate_expanded_enum(
+        derives_etc,
+        input.vis,
+        input.ident,
+        &input.generics,
+        variants,
+        my_args,
+        false,
+    ) {
+        Ok(impls) => impls.into(),
+        Err(error) => {
+            let error_message = format!("{}", error);
+            TokenStream::from(quote! { compile_error!(#error_message); })
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::core::generate_expanded_enum;
+use super::proc::process_attributes;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream, Result as ParseResult};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{braced, parse_macro_input, Attribute, Generics, Path, Token, Variant, Visibility};
+
+/// The input of `enum_ext_impl!`: a copy of an enum definition, where the name can be a path.
+struct ImplInput {
+    attrs: Vec<Attribute>,
+    path: Path,
+    variants: Punctuated<Variant, Comma>,
+}
+
+impl Parse for ImplInput {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        // input is in the form of:
+        // #[enum_def(...)] - optional
+        // #[derive()] - optional, describes the derives of the existing enum
+        // pub enum path::to::MyEnum { - visibility and `enum` are optional, so a definition can be copied as is
+        //     A = 1,
+        //     B = 2,
+        // }
+        let attrs = input.call(Attribute::parse_outer)?;
+        let _: Visibility = input.parse()?;
+        let _: Option<Token![enum]> = input.parse()?;
+        let path = input.call(Path::parse_mod_style)?;
+        let content;
+        braced!(content in input);
+        let variants = content.parse_terminated(Variant::parse, Token![,])?;
+        Ok(ImplInput {
+            attrs,
+            path,
+            variants,
+        })
+    }
+}
+
+/// Generates the enum_ext helper impls for an enum that's defined elsewhere, without re-emitting the enum.
+pub fn enum_ext_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ImplInput);
+
+    let (my_args, derives_etc) = match process_attributes(&input.attrs) {
+        Ok(result) => result,
+        Err(error) => {
+            let error_message = format!("{}", error);
+            return TokenStream::from(quote! { compile_error!(#error_message); });
+        }
+    };
+
+    let name = match input.path.segments.last() {
+        Some(segment) => segment.ident.clone(),
+        None => {
+            return TokenStream::from(
+                quote! { compile_error!("enum_ext_impl! expects an enum name"); },
+            )
+        }
+    };
+
+    match generate_expanded_enum(
+        derives_etc,
+        Visibility::Inherited,
+        name,
+        &Generics::default(),
+        &input.variants,
+        my_args,
+        false,
+    ) {
+        Ok(impls) => {
+            if input.path.segments.len() > 1 {
+                // bring the enum into scope by its name, without leaking the import into the caller's module
+                let path = input.path;
+                quote! {
+                    const _: () = {
+                        use #path;
+                        #impls
+                    };
+                }
+                .into()
+            } else {
+                impls.into()
+            }
+        }
+        Err(error) => {
+            let error_message = format!("{}", error);
+            TokenStream::from(quote! { compile_error!(#error_message); })
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use super::core::{generate_expanded_enum, EnumMacroError};
+use super::proc::process_attributes;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream, Result as ParseResult};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{
+    parenthesized, parse_macro_input, Attribute, Generics, Ident, LitStr, Token, Variant,
+    Visibility,
+};
+
+/// The input of `enum_from_file!`: the enum header, with the data file in place of the variants.
+struct FromFileInput {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    file: LitStr,
+}
+
+impl Parse for FromFileInput {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        // input is in the form of:
+        // #[enum_def(...)] - optional
+        // #[derive()] - optional
+        // pub enum MyEnum("path/to/file.csv");
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let _: Token![enum] = input.parse()?;
+        let name = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let file = content.parse()?;
+        let _: Option<Token![;]> = input.parse()?;
+        Ok(FromFileInput {
+            attrs,
+            vis,
+            name,
+            file,
+        })
+    }
+}
+
+/// Splits a CSV line into its fields. Fields can be quoted, with `""` for a quote inside a quoted field.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|x| x.trim().to_string()).collect()
+}
+
+/// Builds the variants from CSV data with a `name,value,description` header.
+///
+/// The `value` and `description` columns are optional and can be in any order. Values become discriminants, and
+/// descriptions become doc comments.
+fn parse_csv(data: &str) -> Result<Punctuated<Variant, Comma>, EnumMacroError> {
+    let mut lines = data.lines().filter(|line| !line.trim().is_empty());
+    let header = split_csv_line(lines.next().unwrap_or_default());
+    let column = |name: &str| header.iter().position(|x| x.eq_ignore_ascii_case(name));
+    let name_column = column("name").ok_or_else(|| {
+        EnumMacroError::ParseError(
+            "the first row must be a header with a 'name' column".to_string(),
+        )
+    })?;
+    let value_column = column("value");
+    let description_column = column("description");
+
+    let mut variants = Punctuated::new();
+    for line in lines {
+        let fields = split_csv_line(line);
+        let field = |i: Option<usize>| {
+            i.and_then(|i| fields.get(i))
+                .map(String::as_str)
+                .filter(|x| !x.is_empty())
+        };
+        let name = field(Some(name_column))
+            .ok_or_else(|| EnumMacroError::ParseError(format!("row '{}' has no name", line)))?;
+        let ident = syn::parse_str::<Ident>(name).map_err(|_| {
+            EnumMacroError::ParseError(format!("'{}' is not a valid variant name", name))
+        })?;
+        let discriminant = match field(value_column) {
+            Some(value) => {
+                let expr = syn::parse_str::<syn::Expr>(value).map_err(|e| {
+                    EnumMacroError::ParseError(format!(
+                        "invalid value '{}' for '{}': {}",
+                        value, name, e
+                    ))
+                })?;
+                quote! { = #expr }
+            }
+            None => quote! {},
+        };
+        let doc = field(description_column).map(|description| {
+            let description = format!(" {}", description);
+            quote! { #[doc = #description] }
+        });
+        let variant = syn::parse2::<Variant>(quote! { #doc #ident #discriminant })
+            .map_err(|e| EnumMacroError::ParseError(format!("invalid row '{}': {}", line, e)))?;
+        variants.push(variant);
+    }
+    Ok(variants)
+}
+
+/// Generates an enum_ext enhanced enum from the rows of a data file.
+pub fn enum_from_file(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as FromFileInput);
+
+    match expand(input) {
+        Ok(expanded_enum) => expanded_enum.into(),
+        Err(error) => {
+            let error_message = format!("{}", error);
+            TokenStream::from(quote! { compile_error!(#error_message); })
+        }
+    }
+}
+
+fn expand(input: FromFileInput) -> Result<proc_macro2::TokenStream, EnumMacroError> {
+    let file = input.file.value();
+    if !file.to_ascii_lowercase().ends_with(".csv") {
+        return Err(EnumMacroError::ParseError(format!(
+            "unsupported data file '{}': only .csv files are supported",
+            file
+        )));
+    }
+    // relative paths are relative to the crate root, like include_str! from the crate root would be
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = std::path::Path::new(&manifest_dir).join(&file);
+    let data = std::fs::read_to_string(&path).map_err(|e| {
+        EnumMacroError::ParseError(format!("unable to read '{}': {}", path.display(), e))
+    })?;
+
+    let (my_args, derives_etc) = process_attributes(&input.attrs)?;
+    let variants = parse_csv(&data)?;
+    let expanded_enum = generate_expanded_enum(
+        derives_etc,
+        input.vis,
+        input.name,
+        &Generics::default(),
+        &variants,
+        my_args,
+        true,
+    )?;
+
+    // include the file so the enum is regenerated when it changes
+    let path = LitStr::new(&path.to_string_lossy(), Span::call_site());
+    Ok(quote! {
+        #expanded_enum
+        const _: &str = include_str!(#path);
+    })
+}
@@ -1,6 +1,11 @@
 #![doc=include_str!("../README.md")]
+#![allow(clippy::needless_doctest_main)]
 mod attr;
 mod core;
+mod derive;
+mod ext_impl;
+mod from_file;
+mod merge;
 mod proc;
 
 #[doc = include_str!("../PROCS.md")]
@@ -18,6 +23,197 @@ pub fn enum_extend(
     attr::enum_extend(attr, item)
 }
 
+/// Generates the same utility methods as `enum_ext!` for an enum that's defined elsewhere, such as generated code,
+/// without re-emitting the enum.
+///
+/// The input is a copy of the enum definition. The name can be a path, and `pub` and `enum` are optional. `enum_def`
+/// works the same as with `enum_ext!`, and the derives describe the ones the existing enum already has. Derives can't be
+/// added to an existing enum, so this always behaves as if `NoAutoDerives` is set.
+///
+/// Rust only allows inherent impls in the crate that defines the enum, so the enum must be defined in the same crate.
+///
+/// ```rust
+/// mod generated {
+///     #[derive(Debug, Clone, PartialEq)]
+///     pub enum Status {
+///         Open = 1,
+///         InQA = 3,
+///     }
+/// }
+///
+/// enum_ext::enum_ext_impl! {
+///     #[enum_def(IntType = "i32")]
+///     #[derive(Debug, Clone, PartialEq)]
+///     generated::Status {
+///         Open = 1,
+///         InQA = 3,
+///     }
+/// }
+///
+/// use generated::Status;
+/// assert_eq!(Status::InQA.pascal_spaced(), "In QA");
+/// assert_eq!(Status::from_i32(1), Some(Status::Open));
+/// ```
+#[proc_macro]
+pub fn enum_ext_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    ext_impl::enum_ext_impl(input)
+}
+
+/// Derives the same utility methods as `enum_ext!` for an enum, leaving the enum as written.
+///
+/// The enum is configured with an `#[enum_ext(...)]` helper attribute, which takes the same options as `enum_def`, and
+/// the variants with `#[ext(...)]`, just like with `#[enum_extend]`. A derive can't change the enum it's attached to, so
+/// this always behaves as if `NoAutoDerives` is set.
+///
+/// ```rust
+/// use enum_ext::EnumExt;
+///
+/// #[derive(EnumExt, Debug, Clone, Copy, PartialEq)]
+/// #[enum_ext(IntType = "u8")]
+/// #[repr(u8)]
+/// pub enum Status {
+///     Open = 1,
+///     #[ext(alias = "qa")]
+///     InQA = 3,
+/// }
+///
+/// assert_eq!(Status::InQA.pascal_spaced(), "In QA");
+/// assert_eq!(Status::from_u8(1), Some(Status::Open));
+/// assert_eq!(Status::parse("qa"), Some(Status::InQA));
+/// ```
+#[proc_macro_derive(EnumExt, attributes(enum_ext, ext))]
+pub fn derive_enum_ext(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive::derive_enum_ext(input)
+}
+
+/// Generates an enum with all the `enum_ext!` utility methods from the rows of a CSV file, read at compile time.
+///
+/// The file path is relative to the crate root (the directory with `Cargo.toml`). The first row is a header with a
+/// `name` column, and optionally `value` and `description` columns, in any order. Values become the discriminants,
+/// and descriptions become the doc comments of the variants. Fields can be quoted, e.g. `"Not found, or hidden"`.
+///
+/// ```csv
+/// name,value,description
+/// Ok,200,The request succeeded
+/// NotFound,404,The resource wasn't found
+/// ```
+///
+/// ```rust,ignore
+/// enum_ext::enum_from_file! {
+///     #[enum_def(IntType = "u16")]
+///     #[derive(Debug, Clone, PartialEq)]
+///     pub enum HttpStatus("data/status_codes.csv");
+/// }
+///
+/// assert_eq!(HttpStatus::from_u16(404), Some(HttpStatus::NotFound));
+/// ```
+#[proc_macro]
+pub fn enum_from_file(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    from_file::enum_from_file(input)
+}
+
+/// Generates an enum with the variants of two or more enums, with all the `enum_ext!` utility methods and a `From`
+/// impl from each of the source enums.
+///
+/// A macro can't see the variants of an enum defined elsewhere, so each source enum is followed by a copy of its
+/// variant names. The `From` impls match every listed variant without a wildcard, so a list that's out of date with
+/// its enum fails to compile. Variant names must be unique across all the source enums.
+///
+/// ```rust
+/// use enum_ext::{enum_ext_merge, enum_extend};
+///
+/// #[enum_extend]
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub enum OrderStatus {
+///     Open,
+///     Shipped,
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub enum PaymentStatus {
+///     Pending,
+///     Paid,
+/// }
+///
+/// enum_ext_merge! {
+///     #[derive(Debug, Clone, PartialEq)]
+///     pub enum Status = OrderStatus { Open, Shipped } + PaymentStatus { Pending, Paid };
+/// }
+///
+/// assert_eq!(Status::count(), 4);
+/// assert_eq!(Status::from(PaymentStatus::Paid), Status::Paid);
+/// ```
+#[proc_macro]
+pub fn enum_ext_merge(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    merge::enum_ext_merge(input)
+}
+
+/// Declares the registry of enum_ext enums, `EnumExtInfo` and `EnumExtValue`, with the `registry` feature. Invoke it
+/// once at the crate root.
+///
+/// Every enum of the crate with `enum_def(Registry)` registers an `EnumExtInfo` with its name, `pretty_print()` and its
+/// variants with their discriminants, which `EnumExtInfo::iter()` returns. For example, to log every enum at
+/// startup so mismatched definitions between services can be spotted. The using crate needs the `inventory` crate as
+/// a dependency.
+///
+/// ```rust,ignore
+/// enum_ext::enum_ext_registry!();
+///
+/// fn main() {
+///     for info in EnumExtInfo::iter() {
+///         println!("{}", info.pretty_print);
+///     }
+/// }
+/// ```
+#[cfg(feature = "registry")]
+#[proc_macro]
+pub fn enum_ext_registry(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    if !input.is_empty() {
+        return quote::quote! { compile_error!("enum_ext_registry! doesn't take any arguments"); }
+            .into();
+    }
+    quote::quote! {
+        /// An enum generated by enum_ext, registered for startup logging and export
+        #[derive(Debug)]
+        pub struct EnumExtInfo {
+            /// The name of the enum
+            pub name: &'static str,
+            /// The `pretty_print()` of the enum
+            pub pretty_print: &'static str,
+            /// The name and discriminant of each variant, in ordinal order
+            pub variants: &'static [(&'static str, EnumExtValue)],
+        }
+
+        /// The discriminant of a registered variant, unsigned if the `IntType` of its enum is
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum EnumExtValue {
+            /// The discriminant of an enum with a signed `IntType`
+            Signed(i128),
+            /// The discriminant of an enum with an unsigned `IntType`, or without one
+            Unsigned(u128),
+        }
+
+        impl ::core::fmt::Display for EnumExtValue {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    EnumExtValue::Signed(value) => ::core::fmt::Display::fmt(value, f),
+                    EnumExtValue::Unsigned(value) => ::core::fmt::Display::fmt(value, f),
+                }
+            }
+        }
+
+        ::inventory::collect!(EnumExtInfo);
+
+        impl EnumExtInfo {
+            /// Returns an iterator over every registered enum, in no particular order
+            pub fn iter() -> impl Iterator<Item = &'static EnumExtInfo> {
+                ::inventory::iter::<EnumExtInfo>.into_iter()
+            }
+        }
+    }
+    .into()
+}
+
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
 struct ReadmeDocTests;
@@ -0,0 +1,123 @@
+use super::core::{generate_expanded_enum, EnumMacroError};
+use super::proc::process_attributes;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream, Result as ParseResult};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{
+    braced, parse_macro_input, Attribute, Generics, Ident, Path, Token, Variant, Visibility,
+};
+
+/// One of the enums being merged: its path and a copy of its variant names.
+struct MergeSource {
+    path: Path,
+    variants: Punctuated<Ident, Comma>,
+}
+
+/// The input of `enum_ext_merge!`.
+struct MergeInput {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    sources: Vec<MergeSource>,
+}
+
+impl Parse for MergeInput {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        // input is in the form of:
+        // #[enum_def(...)] - optional
+        // #[derive()] - optional
+        // pub enum Combined = StatusA { A, B } + StatusB { C };
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let _: Token![enum] = input.parse()?;
+        let name = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        let mut sources = Vec::new();
+        loop {
+            let path = input.call(Path::parse_mod_style)?;
+            let content;
+            braced!(content in input);
+            let variants = content.parse_terminated(Ident::parse, Token![,])?;
+            sources.push(MergeSource { path, variants });
+            if input.parse::<Option<Token![+]>>()?.is_none() {
+                break;
+            }
+        }
+        let _: Option<Token![;]> = input.parse()?;
+        Ok(MergeInput {
+            attrs,
+            vis,
+            name,
+            sources,
+        })
+    }
+}
+
+/// Generates an enum with the variants of several enums, and `From` impls from each of them.
+pub fn enum_ext_merge(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as MergeInput);
+
+    match expand(input) {
+        Ok(expanded_enum) => expanded_enum.into(),
+        Err(error) => {
+            let error_message = format!("{}", error);
+            TokenStream::from(quote! { compile_error!(#error_message); })
+        }
+    }
+}
+
+fn expand(input: MergeInput) -> Result<TokenStream2, EnumMacroError> {
+    let name = input.name;
+    let mut variants = Punctuated::<Variant, Comma>::new();
+    let mut from_impls = TokenStream2::new();
+    for source in &input.sources {
+        let source_path = &source.path;
+        let mut arms = TokenStream2::new();
+        for ident in &source.variants {
+            if variants.iter().any(|x| x.ident == *ident) {
+                return Err(EnumMacroError::VariantError(format!(
+                    "can't merge '{}' into {}: a variant named '{}' is already in {}",
+                    source_path.to_token_stream(),
+                    name,
+                    ident,
+                    name
+                )));
+            }
+            variants.push(syn::parse_quote! { #ident });
+            // no wildcard arm, so an out of date variant list doesn't compile
+            arms.extend(quote! {
+                #source_path::#ident => #name::#ident,
+            });
+        }
+        from_impls.extend(quote! {
+            impl From<#source_path> for #name {
+                /// Returns the variant with the same name
+                #[inline]
+                fn from(val: #source_path) -> Self {
+                    match val {
+                        #arms
+                    }
+                }
+            }
+        });
+    }
+
+    let (my_args, derives_etc) = process_attributes(&input.attrs)?;
+    let expanded_enum = generate_expanded_enum(
+        derives_etc,
+        input.vis,
+        name,
+        &Generics::default(),
+        &variants,
+        my_args,
+        true,
+    )?;
+
+    Ok(quote! {
+        #expanded_enum
+        #from_impls
+    })
+}
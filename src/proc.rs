@@ -1,12 +1,45 @@
-use super::core::{
-    append_int_fns, check_derive_traits, make_pretty_print, parse_variants, valid_int_type,
-    EnumDefArgs, EnumMacroError,
-};
+use super::core::{generate_expanded_enum, EnumDefArgs, EnumMacroError};
 use proc_macro::TokenStream;
-use proc_macro2::{Span, TokenStream as TokenStream2};
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::parse::Parse;
-use syn::{parse_macro_input, Attribute, DeriveInput, Ident};
+use syn::parse::{Parse, ParseStream, Result as ParseResult};
+use syn::{parenthesized, parse_macro_input, Attribute, DeriveInput, Ident, Token};
+
+/// The input of `enum_ext!`: an optional `config!(...);` block followed by one or more enums.
+struct EnumExtInput {
+    config: Option<EnumDefArgs>,
+    enums: Vec<DeriveInput>,
+}
+
+impl Parse for EnumExtInput {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        // input is in the form of:
+        // config!(IntType = "u8"); - optional, the enum_def defaults for every enum below
+        // #[derive()] - optional
+        // pub enum MyEnum { ... }
+        // pub enum MyOtherEnum { ... } - optional
+        let mut config = None;
+        if input.peek(Ident) && input.peek2(Token![!]) {
+            let ident: Ident = input.parse()?;
+            if ident != "config" {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("expected config!(...) or an enum, found {}!", ident),
+                ));
+            }
+            let _: Token![!] = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            config = Some(content.parse::<EnumDefArgs>()?);
+            let _: Token![;] = input.parse()?;
+        }
+        let mut enums = vec![input.parse::<DeriveInput>()?];
+        while !input.is_empty() {
+            enums.push(input.parse()?);
+        }
+        Ok(EnumExtInput { config, enums })
+    }
+}
 
 /// Processes the attributes of an enum variant.
 ///
@@ -39,7 +72,7 @@ use syn::{parse_macro_input, Attribute, DeriveInput, Ident};
 /// };
 ///
 /// ```
-fn process_attributes(
+pub(crate) fn process_attributes(
     attrs: &[Attribute],
 ) -> Result<(EnumDefArgs, Vec<Attribute>), EnumMacroError> {
     // Logic to process attributes
@@ -87,6 +120,7 @@ fn process_attributes(
 /// - `#[enum_def(IntType = "i32")]`: Specifies the integer type for conversion methods.
 ///   The generated methods allow conversion from the specified integer type to an enum variant
 ///   and vice versa. Supported types include standard Rust integer types like `i32`, `u32`, `i64`, etc.
+///   `"auto"` picks the smallest type that fits all discriminants, preferring unsigned types.
 ///
 /// - **Note:** If the integer type is not specified in the `enum_def` attribute, usize is used as the default.
 /// - **Note:** If the enum has discriminant values, `#[derive(Clone)]` is added to the enum (if not already present).
@@ -177,223 +211,49 @@ pub fn enum_ext(input: TokenStream) -> TokenStream {
     //     C = 3,
     // }
 
-    let input = parse_macro_input!(input as DeriveInput);
+    let EnumExtInput { config, enums } = parse_macro_input!(input as EnumExtInput);
 
+    let mut expanded = TokenStream2::new();
+    for input in enums {
+        expanded.extend(expand_enum(input, config.clone()));
+    }
+    expanded.into()
+}
+
+/// Expands one enum of an `enum_ext!` invocation, using `config` for the options its `enum_def` doesn't set.
+fn expand_enum(input: DeriveInput, config: Option<EnumDefArgs>) -> TokenStream2 {
     // first make sure it's an enum
     let variants = match input.data {
         syn::Data::Enum(e) => e.variants,
-        _ => return TokenStream::from(quote! { compile_error!("enum_ext only works on enums"); }),
+        _ => return quote! { compile_error!("enum_ext only works on enums"); },
     };
 
-    // placeholders
-    let mut int_type = quote! { usize };
-    let mut int_type_str = "usize".to_string();
-    let mut _other_type_str = "".to_string();
-
     // parse the attributes. EnumDefArgs will contain stuff we're interested in. everything else (like derive etc) will be in derives_etc.
     let (my_args, derives_etc) = match process_attributes(&input.attrs) {
         Ok(result) => result,
         Err(error) => {
             let error_message = format!("{}", error);
-            return TokenStream::from(quote! { compile_error!(#error_message); });
+            return quote! { compile_error!(#error_message); };
         }
     };
+    let my_args = match config {
+        Some(config) => my_args.or(config),
+        None => my_args,
+    };
 
-    if let Some(lit_str) = my_args.int_type {
-        int_type_str = lit_str.value();
-        if !valid_int_type(&int_type_str) {
-            let error_message = format!("Invalid IntType: {}", int_type_str);
-            return TokenStream::from(quote! { compile_error!(#error_message); });
-        }
-
-        int_type = match lit_str.parse() {
-            Ok(result) => result,
-            Err(error) => {
-                let error_message = format!("Invalid IntType: {}", error);
-                return TokenStream::from(quote! { compile_error!(#error_message); });
-            }
-        };
-    }
-
-    if let Some(lit_str) = my_args.other_type {
-        _other_type_str = lit_str.value();
-    }
-
-    let derive_summary = check_derive_traits(&derives_etc);
-
-    let vis = input.vis;
-    let name = input.ident;
-
-    // Prepare the enum body with variants
-    let (
-        enum_body,
-        variant_list,
-        variant_ordinals,
-        variant_map,
-        to_pascal_split,
-        from_pascal_split,
-        variant_count,
-        variant_from_ordinals,
-    ) = match parse_variants(&name, &variants, &int_type) {
-        Ok(result) => result,
+    match generate_expanded_enum(
+        derives_etc,
+        input.vis,
+        input.ident,
+        &input.generics,
+        &variants,
+        my_args,
+        true,
+    ) {
+        Ok(expanded_enum) => expanded_enum,
         Err(error) => {
             let error_message = format!("{}", error);
-            return TokenStream::from(quote! { compile_error!(#error_message); });
-        }
-    };
-
-    let mut enum_fns = quote! {
-        /// Returns an array of all variants in the enum
-        #[inline]
-        pub const fn list() -> [#name; #variant_count] {
-            [#variant_list]
-        }
-        /// Returns the number of variants in the enum
-        #[inline]
-        pub const fn count() -> usize {
-            #variant_count
-        }
-        /// Returns the ordinal of the variant
-        #[inline]
-        pub const fn ordinal(&self) -> usize {
-            match self {
-                #variant_ordinals
-            }
-        }
-        /// Returns true if the ordinal is valid for the enum
-        #[inline]
-        pub const fn valid_ordinal(ordinal : usize) -> bool {
-            ordinal < #variant_count
-        }
-        /// Returns &Self from the ordinal.
-        pub const fn ref_from_ordinal(ord: usize) -> Option<&'static Self> {
-            const list : [#name; #variant_count] = #name::list();
-            if ord >= #variant_count {
-                return None;
-            }
-            Some(&list[ord])
-        }
-        /// Returns an iterator over the variants in the enum
-        pub fn iter() -> impl Iterator<Item = &'static #name> {
-            const list : [#name; #variant_count] = #name::list();
-            list.iter()
-        }
-
-        /// Returns the variant name in spaced PascalCase
-        /// * For example, MyEnum::InQA.pascal_spaced() returns "In QA"
-        pub const fn pascal_spaced(&self) -> &'static str {
-            match self {
-                #to_pascal_split
-            }
-        }
-
-        /// Returns the variant from the spaced PascalCase name
-        /// * For example, MyEnum::from_pascal_spaced("In QA") returns Some(MyEnum::InQA)
-        pub fn from_pascal_spaced(s: &str) -> Option<Self> {
-            match s {
-                #from_pascal_split
-                _ => None,
-            }
-        }
-    };
-
-    let mut needed_derives = TokenStream2::new();
-
-    let int_type_added =
-        append_int_fns(&mut enum_fns, &name, variant_map, &int_type_str, &int_type);
-
-    let mut clone_added = false;
-    if int_type_added {
-        if !derive_summary.has_derive {
-            clone_added = true;
-            needed_derives.extend(quote! {
-                #[derive(Clone)]
-            });
-        } else {
-            if !derive_summary.has_clone {
-                clone_added = true;
-                needed_derives.extend(quote! {
-                    #[derive(Clone)]
-                });
-            }
+            quote! { compile_error!(#error_message); }
         }
     }
-
-    let mut repl_value = TokenStream2::new();
-    //dbg!(int_type_added);
-    if int_type_added {
-        repl_value.extend(quote! {
-            #[repr(#int_type)]
-        });
-        //dbg!(&repl_value);
-    }
-
-    if derive_summary.has_clone || clone_added {
-        // fn's that require Clone
-        enum_fns.extend(quote! {
-           /// Returns Self from the ordinal.
-        pub const fn from_ordinal(ord: usize) -> Option<Self> {
-            match ord {
-                #variant_from_ordinals
-                _ => None,
-            }
-        }
-        });
-    }
-
-    let attrs2 = derives_etc.clone();
-    let needed_derives2 = needed_derives.clone();
-    let repl_value2 = repl_value.clone();
-    let vis2 = vis.clone();
-    let name2 = name.clone();
-    let enum_body2 = enum_body.clone();
-    let pretty_print_body = make_pretty_print(
-        attrs2,
-        needed_derives2,
-        vis2,
-        name2,
-        enum_body2,
-        repl_value2,
-    );
-
-    let mut expanded_enum = quote! {
-        #(#derives_etc)*
-        #needed_derives
-        #repl_value
-        #vis enum #name {
-            #enum_body
-        }
-
-        impl #name {
-            #enum_fns
-
-            /// Returns a pretty printed string of the enum definition
-            pub const fn pretty_print() -> &'static str {
-                #pretty_print_body
-            }
-        }
-
-    };
-
-    if int_type_added {
-        let from_fn_name_str = format!("from_{}", int_type_str);
-        let from_fn_name = Ident::new(&from_fn_name_str, Span::call_site());
-        let impl_from = quote! {
-            impl From<#int_type> for #name {
-                /// Returns the enum variant from the integer value.
-                /// <br><br>
-                /// This will panic if the integer value is not a valid discriminant. Use the #from_fn_name or `try_from` functions
-                /// instead if you want to handle invalid values.
-                #[inline]
-                fn from(val: #int_type) -> Self {
-                    Self::#from_fn_name(val).unwrap()
-                }
-            }
-        };
-
-        expanded_enum.extend(impl_from);
-    }
-
-    // Convert to TokenStream and return
-    expanded_enum.into()
 }
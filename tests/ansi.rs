@@ -0,0 +1,37 @@
+//! Run with `cargo test --features ansi`.
+#![cfg(feature = "ansi")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn ansi_colored_name() {
+    #[enum_extend(Ansi)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        #[ext(color = "green")]
+        Open,
+        #[ext(color = "yellow")]
+        InQA,
+        Done,
+    }
+
+    assert_eq!(Status::Open.ansi_colored_name(), "\x1b[32mOpen\x1b[0m");
+    assert_eq!(Status::InQA.ansi_colored_name(), "\x1b[33mInQA\x1b[0m");
+    // no color, no escape codes
+    assert_eq!(Status::Done.ansi_colored_name(), "Done");
+}
+
+#[test]
+fn ansi_colored_name_proc() {
+    enum_ext! {
+        #[enum_def(Ansi)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Level {
+            #[ext(color = "red")]
+            Error,
+            Info,
+        }
+    }
+
+    assert_eq!(Level::Error.ansi_colored_name(), "\x1b[31mError\x1b[0m");
+}
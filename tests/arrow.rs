@@ -0,0 +1,54 @@
+//! Run with `cargo test --features arrow`.
+#![cfg(feature = "arrow")]
+#![allow(unused, dead_code)]
+use arrow::array::{Array, DictionaryArray, Int32Array, StringArray, UInt16Array};
+use arrow::datatypes::Int32Type;
+use enum_ext::{enum_ext, enum_extend};
+use std::sync::Arc;
+
+#[test]
+fn arrow_dictionary_round_trip() {
+    #[enum_extend(Arrow)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+        Done,
+    }
+
+    let column = Status::to_arrow_dictionary(&[Status::Done, Status::Open, Status::Done]);
+    assert_eq!(column.len(), 3);
+    assert_eq!(column.keys().values(), &[2, 0, 2]);
+    assert_eq!(column.values().len(), Status::count());
+    let decoded = Status::from_arrow_dictionary(&column).collect::<Vec<_>>();
+    assert_eq!(
+        decoded,
+        [Some(Status::Done), Some(Status::Open), Some(Status::Done)]
+    );
+
+    // a dictionary written elsewhere, with a null and an unknown name
+    let keys = Int32Array::from(vec![Some(1), None, Some(0)]);
+    let values = StringArray::from(vec!["Closed", "InQA"]);
+    let column = DictionaryArray::<Int32Type>::new(keys, Arc::new(values));
+    let decoded = Status::from_arrow_dictionary(&column).collect::<Vec<_>>();
+    assert_eq!(decoded, [Some(Status::InQA), None, None]);
+}
+
+#[test]
+fn arrow_codes() {
+    enum_ext! {
+        #[enum_def(IntType = "u16", Arrow)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum HttpStatus {
+            Ok = 200,
+            NotFound = 404,
+        }
+    }
+
+    let codes = UInt16Array::from(vec![Some(404), None, Some(500), Some(200)]);
+    let decoded = HttpStatus::from_arrow_codes(&codes).collect::<Vec<_>>();
+    assert_eq!(
+        decoded,
+        [Some(HttpStatus::NotFound), None, None, Some(HttpStatus::Ok)]
+    );
+}
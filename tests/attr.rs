@@ -19,10 +19,8 @@ fn simple_1() {
     assert_eq!(Simple::B.ordinal(), 1);
     assert_eq!(Simple::C.ordinal(), 2);
 
-    let mut ord = 0;
-    for x in Simple::list() {
+    for (ord, x) in Simple::list().iter().enumerate() {
         assert_eq!(x.ordinal(), ord);
-        ord += 1;
     }
 
     for (i, v) in Simple::iter().enumerate() {
@@ -91,10 +89,8 @@ fn discriminant_1() {
     assert_eq!(Variant::B.ordinal(), 1);
     assert_eq!(Variant::C.ordinal(), 2);
 
-    let mut ord = 0;
-    for x in Variant::list() {
+    for (ord, x) in Variant::list().iter().enumerate() {
         assert_eq!(x.ordinal(), ord);
-        ord += 1;
     }
 
     for (i, v) in Variant::iter().enumerate() {
@@ -146,10 +142,8 @@ fn discriminant_2() {
     assert_eq!(Variant::B.ordinal(), 1);
     assert_eq!(Variant::C.ordinal(), 2);
 
-    let mut ord = 0;
-    for x in Variant::list() {
+    for (ord, x) in Variant::list().iter().enumerate() {
         assert_eq!(x.ordinal(), ord);
-        ord += 1;
     }
 
     for (i, v) in Variant::iter().enumerate() {
@@ -200,10 +194,8 @@ fn discriminant_neg1() {
     assert_eq!(Variant::B.ordinal(), 1);
     assert_eq!(Variant::C.ordinal(), 2);
 
-    let mut ord = 0;
-    for x in Variant::list() {
+    for (ord, x) in Variant::list().iter().enumerate() {
         assert_eq!(x.ordinal(), ord);
-        ord += 1;
     }
 
     for (i, v) in Variant::iter().enumerate() {
@@ -396,3 +388,1741 @@ pub enum PrettyPrint {
 }"##
     );
 }
+
+#[test]
+fn discriminant_expressions() {
+    #[enum_extend(IntType = "i32")]
+    #[derive(Debug, PartialEq)]
+    pub enum Expressions {
+        A = 1 << 1,
+        B = 261u16 as u8 as i32,
+        C = 0x10 + 1,
+        D = -(2 * 3),
+    }
+
+    assert_eq!(Expressions::from_i32(2), Some(Expressions::A));
+    assert_eq!(Expressions::from_i32(5), Some(Expressions::B));
+    assert_eq!(Expressions::from_i32(17), Some(Expressions::C));
+    assert_eq!(Expressions::from_i32(-6), Some(Expressions::D));
+    assert_eq!(Expressions::from_i32(0), None);
+}
+
+#[test]
+fn int_type_auto() {
+    #[enum_extend(IntType = "auto")]
+    #[derive(Debug, PartialEq)]
+    pub enum Unsigned {
+        A = 1,
+        B = 200,
+    }
+
+    #[enum_extend(IntType = "auto")]
+    #[derive(Debug, PartialEq)]
+    pub enum Signed {
+        A = -1,
+        B = 200,
+    }
+
+    #[enum_extend(IntType = "auto")]
+    #[derive(Debug, PartialEq)]
+    pub enum Wide {
+        A = 1,
+        B = 70_000,
+    }
+
+    assert_eq!(std::mem::size_of::<Unsigned>(), 1);
+    assert_eq!(Unsigned::B.as_u8(), 200);
+    assert_eq!(Unsigned::from_u8(1), Some(Unsigned::A));
+
+    assert_eq!(std::mem::size_of::<Signed>(), 2);
+    assert_eq!(Signed::A.as_i16(), -1);
+    assert_eq!(Signed::from_i16(200), Some(Signed::B));
+
+    assert_eq!(std::mem::size_of::<Wide>(), 4);
+    assert_eq!(Wide::from_u32(70_000), Some(Wide::B));
+}
+
+#[test]
+fn multiple_int_types() {
+    #[enum_extend(IntType = "u8, u32, i64")]
+    #[derive(Debug, PartialEq)]
+    pub enum Multi {
+        A = 1,
+        B = 2,
+        C = 200,
+    }
+
+    assert_eq!(std::mem::size_of::<Multi>(), 1);
+    for x in Multi::list() {
+        assert_eq!(Multi::from_u8(x.as_u8()), Some(x.clone()));
+        assert_eq!(Multi::from_u32(x.as_u32()), Some(x.clone()));
+        assert_eq!(Multi::from_i64(x.as_i64()), Some(x.clone()));
+    }
+    assert_eq!(Multi::C.as_i64(), 200i64);
+    assert_eq!(Multi::from(2u32), Multi::B);
+    assert_eq!(Multi::from(200i64), Multi::C);
+    assert_eq!(Multi::from_i64(3), None);
+}
+
+#[test]
+fn try_from_int_widths() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Widths {
+        A = 1,
+        B = 2,
+        C = 255,
+    }
+
+    assert_eq!(Widths::try_from(1u16), Ok(Widths::A));
+    assert_eq!(Widths::try_from(2i64), Ok(Widths::B));
+    assert_eq!(Widths::try_from(255usize), Ok(Widths::C));
+    assert_eq!(Widths::try_from(3u32), Err(3));
+    // 257 would truncate to 1 (A) with a cast
+    assert_eq!(Widths::try_from(257u32), Err(257));
+    assert_eq!(Widths::try_from(-1i8), Err(-1));
+}
+
+#[test]
+fn range_between_variants() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open,
+        InDev,
+        InQA,
+        Done,
+    }
+
+    assert_eq!(
+        Status::range_inclusive(&Status::InDev, &Status::Done),
+        &[Status::InDev, Status::InQA, Status::Done]
+    );
+    assert_eq!(
+        Status::range_inclusive(&Status::InQA, &Status::InQA),
+        &[Status::InQA]
+    );
+    assert!(Status::range_inclusive(&Status::Done, &Status::Open).is_empty());
+
+    let between: Vec<&Status> = Status::iter_between(&Status::Open, &Status::InDev).collect();
+    assert_eq!(between, [&Status::Open, &Status::InDev]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_string_and_cow() {
+    use std::borrow::Cow;
+
+    #[enum_extend(IntoString)]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+    }
+
+    fn takes_into_string(s: impl Into<String>) -> String {
+        s.into()
+    }
+
+    assert_eq!(Status::InQA.variant_name(), "InQA");
+    assert_eq!(String::from(Status::Open), "Open");
+    assert_eq!(String::from(&Status::InQA), "InQA");
+    assert_eq!(takes_into_string(Status::InQA), "InQA");
+
+    let cow: Cow<'static, str> = Status::InQA.into();
+    assert!(matches!(cow, Cow::Borrowed("InQA")));
+
+    // without IntoString, the conversion is left to the enum
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Level {
+        Low,
+        High,
+    }
+
+    impl From<Level> for String {
+        fn from(level: Level) -> Self {
+            level.snake_case().to_uppercase()
+        }
+    }
+
+    assert_eq!(String::from(Level::High), "HIGH");
+}
+
+#[test]
+fn compare_by_discriminant() {
+    use std::cmp::Ordering;
+
+    #[enum_extend(IntType = "i16")]
+    #[derive(Debug, PartialEq)]
+    pub enum Codes {
+        Ok = 200,
+        Moved = 301,
+        Info = 100,
+        Error = -1,
+    }
+
+    assert_eq!(Codes::Ok.cmp_discriminant(&Codes::Moved), Ordering::Less);
+    assert_eq!(Codes::Ok.cmp_discriminant(&Codes::Info), Ordering::Greater);
+    assert_eq!(Codes::Ok.cmp_discriminant(&Codes::Ok), Ordering::Equal);
+    assert_eq!(Codes::max_by_value(), &Codes::Moved);
+    assert_eq!(Codes::min_by_value(), &Codes::Error);
+
+    let mut sorted = Codes::list();
+    sorted.sort_by(Codes::cmp_discriminant);
+    assert_eq!(sorted, [Codes::Error, Codes::Info, Codes::Ok, Codes::Moved]);
+}
+
+#[test]
+fn entries_table() {
+    #[enum_extend(IntType = "i32")]
+    #[derive(Debug, PartialEq)]
+    pub enum Codes {
+        A = 10,
+        B = -20,
+    }
+
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Plain {
+        X,
+        Y,
+    }
+
+    assert_eq!(Codes::ENTRIES, [("A", 0, 10), ("B", 1, -20)]);
+    assert_eq!(Plain::ENTRIES, [("X", 0, 0), ("Y", 1, 1)]);
+
+    const FIRST: (&str, usize, i32) = Codes::ENTRIES[0];
+    assert_eq!(FIRST.0, "A");
+}
+
+#[test]
+fn variant_attributes_preserved() {
+    #[enum_extend]
+    #[derive(Debug, Default, PartialEq)]
+    pub enum Status {
+        /// Documented variant
+        #[default]
+        #[ext()]
+        Open,
+        #[ext()]
+        #[allow(non_camel_case_types)]
+        in_qa,
+    }
+
+    assert_eq!(Status::default(), Status::Open);
+    assert_eq!(Status::in_qa.variant_name(), "in_qa");
+    assert!(Status::pretty_print().contains("#[default]"));
+    assert!(!Status::pretty_print().contains("ext"));
+}
+
+#[test]
+fn implied_discriminants() {
+    #[enum_extend(IntType = "i32")]
+    #[derive(Debug, PartialEq)]
+    pub enum Mixed {
+        A = 10,
+        B,
+        C,
+        D = -5,
+        E,
+    }
+
+    assert_eq!(Mixed::from_i32(10), Some(Mixed::A));
+    assert_eq!(Mixed::from_i32(11), Some(Mixed::B));
+    assert_eq!(Mixed::from_i32(12), Some(Mixed::C));
+    assert_eq!(Mixed::from_i32(-5), Some(Mixed::D));
+    assert_eq!(Mixed::from_i32(-4), Some(Mixed::E));
+    for x in Mixed::list() {
+        assert_eq!(Mixed::from_i32(x.as_i32()), Some(x.clone()));
+    }
+}
+
+#[test]
+fn cfg_attr_derives() {
+    // Clone is derived behind cfg_attr, so the macro must not add a second, unconditional Clone
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    #[cfg_attr(test, derive(Clone))]
+    pub enum Enabled {
+        A = 1,
+        B = 2,
+    }
+
+    // the cfg_attr never applies, so the macro adds Clone itself
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    #[cfg_attr(any(), derive(Clone))]
+    pub enum Disabled {
+        A = 1,
+        B = 2,
+    }
+
+    // no discriminants: from_ordinal is available whenever Clone is
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    #[cfg_attr(all(test, not(any())), derive(Clone))]
+    pub enum Plain {
+        A,
+        B,
+    }
+
+    assert_eq!(Enabled::from_ordinal(1), Some(Enabled::B));
+    assert_eq!(Enabled::B.clone().as_u8(), 2);
+    assert_eq!(Disabled::from_ordinal(0), Some(Disabled::A));
+    assert_eq!(Plain::from_ordinal(1), Some(Plain::B));
+}
+
+#[test]
+fn char_codes() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Side {
+        #[ext(code = 'B')]
+        Buy,
+        #[ext(code = 'S')]
+        Sell,
+        /// Short sell
+        #[ext(code = 'X')]
+        SellShort,
+    }
+
+    assert_eq!(Side::Buy.code(), 'B');
+    assert_eq!(Side::SellShort.code(), 'X');
+    assert_eq!(Side::from_code('S'), Some(Side::Sell));
+    assert_eq!(Side::from_code('Z'), None);
+    for x in Side::iter() {
+        assert_eq!(Side::from_code(x.code()).as_ref(), Some(x));
+    }
+}
+
+#[test]
+fn snake_and_kebab_case() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        InDev,
+        InQA,
+        FinalCodeReview,
+    }
+
+    assert_eq!(Status::InQA.snake_case(), "in_qa");
+    assert_eq!(Status::FinalCodeReview.kebab_case(), "final-code-review");
+    assert_eq!(Status::from_snake_case("in_dev"), Some(Status::InDev));
+    assert_eq!(Status::from_kebab_case("in-qa"), Some(Status::InQA));
+    assert_eq!(Status::from_snake_case("in-qa"), None);
+}
+
+#[test]
+fn parse_names_then_numbers() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        InDev = 1,
+        InQA = 3,
+        Done = 7,
+    }
+
+    assert_eq!(Status::parse("InQA"), Some(Status::InQA));
+    assert_eq!(Status::parse("In QA"), Some(Status::InQA));
+    assert_eq!(Status::parse("in_qa"), Some(Status::InQA));
+    assert_eq!(Status::parse("in-dev"), Some(Status::InDev));
+    assert_eq!(Status::parse("3"), Some(Status::InQA));
+    assert_eq!(Status::parse("7"), Some(Status::Done));
+    assert_eq!(Status::parse("2"), None);
+    assert_eq!(Status::parse("300"), None);
+    assert_eq!(Status::parse("nope"), None);
+
+    // without discriminants the number is the ordinal
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Plain {
+        A,
+        B,
+    }
+
+    assert_eq!(Plain::parse("b"), Some(Plain::B));
+    assert_eq!(Plain::parse("0"), Some(Plain::A));
+    assert_eq!(Plain::parse("2"), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn parse_and_format_list() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open = 1,
+        InQA = 3,
+        Done = 7,
+    }
+
+    assert_eq!(
+        Status::parse_list("open, in_qa,7", ','),
+        Ok(vec![Status::Open, Status::InQA, Status::Done])
+    );
+    assert_eq!(
+        Status::parse_list("Done|In QA", '|'),
+        Ok(vec![Status::Done, Status::InQA])
+    );
+    assert_eq!(Status::parse_list(" ", ','), Ok(vec![]));
+    assert_eq!(Status::parse_list("open,closed,done", ','), Err("closed"));
+    assert_eq!(Status::parse_list("open,,done", ','), Err(""));
+
+    assert_eq!(
+        Status::format_list(&[Status::Open, Status::InQA], ','),
+        "Open,InQA"
+    );
+    assert_eq!(Status::format_list(Status::iter(), ';'), "Open;InQA;Done");
+    assert_eq!(Status::format_list(&[], ','), "");
+    let all = Status::format_list(Status::iter(), ',');
+    assert_eq!(Status::parse_list(&all, ','), Ok(Status::list().to_vec()));
+}
+
+#[test]
+fn bitmask() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Permission {
+        Read = 1,
+        Write = 2,
+        Execute = 1 << 2,
+        Admin = 0x80,
+    }
+
+    assert_eq!(Permission::Execute.to_mask(), 4);
+    assert_eq!(Permission::from_mask_single(2), Some(Permission::Write));
+    assert_eq!(Permission::from_mask_single(3), None);
+    assert_eq!(
+        Permission::variants_in_mask(0b1000_0101).collect::<Vec<_>>(),
+        vec![Permission::Read, Permission::Execute, Permission::Admin]
+    );
+    assert_eq!(Permission::variants_in_mask(0b0111_1000).count(), 0);
+    assert_eq!(
+        Permission::mask_of(&[Permission::Write, Permission::Admin]),
+        0x82
+    );
+    let mask = Permission::mask_of(Permission::variants_in_mask(0b101));
+    assert_eq!(mask, 0b101);
+    assert_eq!(Permission::mask_of(Permission::iter()), 0b1000_0111);
+}
+
+#[test]
+fn custom_order() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Priority {
+        // the discriminants are stored, so they can't change
+        #[ext(order = 2)]
+        Low = 1,
+        #[ext(order = 0)]
+        Critical = 2,
+        #[ext(order = 1)]
+        High = 3,
+        #[ext(order = 10)]
+        Someday = 4,
+    }
+
+    assert_eq!(
+        Priority::list(),
+        [
+            Priority::Critical,
+            Priority::High,
+            Priority::Low,
+            Priority::Someday
+        ]
+    );
+    assert_eq!(
+        Priority::iter().map(|x| x.as_u8()).collect::<Vec<_>>(),
+        vec![2, 3, 1, 4]
+    );
+    assert_eq!(Priority::Critical.ordinal(), 0);
+    assert_eq!(Priority::Low.ordinal(), 2);
+    assert_eq!(Priority::from_ordinal(1), Some(Priority::High));
+    assert_eq!(Priority::ref_from_ordinal(3), Some(&Priority::Someday));
+    assert_eq!(
+        Priority::range_inclusive(&Priority::High, &Priority::Someday),
+        &[Priority::High, Priority::Low, Priority::Someday]
+    );
+    assert_eq!(Priority::ENTRIES[0], ("Critical", 0, 2));
+    assert_eq!(Priority::from_u8(1), Some(Priority::Low));
+}
+
+#[test]
+fn names_and_values_statics() {
+    #[enum_extend(IntType = "i16", Ffi)]
+    #[derive(Debug, PartialEq)]
+    pub enum HttpStatus {
+        #[ext(order = 1)]
+        Ok = 200,
+        #[ext(order = 2)]
+        NotFound = 404,
+        #[ext(order = 0)]
+        Continue = 100,
+    }
+
+    assert_eq!(HTTP_STATUS_NAMES, ["Continue", "Ok", "NotFound"]);
+    assert_eq!(HTTP_STATUS_VALUES, [100, 200, 404]);
+}
+
+#[test]
+fn all_pairs_and_all_with() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Light {
+        Red,
+        Yellow,
+        Green,
+    }
+
+    let pairs = Light::all_pairs().collect::<Vec<_>>();
+    assert_eq!(pairs.len(), 9);
+    assert_eq!(pairs[0], (Light::Red, Light::Red));
+    assert_eq!(pairs[1], (Light::Red, Light::Yellow));
+    assert_eq!(pairs[8], (Light::Green, Light::Green));
+
+    let with = Light::all_with(&["on", "off"]).collect::<Vec<_>>();
+    assert_eq!(
+        with,
+        vec![
+            (Light::Red, "on"),
+            (Light::Red, "off"),
+            (Light::Yellow, "on"),
+            (Light::Yellow, "off"),
+            (Light::Green, "on"),
+            (Light::Green, "off"),
+        ]
+    );
+    assert_eq!(Light::all_with::<u8>(&[]).count(), 0);
+}
+
+#[test]
+fn next_previous_and_wrapping() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Worker {
+        A,
+        B,
+        C,
+    }
+
+    assert_eq!(Worker::A.next(), Some(&Worker::B));
+    assert_eq!(Worker::C.next(), None);
+    assert_eq!(Worker::B.previous(), Some(&Worker::A));
+    assert_eq!(Worker::A.previous(), None);
+
+    assert_eq!(Worker::A.wrapping_add(1), &Worker::B);
+    assert_eq!(Worker::B.wrapping_add(2), &Worker::A);
+    assert_eq!(Worker::C.wrapping_add(3), &Worker::C);
+    assert_eq!(Worker::A.wrapping_add(usize::MAX), &Worker::A);
+    assert_eq!(Worker::A.wrapping_sub(1), &Worker::C);
+    assert_eq!(Worker::B.wrapping_sub(5), &Worker::C);
+    assert_eq!(Worker::C.wrapping_sub(usize::MAX), &Worker::C);
+}
+
+#[test]
+fn atomic_companion() {
+    use std::sync::atomic::Ordering;
+
+    #[enum_extend(IntType = "u8", Atomic)]
+    #[derive(Debug, PartialEq)]
+    pub enum State {
+        Idle = 1,
+        Running = 5,
+        Stopped = 9,
+    }
+
+    let state = AtomicState::new(State::Idle);
+    assert_eq!(state.load(Ordering::Relaxed), State::Idle);
+    state.store(State::Running, Ordering::Relaxed);
+    assert_eq!(
+        state.swap(State::Stopped, Ordering::Relaxed),
+        State::Running
+    );
+    assert_eq!(
+        state.compare_exchange(
+            State::Idle,
+            State::Running,
+            Ordering::AcqRel,
+            Ordering::Acquire
+        ),
+        Err(State::Stopped)
+    );
+    assert_eq!(
+        state.compare_exchange(
+            State::Stopped,
+            State::Idle,
+            Ordering::AcqRel,
+            Ordering::Acquire
+        ),
+        Ok(State::Stopped)
+    );
+    assert_eq!(state.into_inner(), State::Idle);
+    assert_eq!(
+        std::mem::size_of::<AtomicState>(),
+        std::mem::size_of::<u8>()
+    );
+
+    // without discriminants the ordinal is stored
+    #[enum_extend(Atomic)]
+    #[derive(Debug, PartialEq)]
+    pub enum Plain {
+        A,
+        B,
+    }
+
+    static PLAIN: AtomicPlain = AtomicPlain::new(Plain::B);
+    assert_eq!(PLAIN.load(Ordering::SeqCst), Plain::B);
+    assert_eq!(AtomicPlain::from(Plain::A).into_inner(), Plain::A);
+}
+
+#[test]
+fn schema_hash() {
+    mod service_a {
+        use enum_ext::enum_extend;
+
+        #[enum_extend(IntType = "u8")]
+        pub enum Status {
+            Open = 1,
+            Done = 2,
+        }
+    }
+    mod service_b {
+        use enum_ext::enum_extend;
+
+        // same variants, written differently
+        #[enum_extend(IntType = "u8")]
+        #[derive(Debug)]
+        pub enum Status {
+            Open = 1,
+            Done,
+        }
+    }
+    mod renumbered {
+        use enum_ext::enum_extend;
+
+        #[enum_extend(IntType = "u8")]
+        pub enum Status {
+            Open = 1,
+            Done = 3,
+        }
+    }
+    mod renamed {
+        use enum_ext::enum_extend;
+
+        #[enum_extend(IntType = "u8")]
+        pub enum Status {
+            Open = 1,
+            Closed = 2,
+        }
+    }
+
+    assert_eq!(
+        service_a::Status::schema_hash(),
+        service_b::Status::schema_hash()
+    );
+    assert_ne!(
+        service_a::Status::schema_hash(),
+        renumbered::Status::schema_hash()
+    );
+    assert_ne!(
+        service_a::Status::schema_hash(),
+        renamed::Status::schema_hash()
+    );
+    const HASH: u64 = service_a::Status::schema_hash();
+    assert_eq!(HASH, service_a::Status::schema_hash());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn compare_schema() {
+    #[enum_extend(IntType = "u8", CompareSchema)]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open = 1,
+        InQA = 2,
+        Done = 3,
+    }
+
+    assert_eq!(Status::schema(), "Open=1;InQA=2;Done=3;");
+    assert!(Status::compare_schema(Status::schema()).is_empty());
+
+    let diff = Status::compare_schema("Open=1;Done=4;Closed=5;");
+    assert_eq!(diff.added, vec!["InQA".to_string()]);
+    assert_eq!(diff.removed, vec!["Closed".to_string()]);
+    assert_eq!(diff.renumbered, vec![("Done".to_string(), 3, 4)]);
+    assert!(!diff.is_empty());
+
+    // a discriminant that couldn't be evaluated is listed by its expression
+    let diff = Status::compare_schema("Open=1;InQA=proc_consts :: IN_QA;Done=3;");
+    assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.renumbered.is_empty());
+    assert_eq!(diff.unknown, vec!["InQA".to_string()]);
+    assert!(!diff.is_empty());
+}
+
+#[test]
+fn from_ascii_bytes() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Method {
+        Get = 1,
+        Post = 2,
+        Delete = 3,
+    }
+
+    assert_eq!(Method::from_ascii_bytes(b"Post"), Some(Method::Post));
+    assert_eq!(Method::from_ascii_bytes(b"post"), None);
+    assert_eq!(Method::from_ascii_bytes(b""), None);
+    assert_eq!(Method::from_ascii_bytes(&[0xff, 0xfe]), None);
+    let header = b"Delete /items/1";
+    assert_eq!(Method::from_ascii_bytes(&header[..6]), Some(Method::Delete));
+    const GET: Option<Method> = Method::from_ascii_bytes(b"Get");
+    assert_eq!(GET, Some(Method::Get));
+}
+
+mod status_codes {
+    pub const OPEN: i32 = 100;
+    pub const DONE: i32 = 200;
+    pub const FLAG: u8 = 7;
+}
+
+#[test]
+fn const_path_discriminants() {
+    #[enum_extend(IntType = "i32")]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open = status_codes::OPEN,
+        Pending,
+        Done = status_codes::DONE,
+        Flagged = status_codes::FLAG as i32,
+        Literal = 5,
+    }
+
+    assert_eq!(Status::from_i32(100), Some(Status::Open));
+    assert_eq!(Status::from_i32(101), Some(Status::Pending));
+    assert_eq!(Status::from_i32(200), Some(Status::Done));
+    assert_eq!(Status::from_i32(7), Some(Status::Flagged));
+    assert_eq!(Status::from_i32(5), Some(Status::Literal));
+    assert_eq!(Status::from_i32(6), None);
+    assert_eq!(Status::Done.as_i32(), 200);
+    assert_eq!(Status::try_from(101u8), Ok(Status::Pending));
+    assert_eq!(Status::parse("200"), Some(Status::Done));
+}
+
+#[test]
+fn discriminants_referencing_variants() {
+    #[enum_extend(IntType = "auto")]
+    #[derive(Debug, PartialEq)]
+    pub enum Level {
+        Low = 10,
+        Medium = Self::Low as u8 + 10,
+        High = Level::Medium as u8 * 2,
+        Higher,
+    }
+
+    assert_eq!(Level::from_u8(20), Some(Level::Medium));
+    assert_eq!(Level::from_u8(40), Some(Level::High));
+    assert_eq!(Level::from_u8(41), Some(Level::Higher));
+    assert_eq!(Level::Higher.as_u8(), 41);
+    assert_eq!(Level::schema(), "Low=10;Medium=20;High=40;Higher=41;");
+}
+
+#[test]
+fn no_auto_derives() {
+    #[enum_extend(IntType = "i32", NoAutoDerives)]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open = 10,
+        Pending,
+        Done = status_codes::DONE,
+    }
+
+    // Status isn't Clone, but the conversions still work
+    assert_eq!(Status::Open.as_i32(), 10);
+    assert_eq!(Status::Pending.as_i32(), 11);
+    assert_eq!(Status::Done.as_i32(), 200);
+    assert_eq!(Status::from_i32(11), Some(Status::Pending));
+    assert_eq!(
+        Status::Done.cmp_discriminant(&Status::Open),
+        std::cmp::Ordering::Greater
+    );
+    assert!(!Status::pretty_print().contains("Clone"));
+    const OPEN: i32 = Status::Open.as_i32();
+    assert_eq!(OPEN, 10);
+}
+
+#[test]
+fn auto_copy() {
+    #[enum_extend(IntType = "i32", AutoCopy)]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open = 10,
+        Pending,
+        Done = status_codes::DONE,
+    }
+
+    // Status is Copy, so it can be iterated by value
+    let all: Vec<Status> = Status::iter().copied().collect();
+    assert_eq!(all, vec![Status::Open, Status::Pending, Status::Done]);
+    let open = Status::Open;
+    let copied = open;
+    assert_eq!(open, copied);
+    const DONE: i32 = Status::Done.as_i32();
+    assert_eq!(DONE, 200);
+    assert!(Status::pretty_print().contains("Copy"));
+}
+
+#[test]
+fn derive_extra() {
+    use std::collections::HashSet;
+
+    #[enum_extend(DeriveExtra = "Hash, Eq, PartialEq, std::cmp::PartialOrd")]
+    #[derive(Debug, PartialEq)]
+    pub enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    let set: HashSet<Color> = [Color::Red, Color::Blue, Color::Red].into_iter().collect();
+    assert_eq!(set.len(), 2);
+    assert!(Color::Red < Color::Blue);
+    // PartialEq is already derived, so it isn't derived twice
+    assert!(Color::pretty_print().contains("#[derive(Hash, Eq, std :: cmp :: PartialOrd)]"));
+}
+
+#[test]
+fn ordinal_type() {
+    #[enum_extend(OrdinalType = "u8", Atomic)]
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Phase {
+        Init,
+        Run,
+        Stop,
+    }
+
+    let ord: u8 = Phase::Stop.ordinal();
+    assert_eq!(ord, 2);
+    assert_eq!(Phase::from_ordinal(1u8), Some(Phase::Run));
+    assert_eq!(Phase::ref_from_ordinal(3u8), None);
+    assert!(Phase::valid_ordinal(2u8));
+    assert_eq!(Phase::ENTRIES[1], ("Run", 1u8, 1usize));
+    assert_eq!(Phase::Run.next(), Some(&Phase::Stop));
+    assert_eq!(Phase::Init.previous(), None);
+    assert_eq!(Phase::Stop.wrapping_add(1), &Phase::Init);
+    // without discriminants the atomic stores the ordinal, so it's an AtomicU8
+    assert_eq!(std::mem::size_of::<AtomicPhase>(), 1);
+}
+
+#[test]
+fn empty_enum() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Never {}
+
+    assert_eq!(Never::count(), 0);
+    assert!(Never::list().is_empty());
+    assert_eq!(Never::iter().count(), 0);
+    assert_eq!(
+        Never::pretty_print(),
+        "#[derive(Debug, PartialEq)]\npub enum Never {}"
+    );
+}
+
+#[test]
+fn bits_required() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Opcode {
+        Nop = 0,
+        Load = 5,
+        Store = 6,
+    }
+
+    #[enum_extend(IntType = "i16")]
+    #[derive(Debug, PartialEq)]
+    pub enum Offset {
+        Back = -1,
+        Forward = 1,
+    }
+
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Single {
+        Only,
+    }
+
+    const OPCODE_BITS: u32 = Opcode::bits_required();
+    assert_eq!(OPCODE_BITS, 3);
+    assert_eq!(Offset::bits_required(), 16);
+    assert_eq!(Single::bits_required(), 1);
+}
+
+#[test]
+fn group_by_prefix() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Event {
+        HttpGet,
+        DbRead,
+        HttpPost,
+        DbWrite,
+        Shutdown,
+    }
+
+    let groups = Event::group_by_prefix();
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[0], ("Http", &[Event::HttpGet, Event::HttpPost][..]));
+    assert_eq!(groups[1], ("Db", &[Event::DbRead, Event::DbWrite][..]));
+    assert_eq!(groups[2], ("Shutdown", &[Event::Shutdown][..]));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn table_string() {
+    #[enum_extend(IntType = "u16")]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        /// Waiting on work
+        Open = 1,
+        InProgress = 20,
+        /// All done,
+        /// nothing left
+        Done = 300,
+    }
+
+    assert_eq!(
+        Status::table_string(),
+        "Name       | Ordinal | Value | Description\n\
+         -----------+---------+-------+-----------------------\n\
+         Open       | 0       | 1     | Waiting on work\n\
+         InProgress | 1       | 20\n\
+         Done       | 2       | 300   | All done, nothing left"
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn lenient_parsing() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open = 1,
+        InQA = 2,
+        NeedsReview = 3,
+    }
+
+    assert_eq!(
+        Status::from_pascal_spaced_lenient("  needs \t  REVIEW "),
+        Some(Status::NeedsReview)
+    );
+    assert_eq!(
+        Status::from_pascal_spaced_lenient("in qa"),
+        Some(Status::InQA)
+    );
+    assert_eq!(
+        Status::from_snake_case_lenient(" IN_QA "),
+        Some(Status::InQA)
+    );
+    assert_eq!(
+        Status::from_kebab_case_lenient("Needs-Review\n"),
+        Some(Status::NeedsReview)
+    );
+    assert_eq!(Status::from_pascal_spaced_lenient("needsreview"), None);
+}
+
+#[test]
+fn acronym_word_boundaries() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Stage {
+        QAInDev,
+        HTTPServer,
+        InQA,
+    }
+
+    assert_eq!(Stage::QAInDev.pascal_spaced(), "QA In Dev");
+    assert_eq!(Stage::HTTPServer.snake_case(), "http_server");
+    assert_eq!(Stage::QAInDev.kebab_case(), "qa-in-dev");
+    // every form round-trips
+    for stage in Stage::iter() {
+        assert_eq!(
+            Stage::from_pascal_spaced(stage.pascal_spaced()).as_ref(),
+            Some(stage)
+        );
+        assert_eq!(
+            Stage::from_snake_case(stage.snake_case()).as_ref(),
+            Some(stage)
+        );
+        assert_eq!(
+            Stage::from_kebab_case(stage.kebab_case()).as_ref(),
+            Some(stage)
+        );
+    }
+}
+
+#[test]
+fn unicode_lowercasing() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum City {
+        İstanbul,
+        ÜberLand,
+    }
+
+    // 'İ' lowercases to "i\u{307}", and both characters are kept
+    assert_eq!(City::İstanbul.snake_case(), "i\u{307}stanbul");
+    assert_eq!(City::ÜberLand.kebab_case(), "über-land");
+    assert_eq!(
+        City::from_snake_case("i\u{307}stanbul"),
+        Some(City::İstanbul)
+    );
+}
+
+#[test]
+fn ascii_case() {
+    #[enum_extend(AsciiCase)]
+    #[derive(Debug, PartialEq)]
+    pub enum City {
+        İstanbul,
+        ÜberLand,
+    }
+
+    // only ASCII letters are lowercased
+    assert_eq!(City::İstanbul.snake_case(), "İstanbul");
+    assert_eq!(City::ÜberLand.kebab_case(), "Über-land");
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(City::from_snake_case_lenient(" über_land "), None);
+        assert_eq!(
+            City::from_snake_case_lenient(" Über_LAND "),
+            Some(City::ÜberLand)
+        );
+    }
+}
+
+#[test]
+fn ordinal_cast_for_sequential_copy_enums() {
+    #[enum_extend(IntType = "u8", OrdinalType = "u8")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Sequential {
+        A = 0,
+        B = 1,
+        C,
+    }
+
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Gapped {
+        A = 0,
+        B = 5,
+    }
+
+    const C: u8 = Sequential::C.ordinal();
+    assert_eq!(C, 2);
+    assert_eq!(Sequential::B.as_u8(), 1);
+    assert_eq!(
+        Sequential::iter().map(|x| x.ordinal()).collect::<Vec<_>>(),
+        vec![0, 1, 2]
+    );
+    // not sequential, so the ordinal isn't the discriminant
+    assert_eq!(Gapped::B.ordinal(), 1);
+}
+
+#[test]
+fn from_prefix() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open = 1,
+        Opened = 2,
+        InQA = 3,
+        InProgress = 4,
+    }
+
+    assert_eq!(Status::from_prefix("in-q").ok(), Some(&Status::InQA));
+    assert_eq!(Status::from_prefix("InP").ok(), Some(&Status::InProgress));
+    // the error iterates over the variants that match
+    let candidates = Status::from_prefix("in").err().unwrap();
+    assert!(candidates.eq([&Status::InQA, &Status::InProgress]));
+    // an exact name wins over longer names it's the start of
+    assert_eq!(Status::from_prefix("open").ok(), Some(&Status::Open));
+    assert_eq!(Status::from_prefix("done").err().unwrap().count(), 0);
+}
+
+#[test]
+fn integration_cfg() {
+    // `any()` never holds, so the integration impls are always left out
+    #[enum_extend(IntType = "u8", IntegrationCfg = "any()")]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open = 1,
+        Done = 2,
+    }
+
+    assert_eq!(Status::from_u8(2), Some(Status::Done));
+}
+
+#[test]
+fn for_each_macro() {
+    #[enum_extend(IntType = "u16")]
+    #[derive(Debug, PartialEq)]
+    pub enum HttpStatus {
+        Ok = 200,
+        NotFound = 404,
+        Teapot = proc_consts::TEAPOT,
+    }
+
+    mod proc_consts {
+        pub const TEAPOT: u16 = 418;
+    }
+
+    let mut seen = Vec::new();
+    macro_rules! collect {
+        ($variant:path, $name:expr, $value:expr) => {
+            seen.push(($variant, $name, $value));
+        };
+    }
+    http_status_for_each!(collect);
+    assert_eq!(
+        seen,
+        vec![
+            (HttpStatus::Ok, "Ok", 200),
+            (HttpStatus::NotFound, "NotFound", 404),
+            (HttpStatus::Teapot, "Teapot", 418),
+        ]
+    );
+}
+
+#[test]
+fn nonzero_discriminants() {
+    use std::num::NonZeroU8;
+
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Priority {
+        Low = 1,
+        Medium,
+        High = 10,
+    }
+
+    assert_eq!(Priority::Medium.as_nonzero_u8().get(), 2);
+    assert_eq!(
+        Priority::from_nonzero_u8(NonZeroU8::new(10).unwrap()),
+        Some(Priority::High)
+    );
+    assert_eq!(Priority::from_nonzero_u8(NonZeroU8::new(3).unwrap()), None);
+    assert_eq!(
+        std::mem::size_of::<Option<Priority>>(),
+        std::mem::size_of::<Priority>()
+    );
+}
+
+#[test]
+fn closest_to() {
+    #[enum_extend(IntType = "u32")]
+    #[derive(Debug, PartialEq)]
+    pub enum Baud {
+        B9600 = 9600,
+        B19200 = 19200,
+        B115200 = 115200,
+    }
+
+    assert_eq!(Baud::closest_to(10_000), Baud::B9600);
+    assert_eq!(Baud::closest_to(0), Baud::B9600);
+    assert_eq!(Baud::closest_to(100_000), Baud::B115200);
+    // exactly between the two, so the tie rule decides
+    assert_eq!(Baud::closest_to(14_400), Baud::B9600);
+    assert_eq!(Baud::closest_to_prefer_higher(14_400), Baud::B19200);
+}
+
+#[test]
+fn from_range_value() {
+    #[enum_extend(IntType = "u16")]
+    #[derive(Debug, PartialEq)]
+    pub enum StatusClass {
+        #[ext(range = 100..200)]
+        Informational,
+        #[ext(range = 200..300)]
+        Success,
+        #[ext(range = 300..=399)]
+        Redirection,
+        #[ext(range = 400..)]
+        Error,
+    }
+
+    assert_eq!(StatusClass::from_range_value(99), None);
+    assert_eq!(
+        StatusClass::from_range_value(100),
+        Some(StatusClass::Informational)
+    );
+    assert_eq!(
+        StatusClass::from_range_value(204),
+        Some(StatusClass::Success)
+    );
+    assert_eq!(
+        StatusClass::from_range_value(399),
+        Some(StatusClass::Redirection)
+    );
+    assert_eq!(StatusClass::from_range_value(503), Some(StatusClass::Error));
+    assert_eq!(
+        StatusClass::from_range_value(u16::MAX),
+        Some(StatusClass::Error)
+    );
+}
+
+#[test]
+fn sorted_by_key() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Priority {
+        #[ext(sort_key = 2)]
+        Low = 1,
+        #[ext(sort_key = -1)]
+        Critical = 4,
+        #[ext(sort_key = 0)]
+        High = 3,
+        #[ext(sort_key = 1)]
+        Medium = 2,
+    }
+
+    assert_eq!(Priority::Critical.sort_key(), -1);
+    assert_eq!(
+        Priority::sorted_by_key(),
+        &[
+            Priority::Critical,
+            Priority::High,
+            Priority::Medium,
+            Priority::Low
+        ]
+    );
+    // the ordinals still follow the declaration order
+    assert_eq!(Priority::Critical.ordinal(), 1);
+}
+
+#[test]
+fn options_json() {
+    #[enum_extend(IntType = "u8")]
+    pub enum Quality {
+        /// Fast but blurry
+        Low = 1,
+        /// Slow but "sharp"
+        HighDef = 3,
+    }
+
+    assert_eq!(
+        Quality::options_json(),
+        r#"[{"value":1,"label":"Low","description":"Fast but blurry"},{"value":3,"label":"High Def","description":"Slow but \"sharp\""}]"#
+    );
+}
+
+#[test]
+fn flat_case() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Stage {
+        FinalCodeReview,
+        InQA,
+    }
+
+    assert_eq!(Stage::FinalCodeReview.flat_case(), "finalcodereview");
+    assert_eq!(Stage::InQA.upper_flat(), "INQA");
+    assert_eq!(Stage::from_flat_case("inqa"), Some(Stage::InQA));
+    assert_eq!(
+        Stage::from_upper_flat("FINALCODEREVIEW"),
+        Some(Stage::FinalCodeReview)
+    );
+    assert_eq!(Stage::from_flat_case("final_code_review"), None);
+}
+
+#[test]
+fn custom_case() {
+    #[enum_extend(CustomCase = "::")]
+    #[derive(Debug, PartialEq)]
+    pub enum Scope {
+        InQA,
+        UserAdmin,
+    }
+
+    assert_eq!(Scope::InQA.custom_case(), "in::qa");
+    assert_eq!(Scope::UserAdmin.custom_case(), "user::admin");
+    assert_eq!(
+        Scope::from_custom_case("user::admin"),
+        Some(Scope::UserAdmin)
+    );
+    assert_eq!(Scope::from_custom_case("user_admin"), None);
+}
+
+#[test]
+fn sentence_case() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        InQAReview,
+        Done,
+    }
+
+    assert_eq!(Status::InQAReview.sentence_case(), "In qa review");
+    assert_eq!(Status::Done.sentence_case(), "Done");
+    assert_eq!(
+        Status::from_sentence_case("In qa review"),
+        Some(Status::InQAReview)
+    );
+    assert_eq!(Status::from_sentence_case("In QA Review"), None);
+}
+
+#[test]
+fn aliases() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Color {
+        #[ext(alias = "grey", alias = "silver")]
+        Gray,
+        #[ext(alias = "crimson")]
+        Red,
+        Blue,
+    }
+
+    assert_eq!(Color::parse("grey"), Some(Color::Gray));
+    assert_eq!(Color::parse("crimson"), Some(Color::Red));
+    assert_eq!(Color::parse("Blue"), Some(Color::Blue));
+    assert_eq!(Color::Gray.aliases(), &["grey", "silver"]);
+    assert!(Color::Blue.aliases().is_empty());
+}
+
+#[test]
+fn require_sorted() {
+    // names are compared ignoring case, so Inbox comes before InQA
+    #[enum_extend(RequireSorted = "name")]
+    #[derive(Debug, PartialEq)]
+    pub enum Folder {
+        Archive,
+        Inbox,
+        InQA,
+    }
+
+    assert_eq!(
+        Folder::list(),
+        [Folder::Archive, Folder::Inbox, Folder::InQA]
+    );
+}
+
+#[test]
+fn discriminant_of_and_name_of() {
+    #[enum_extend(IntType = "i32")]
+    pub enum Code {
+        A = 10,
+        B = 20,
+        C = 30,
+    }
+
+    const SECOND: Option<i32> = Code::discriminant_of(1);
+    assert_eq!(SECOND, Some(20));
+    assert_eq!(Code::discriminant_of(3), None);
+    assert_eq!(Code::name_of(2), Some("C"));
+    assert_eq!(Code::name_of(3), None);
+}
+
+#[test]
+fn all_slice() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Side {
+        Left,
+        Right,
+    }
+
+    fn names(sides: &[Side]) -> Vec<&'static str> {
+        sides.iter().map(|x| x.variant_name()).collect()
+    }
+
+    const ALL: &[Side] = Side::all();
+    assert_eq!(ALL, &[Side::Left, Side::Right]);
+    assert_eq!(names(Side::all()), ["Left", "Right"]);
+}
+
+#[test]
+fn complex_enum_kinds() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Command {
+        Quit,
+        Move { x: i32, y: i32 },
+        Write(String),
+    }
+
+    let write = Command::Write("a".to_string());
+    assert!(write.matches_kind(&Command::Write("b".to_string())));
+    assert!(!write.matches_kind(&Command::Quit));
+    assert_eq!(Command::Move { x: 1, y: 2 }.ordinal(), 1);
+    assert_eq!(write.variant_name(), "Write");
+    assert_eq!(Command::count(), 3);
+
+    let log = [Command::Quit, Command::Move { x: 0, y: 0 }];
+    assert_eq!(Command::Move { x: 5, y: 5 }.position_in(&log), Some(1));
+    assert_eq!(write.position_in(&log), None);
+}
+
+#[test]
+fn collections() {
+    #[enum_extend(Collections)]
+    #[derive(Debug, PartialEq)]
+    pub enum Tier {
+        Free,
+        Pro,
+        Team,
+    }
+
+    let mut limits = TierMap::new([1, 10, 100]);
+    assert_eq!(limits[Tier::Pro], 10);
+    limits[Tier::Team] += 1;
+    assert_eq!(limits.get(&Tier::Team), &101);
+    assert_eq!(limits.into_values(), [1, 10, 101]);
+
+    let mut set = TierSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert(Tier::Team));
+    assert!(!set.insert(Tier::Team));
+    assert!(set.insert(Tier::Free));
+    assert!(set.contains(&Tier::Free));
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.iter().collect::<Vec<_>>(), [&Tier::Free, &Tier::Team]);
+    assert!(set.remove(&Tier::Free));
+    assert_eq!(format!("{:?}", set), r#"{"Team"}"#);
+}
+
+#[test]
+fn collection_map_api() {
+    #[enum_extend(Collections)]
+    #[derive(Debug, PartialEq)]
+    pub enum Tier {
+        Free,
+        Pro,
+        Team,
+    }
+
+    let lengths = TierMap::from_fn(|x| x.variant_name().len());
+    assert_eq!(lengths.values().copied().collect::<Vec<_>>(), [4, 3, 4]);
+    assert_eq!(
+        lengths.keys().collect::<Vec<_>>(),
+        [&Tier::Free, &Tier::Pro, &Tier::Team]
+    );
+
+    let mut seats: TierMap<u32> = TierMap::with_default();
+    for (tier, value) in seats.iter_mut() {
+        *value = tier.ordinal() as u32 * 5;
+    }
+    assert_eq!(seats.iter().last(), Some((&Tier::Team, &10)));
+
+    let map: TierMap<&str> = [(Tier::Team, "team"), (Tier::Free, "free")]
+        .into_iter()
+        .collect();
+    assert_eq!(map.into_values(), ["free", "", "team"]);
+    assert_eq!(TierMap::<u8>::default(), TierMap::new([0, 0, 0]));
+}
+
+#[test]
+fn repr_complex_enum() {
+    #[enum_extend]
+    #[repr(C, u8)]
+    #[derive(Debug)]
+    pub enum Packet {
+        Ping = 1,
+        Data(u32) = 7,
+        Ack { seq: u16 } = 200,
+    }
+
+    const ACK: u8 = Packet::Ack { seq: 3 }.as_u8();
+    assert_eq!(ACK, 200);
+    assert_eq!(Packet::Data(9).as_u8(), 7);
+    assert_eq!(Packet::Ping.as_u8(), 1);
+    assert_eq!(Packet::Data(9).ordinal(), 1);
+}
+
+#[test]
+fn repr_unit_enum() {
+    // the repr on the enum is used as the IntType
+    #[enum_extend]
+    #[repr(u16)]
+    #[derive(Debug, PartialEq)]
+    pub enum Port {
+        Http = 80,
+        Https = 443,
+    }
+
+    assert_eq!(Port::Https.as_u16(), 443);
+    assert_eq!(Port::from_u16(80), Some(Port::Http));
+}
+
+#[test]
+fn colors() {
+    #[enum_extend]
+    #[derive(Debug, PartialEq)]
+    pub enum Workflow {
+        #[ext(color = "red")]
+        Failed,
+        #[ext(alias = "ok", color = "green")]
+        Passed,
+        Skipped,
+    }
+
+    assert_eq!(Workflow::Failed.color(), Some("red"));
+    assert_eq!(Workflow::parse("ok"), Some(Workflow::Passed));
+    assert_eq!(Workflow::Passed.color(), Some("green"));
+    assert_eq!(Workflow::Skipped.color(), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn web() {
+    #[enum_extend(Web)]
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Open,
+        #[ext(alias = "qa")]
+        InQA,
+    }
+
+    assert_eq!(format!("{}", Status::InQA), "InQA");
+    assert_eq!(format!("{:>6}", Status::InQA), "  InQA");
+    assert_eq!("qa".parse::<Status>(), Ok(Status::InQA));
+    assert_eq!("in_qa".parse::<Status>(), Ok(Status::InQA));
+    let error: StatusParseError = "closed".parse::<Status>().unwrap_err();
+    assert_eq!(error.input(), "closed");
+}
+
+#[test]
+fn embedded() {
+    // an explicit IntType wins over the smallest one
+    #[enum_extend(Embedded, IntType = "i16")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Motor {
+        Reverse = -1,
+        Stop,
+        Forward,
+    }
+
+    assert_eq!(Motor::Reverse.as_i16(), -1);
+    assert_eq!(Motor::from_i16(1), Some(Motor::Forward));
+    let ordinal: u8 = Motor::Forward.ordinal();
+    assert_eq!(ordinal, 2);
+}
+
+#[test]
+fn gaps() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, PartialEq)]
+    pub enum Opcode {
+        Nop = 0,
+        Load,
+        Store,
+        Halt = 255,
+    }
+
+    assert_eq!(Opcode::gaps().len(), 1);
+    assert_eq!(Opcode::gaps()[0], 3..255);
+    // one past Halt doesn't fit a u8, so it's the first unused value
+    assert_eq!(Opcode::next_free_value(), 3);
+}
+
+#[test]
+fn versioned_codec() {
+    #[enum_extend(IntType = "u8", Versioned)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Command {
+        Start = 1,
+        Stop = 2,
+    }
+
+    // a newer definition of the same enum, with another variant
+    mod v2 {
+        use enum_ext::enum_extend;
+
+        #[enum_extend(IntType = "u8", Versioned)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Command {
+            Start = 1,
+            Stop = 2,
+            Pause = 3,
+        }
+    }
+
+    assert_ne!(Command::SCHEMA_VERSION, v2::Command::SCHEMA_VERSION);
+    let bytes = v2::Command::Stop.encode_versioned();
+    let error = Command::decode_versioned(&bytes).unwrap_err();
+    assert_eq!(
+        error,
+        CommandDecodeError::VersionMismatch {
+            expected: Command::SCHEMA_VERSION,
+            found: v2::Command::SCHEMA_VERSION,
+        }
+    );
+    assert!(error.to_string().starts_with("schema version mismatch"));
+    assert_eq!(
+        Command::decode_versioned(&Command::Stop.encode_versioned()),
+        Ok(Command::Stop)
+    );
+}
+
+#[test]
+fn proto_names() {
+    #[enum_extend(ProtoPrefix = "STATUS_")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum OrderStatus {
+        InQA,
+        Shipped,
+    }
+
+    assert_eq!(OrderStatus::InQA.proto_name(), "STATUS_IN_QA");
+    assert_eq!(
+        OrderStatus::from_proto_name("STATUS_SHIPPED"),
+        Some(OrderStatus::Shipped)
+    );
+}
+
+#[test]
+fn legacy_values() {
+    #[enum_extend(IntType = "i32")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Plan {
+        Basic = 0,
+        #[ext(legacy_value = -1)]
+        #[ext(legacy_value = 5)]
+        Premium = 1,
+    }
+
+    assert_eq!(Plan::from_i32(-1), Some(Plan::Premium));
+    assert_eq!(Plan::from(5), Plan::Premium);
+    assert_eq!(Plan::Premium.as_i32(), 1);
+}
+
+#[test]
+fn borrowed_payloads() {
+    #[enum_extend]
+    #[derive(Debug)]
+    pub enum Segment<'a, 'b: 'a> {
+        Literal(&'a str),
+        Param { name: &'b str },
+        Wildcard,
+    }
+
+    let name = String::from("id");
+    let segment = Segment::Param { name: &name };
+    assert_eq!(segment.kebab_case(), "param");
+    assert_eq!(segment.ordinal(), 1);
+    assert_eq!(
+        segment.position_in(&[Segment::Wildcard, Segment::Param { name: "x" }]),
+        Some(1)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn display_case() {
+    #[enum_extend(Display = "kebab_case", Web)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Route {
+        UserProfile,
+        Settings,
+    }
+
+    assert_eq!(Route::UserProfile.to_string(), "user-profile");
+    assert_eq!("user-profile".parse::<Route>(), Ok(Route::UserProfile));
+}
+
+#[test]
+fn try_from_error() {
+    const BASE: i16 = -2;
+
+    #[enum_extend(IntType = "i16", TryFrom)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Offset {
+        Back = BASE,
+        Still = 0,
+        Forward = 2,
+    }
+
+    assert_eq!(Offset::try_from(-2i16), Ok(Offset::Back));
+    let error = Offset::try_from(1i16).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "1 is not a valid Offset discriminant (valid discriminants are in -2..=2)"
+    );
+    // with std it's an Error
+    #[cfg(feature = "std")]
+    let _: Box<dyn std::error::Error> = error.into();
+}
+
+#[test]
+fn is_variant_predicates() {
+    #[enum_extend]
+    #[derive(Debug)]
+    pub enum Shape {
+        Circle(f64),
+        Rect { w: f64, h: f64 },
+        Empty,
+    }
+
+    assert!(Shape::Circle(1.0).is_circle());
+    assert!(Shape::Rect { w: 1.0, h: 2.0 }.is_rect());
+    assert!(!Shape::Empty.is_circle());
+    assert!(Shape::Empty.is_empty());
+}
+
+#[test]
+fn as_str_and_as_ref() {
+    #[enum_extend]
+    #[derive(Debug)]
+    pub enum Event<'a> {
+        Login { user: &'a str },
+        Logout,
+    }
+
+    let event = Event::Login { user: "ann" };
+    assert_eq!(event.as_str(), "Login");
+    let name: &str = Event::Logout.as_ref();
+    assert_eq!(name, "Logout");
+}
+
+#[test]
+fn title_case() {
+    #[enum_extend]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Report {
+        HTTPErrors,
+        Summary,
+    }
+
+    assert_eq!(Report::HTTPErrors.title_case(), "Http Errors");
+    assert_eq!(Report::from_title_case("Summary"), Some(Report::Summary));
+}
+
+#[test]
+fn dot_case() {
+    #[enum_extend(Display = "dot_case")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Metric {
+        RequestLatency,
+        HTTPErrors,
+    }
+
+    assert_eq!(Metric::HTTPErrors.dot_case(), "http.errors");
+    assert_eq!(
+        format!("app.{}", Metric::RequestLatency),
+        "app.request.latency"
+    );
+}
+
+#[test]
+fn acronyms() {
+    #[enum_extend(Acronyms = "QA,ID", Display = "title_case")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Field {
+        QAIDList,
+        UserID,
+    }
+
+    assert_eq!(Field::QAIDList.snake_case(), "qa_id_list");
+    assert_eq!(Field::UserID.to_string(), "User ID");
+    assert_eq!(Field::from_title_case("QA ID List"), Some(Field::QAIDList));
+}
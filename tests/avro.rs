@@ -0,0 +1,71 @@
+//! Run with `cargo test --features avro,serde`.
+#![cfg(all(feature = "avro", feature = "serde"))]
+#![allow(unused, dead_code)]
+use apache_avro::{from_avro_datum, to_avro_datum, types::Value, AvroSchema, Schema};
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn avro_schema() {
+    #[enum_extend(Avro)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        Done,
+    }
+
+    assert_eq!(
+        Status::avro_schema(),
+        r#"{"type":"enum","name":"Status","symbols":["Open","Done"]}"#
+    );
+    let schema = Status::get_schema();
+    assert_eq!(schema, Schema::parse_str(Status::avro_schema()).unwrap());
+}
+
+#[test]
+fn avro_round_trip() {
+    enum_ext! {
+        #[enum_def(Avro, Web)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Color {
+            Red,
+            Green,
+        }
+    }
+
+    // Web serializes the variants as their names, which is what Avro enums hold
+    let schema = Color::get_schema();
+    let value = apache_avro::to_value(Color::Green)
+        .unwrap()
+        .resolve(&schema)
+        .unwrap();
+    assert_eq!(value, Value::Enum(1, "Green".to_string()));
+    let bytes = to_avro_datum(&schema, value).unwrap();
+    assert_eq!(bytes, [2]);
+    // the datum holds the symbol, which parses back to the variant
+    match from_avro_datum(&schema, &mut bytes.as_slice(), None).unwrap() {
+        Value::Enum(_, symbol) => assert_eq!(Color::parse(&symbol), Some(Color::Green)),
+        other => panic!("expected an enum, got {:?}", other),
+    }
+}
+
+#[test]
+fn avro_serde_case() {
+    // the symbols are what serde writes
+    #[enum_extend(Avro, Serde = "snake_case")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+    }
+
+    assert_eq!(
+        Status::avro_schema(),
+        r#"{"type":"enum","name":"Status","symbols":["open","in_qa"]}"#
+    );
+    let schema = Status::get_schema();
+    let value = apache_avro::to_value(Status::InQA)
+        .unwrap()
+        .resolve(&schema)
+        .unwrap();
+    assert_eq!(value, Value::Enum(1, "in_qa".to_string()));
+}
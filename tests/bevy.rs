@@ -0,0 +1,38 @@
+//! Run with `cargo test --features bevy`.
+#![cfg(feature = "bevy")]
+#![allow(unused, dead_code)]
+use bevy_reflect::{PartialReflect, Reflect, TypePath};
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn reflect() {
+    #[enum_extend(Bevy)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+        Done,
+    }
+
+    let status = Status::InQA;
+    assert_eq!(Status::type_ident(), Some("Status"));
+    let reflected: Box<dyn Reflect> = Box::new(status);
+    assert_eq!(reflected.downcast_ref::<Status>(), Some(&Status::InQA));
+}
+
+#[test]
+fn reflect_apply() {
+    enum_ext! {
+        #[enum_def(Bevy)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Mode {
+            Idle,
+            Run,
+        }
+    }
+
+    let mut mode = Mode::Idle;
+    mode.apply(&Mode::Run);
+    assert_eq!(mode, Mode::Run);
+    assert!(mode.reflect_partial_eq(&Mode::Run).unwrap_or(false));
+}
@@ -0,0 +1,73 @@
+//! Run with `cargo test --features capnp`.
+#![cfg(feature = "capnp")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+/// What capnpc generates for `enum Color { red @0; green @1; blue @2; }`
+mod schema_capnp {
+    #[repr(u16)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Color {
+        Red = 0,
+        Green = 1,
+        Blue = 2,
+    }
+
+    impl TryFrom<u16> for Color {
+        type Error = capnp::NotInSchema;
+        fn try_from(value: u16) -> Result<Self, capnp::NotInSchema> {
+            match value {
+                0 => Ok(Self::Red),
+                1 => Ok(Self::Green),
+                2 => Ok(Self::Blue),
+                n => Err(capnp::NotInSchema(n)),
+            }
+        }
+    }
+
+    impl From<Color> for u16 {
+        fn from(x: Color) -> u16 {
+            x as u16
+        }
+    }
+}
+
+#[test]
+fn capnp_conversions() {
+    #[enum_extend(Capnp = "schema_capnp::Color")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    assert_eq!(
+        schema_capnp::Color::from(Color::Green),
+        schema_capnp::Color::Green
+    );
+    assert_eq!(Color::from(schema_capnp::Color::Blue), Color::Blue);
+}
+
+#[test]
+fn capnp_round_trip() {
+    enum_ext! {
+        #[enum_def(Capnp = "schema_capnp::Color")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+    }
+
+    // messages store the enum as its u16 ordinal in the schema
+    let wire = u16::from(schema_capnp::Color::from(Color::Blue));
+    assert_eq!(wire, 2);
+    let read = schema_capnp::Color::try_from(wire).map(Color::from);
+    assert_eq!(read, Ok(Color::Blue));
+    let error = schema_capnp::Color::try_from(5)
+        .map(Color::from)
+        .unwrap_err();
+    assert_eq!(error.0, 5);
+}
@@ -0,0 +1,126 @@
+//! Run with `cargo test --features config,serde`.
+#![cfg(all(feature = "config", feature = "serde"))]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+use serde::Deserialize;
+
+#[test]
+fn from_config_value() {
+    #[enum_extend(Config)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+        Done,
+    }
+
+    assert_eq!(Status::from_config_value(" IN_QA "), Some(Status::InQA));
+    assert_eq!(Status::from_config_value("in-qa"), Some(Status::InQA));
+    assert_eq!(Status::from_config_value("In QA"), Some(Status::InQA));
+    assert_eq!(Status::from_config_value("closed"), None);
+}
+
+#[test]
+fn deserialize_names_or_numbers() {
+    enum_ext! {
+        #[enum_def(Config, IntType = "u8")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Level {
+            Low = 1,
+            High = 5,
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Settings {
+        level: Level,
+    }
+
+    let settings: Settings = serde_json::from_str(r#"{"level":"HIGH"}"#).unwrap();
+    assert_eq!(settings.level, Level::High);
+    let settings: Settings = serde_json::from_str(r#"{"level":1}"#).unwrap();
+    assert_eq!(settings.level, Level::Low);
+    assert!(serde_json::from_str::<Settings>(r#"{"level":2}"#).is_err());
+    assert!(serde_json::from_str::<Settings>(r#"{"level":"medium"}"#).is_err());
+}
+
+#[test]
+fn deserialize_ordinals_lenient() {
+    #[enum_extend(Config, SerdeMode = "lenient")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Channel {
+        Email,
+        Sms,
+        #[ext(other)]
+        Unknown,
+    }
+
+    // without discriminants, the numbers are the ordinals
+    assert_eq!(serde_json::from_str::<Channel>("1").unwrap(), Channel::Sms);
+    assert_eq!(
+        serde_json::from_str::<Channel>(r#""fax""#).unwrap(),
+        Channel::Unknown
+    );
+    assert_eq!(
+        serde_json::from_str::<Channel>("9").unwrap(),
+        Channel::Unknown
+    );
+}
+
+#[test]
+fn config_replaces_serde_deserialize() {
+    // Serde = "int" still serializes, but the Deserialize is the Config one, which takes the names too
+    enum_ext! {
+        #[enum_def(Config, Serde = "int", IntType = "u8")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Level {
+            Low = 1,
+            High = 5,
+        }
+    }
+
+    assert_eq!(serde_json::to_string(&Level::High).unwrap(), "5");
+    assert_eq!(serde_json::from_str::<Level>("5").unwrap(), Level::High);
+    assert_eq!(
+        serde_json::from_str::<Level>(r#""low""#).unwrap(),
+        Level::Low
+    );
+
+    // the same goes for Web
+    #[enum_extend(Config, Web)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Color {
+        Red,
+        DarkGreen,
+    }
+
+    assert_eq!(
+        serde_json::to_string(&Color::DarkGreen).unwrap(),
+        r#""DarkGreen""#
+    );
+    assert_eq!(
+        serde_json::from_str::<Color>(r#""dark-green""#).unwrap(),
+        Color::DarkGreen
+    );
+    assert_eq!(
+        serde_json::from_str::<Color>("1").unwrap(),
+        Color::DarkGreen
+    );
+}
+
+#[test]
+fn config_replaces_serde_case_deserialize() {
+    #[enum_extend(Config, Serde = "snake_case")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+    }
+
+    assert_eq!(serde_json::to_string(&Status::InQA).unwrap(), r#""in_qa""#);
+    assert_eq!(
+        serde_json::from_str::<Status>(r#""In QA""#).unwrap(),
+        Status::InQA
+    );
+    assert_eq!(serde_json::from_str::<Status>("0").unwrap(), Status::Open);
+}
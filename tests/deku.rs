@@ -0,0 +1,55 @@
+//! Run with `cargo test --features deku`.
+#![cfg(feature = "deku")]
+// the binary literals are grouped by field
+#![allow(unused, dead_code, clippy::unusual_byte_groupings)]
+use deku::prelude::*;
+use enum_ext::{enum_ext, enum_extend};
+
+#[enum_extend(IntType = "u8", Deku)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Opcode {
+    Nop = 0,
+    Load = 1,
+    Store = 5,
+}
+
+enum_ext! {
+    #[enum_def(IntType = "u8", Deku)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Flag {
+        Off = 0,
+        On = 1,
+    }
+}
+
+// 3 bits for the opcode, 1 for the flag and 4 of padding
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct Instruction {
+    opcode: Opcode,
+    flag: Flag,
+    #[deku(bits = 4)]
+    operand: u8,
+}
+
+#[test]
+fn deku_round_trip() {
+    assert_eq!(Opcode::bits_required(), 3);
+    assert_eq!(Flag::bits_required(), 1);
+
+    let instruction = Instruction {
+        opcode: Opcode::Store,
+        flag: Flag::On,
+        operand: 0b1010,
+    };
+    let bytes = instruction.to_bytes().unwrap();
+    assert_eq!(bytes, [0b101_1_1010]);
+    let (_, read) = Instruction::from_bytes((&bytes, 0)).unwrap();
+    assert_eq!(read, instruction);
+}
+
+#[test]
+fn deku_unknown_discriminant() {
+    // 0b011 isn't an Opcode
+    let error = Instruction::from_bytes((&[0b011_0_0000], 0)).unwrap_err();
+    assert!(error.to_string().contains("invalid Opcode discriminant: 3"));
+}
@@ -0,0 +1,51 @@
+#![allow(unused, dead_code)]
+use enum_ext::EnumExt;
+
+#[derive(EnumExt, Debug, Clone, Copy, PartialEq)]
+#[enum_ext(IntType = "u8")]
+#[repr(u8)]
+pub enum Status {
+    Open = 1,
+    #[ext(alias = "qa")]
+    InQA = 3,
+    Done = 7,
+}
+
+#[derive(EnumExt, Debug, Clone, PartialEq)]
+pub enum Plain {
+    A,
+    BigThing,
+}
+
+#[test]
+fn test_derive_basics() {
+    assert_eq!(Plain::count(), 2);
+    assert_eq!(Plain::list(), [Plain::A, Plain::BigThing]);
+    assert_eq!(Plain::BigThing.ordinal(), 1);
+    assert_eq!(Plain::BigThing.pascal_spaced(), "Big Thing");
+    assert_eq!(
+        Plain::from_pascal_spaced("Big Thing"),
+        Some(Plain::BigThing)
+    );
+}
+
+#[test]
+fn test_derive_helper_attributes() {
+    assert_eq!(Status::from_u8(3), Some(Status::InQA));
+    assert_eq!(Status::Done.as_u8(), 7);
+    assert_eq!(Status::parse("qa"), Some(Status::InQA));
+    assert_eq!(Status::InQA.aliases(), &["qa"]);
+}
+
+#[derive(EnumExt, Debug)]
+pub enum Borrowed<'a> {
+    Text(&'a str),
+    Empty,
+}
+
+#[test]
+fn test_derive_lifetimes() {
+    let text = String::from("hi");
+    assert_eq!(Borrowed::Text(&text).variant_name(), "Text");
+    assert_eq!(Borrowed::Empty.ordinal(), 1);
+}
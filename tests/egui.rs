@@ -0,0 +1,48 @@
+//! Run with `cargo test --features egui`.
+#![cfg(feature = "egui")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn ui_select() {
+    #[enum_extend(Egui)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+        Done,
+    }
+
+    // nothing is clicked in a headless frame, so the selection stays put
+    let mut current = Status::InQA;
+    let mut changed = true;
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            changed = Status::ui_select(ui, &mut current);
+        });
+    });
+    assert!(!changed);
+    assert_eq!(current, Status::InQA);
+}
+
+#[test]
+fn ui_select_proc() {
+    enum_ext! {
+        #[enum_def(Egui, IntType = "u8")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Level {
+            Low = 1,
+            High = 4,
+        }
+    }
+
+    let mut current = Level::High;
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            Level::ui_select(ui, &mut current);
+        });
+    });
+    assert_eq!(current, Level::High);
+}
@@ -3,7 +3,7 @@ use std::process::Command;
 #[test]
 fn test_basic_usage_example() {
     let output = Command::new("cargo")
-        .args(&["test", "--example", "basic_no_disc"])
+        .args(["test", "--example", "basic_no_disc"])
         .output()
         .expect("Failed to execute example");
 
@@ -13,7 +13,7 @@ fn test_basic_usage_example() {
 #[test]
 fn test_basic_usage_example2() {
     let output = Command::new("cargo")
-        .args(&["test", "--example", "basic_disc"])
+        .args(["test", "--example", "basic_disc"])
         .output()
         .expect("Failed to execute example");
 
@@ -0,0 +1,105 @@
+#![allow(unused, dead_code)]
+use enum_ext::enum_ext_impl;
+
+mod generated {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[repr(u8)]
+    pub enum Status {
+        Open = 1,
+        InQA = 3,
+        Done = 7,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum NotClone {
+        X = 5,
+        Y = 6,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum Plain {
+        A,
+        B,
+    }
+}
+
+// IntoString needs std
+#[cfg(feature = "std")]
+enum_ext_impl! {
+    #[enum_def(IntType = "u8", IntoString)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[repr(u8)]
+    pub enum generated::Status {
+        Open = 1,
+        InQA = 3,
+        Done = 7,
+    }
+}
+
+#[cfg(not(feature = "std"))]
+enum_ext_impl! {
+    #[enum_def(IntType = "u8")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[repr(u8)]
+    pub enum generated::Status {
+        Open = 1,
+        InQA = 3,
+        Done = 7,
+    }
+}
+
+enum_ext_impl! {
+    crate::generated::Plain { A, B }
+}
+
+enum_ext_impl! {
+    #[enum_def(IntType = "i32")]
+    #[derive(Debug, PartialEq)]
+    generated::NotClone { X = 5, Y = 6 }
+}
+
+#[derive(Debug, PartialEq)]
+enum Local {
+    First,
+    SecondPlace,
+}
+
+enum_ext_impl! {
+    Local {
+        First,
+        SecondPlace,
+    }
+}
+
+#[test]
+fn impls_for_existing_enum() {
+    use generated::Status;
+
+    assert_eq!(Status::count(), 3);
+    assert_eq!(Status::InQA.ordinal(), 1);
+    assert_eq!(Status::from_u8(7), Some(Status::Done));
+    assert_eq!(Status::Done.as_u8(), 7);
+    assert_eq!(Status::try_from(2u16), Err(2));
+    assert_eq!(Status::from(3u8), Status::InQA);
+    assert_eq!(Status::parse("in_qa"), Some(Status::InQA));
+    #[cfg(feature = "std")]
+    assert_eq!(String::from(Status::Open), "Open");
+}
+
+#[test]
+fn impls_for_path_and_local_enum() {
+    use generated::Plain;
+
+    assert_eq!(Plain::list(), [Plain::A, Plain::B]);
+    assert_eq!(Plain::ref_from_ordinal(1), Some(&Plain::B));
+    assert_eq!(Local::SecondPlace.pascal_spaced(), "Second Place");
+    assert_eq!(Local::from_snake_case("first"), Some(Local::First));
+}
+
+#[test]
+fn impls_for_enum_without_clone() {
+    use generated::NotClone;
+
+    assert_eq!(NotClone::Y.as_i32(), 6);
+    assert_eq!(NotClone::from_i32(5), Some(NotClone::X));
+}
@@ -0,0 +1,107 @@
+//! Run with `cargo test --features flatbuffers`.
+#![cfg(feature = "flatbuffers")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+/// What flatc generates for `enum Color : byte { Red, Green, Blue }`
+mod schema_generated {
+    #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+    #[repr(transparent)]
+    pub struct Color(pub i8);
+
+    #[allow(non_upper_case_globals)]
+    impl Color {
+        pub const Red: Self = Self(0);
+        pub const Green: Self = Self(1);
+        pub const Blue: Self = Self(2);
+
+        pub const ENUM_MIN: i8 = 0;
+        pub const ENUM_MAX: i8 = 2;
+        pub const ENUM_VALUES: &'static [Self] = &[Self::Red, Self::Green, Self::Blue];
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for Color {
+        type Inner = Self;
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self(flatbuffers::read_scalar_at::<i8>(buf, loc))
+        }
+    }
+
+    impl flatbuffers::Push for Color {
+        type Output = Color;
+        unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+            flatbuffers::emplace_scalar::<i8>(dst, self.0);
+        }
+    }
+
+    impl flatbuffers::EndianScalar for Color {
+        type Scalar = i8;
+        fn to_little_endian(self) -> i8 {
+            self.0.to_le()
+        }
+        fn from_little_endian(v: i8) -> Self {
+            Self(i8::from_le(v))
+        }
+    }
+}
+
+#[test]
+fn flatbuffers_conversions() {
+    #[enum_extend(FlatBuffers = "schema_generated::Color")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    assert_eq!(
+        schema_generated::Color::from(Color::Blue),
+        schema_generated::Color::Blue
+    );
+    assert_eq!(
+        Color::try_from(schema_generated::Color::Green),
+        Ok(Color::Green)
+    );
+    // a buffer can hold any value, which is the error
+    assert_eq!(
+        Color::try_from(schema_generated::Color(7)),
+        Err(schema_generated::Color(7))
+    );
+}
+
+#[test]
+fn flatbuffers_round_trip() {
+    enum_ext! {
+        #[enum_def(FlatBuffers = "schema_generated::Color")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+    }
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let colors = builder.create_vector(&[
+        Color::Green.into(),
+        Color::Red.into(),
+        schema_generated::Color(9),
+    ]);
+    builder.finish_minimal(colors);
+    // the buffer is the one just built
+    let read = unsafe {
+        flatbuffers::root_unchecked::<flatbuffers::Vector<schema_generated::Color>>(
+            builder.finished_data(),
+        )
+    };
+    let read: Vec<_> = read.iter().map(Color::try_from).collect();
+    assert_eq!(
+        read,
+        [
+            Ok(Color::Green),
+            Ok(Color::Red),
+            Err(schema_generated::Color(9))
+        ]
+    );
+}
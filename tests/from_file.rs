@@ -0,0 +1,27 @@
+#![allow(unused, dead_code)]
+use enum_ext::enum_from_file;
+
+enum_from_file! {
+    #[enum_def(IntType = "u16")]
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum HttpStatus("tests/data/status_codes.csv");
+}
+
+#[test]
+fn enum_from_csv() {
+    assert_eq!(HttpStatus::count(), 4);
+    assert_eq!(
+        HttpStatus::list(),
+        [
+            HttpStatus::Ok,
+            HttpStatus::NotFound,
+            HttpStatus::TeaPot,
+            HttpStatus::InternalError
+        ]
+    );
+    assert_eq!(HttpStatus::from_u16(404), Some(HttpStatus::NotFound));
+    assert_eq!(HttpStatus::InternalError.as_u16(), 500);
+    assert_eq!(HttpStatus::TeaPot.pascal_spaced(), "Tea Pot");
+    assert_eq!(HttpStatus::parse("not_found"), Some(HttpStatus::NotFound));
+    assert!(HttpStatus::pretty_print().contains("NotFound = 404"));
+}
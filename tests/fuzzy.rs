@@ -0,0 +1,42 @@
+//! Run with `cargo test --features fuzzy`.
+#![cfg(feature = "fuzzy")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn find_closest() {
+    #[enum_extend(Fuzzy)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+        Done,
+    }
+
+    let (status, score) = Status::find_closest("in-q").unwrap();
+    assert_eq!(*status, Status::InQA);
+    assert!(score > 0.9);
+    assert_eq!(
+        Status::find_closest("DONE").map(|(x, score)| (*x, score)),
+        Some((Status::Done, 1.0))
+    );
+}
+
+#[test]
+fn search() {
+    enum_ext! {
+        #[enum_def(Fuzzy)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Command {
+            Build,
+            Bench,
+            Check,
+        }
+    }
+
+    let matches = Command::search("bu", 0.7);
+    assert_eq!(matches.first().map(|(x, _)| **x), Some(Command::Build));
+    // best first
+    assert!(matches.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    assert!(Command::search("zzz", 0.9).is_empty());
+}
@@ -0,0 +1,50 @@
+//! Run with `cargo test --features godot`. The property hints build a `GString`, which needs a running Godot engine,
+//! so only the conversions run here.
+#![cfg(feature = "godot")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+use godot::meta::{FromGodot, GodotConvert, ToGodot};
+use godot::register::property::{Export, Var};
+
+#[test]
+fn godot_discriminants() {
+    #[enum_extend(IntType = "i16", Godot)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Team {
+        Red = -1,
+        Blue = 10,
+    }
+
+    // the ints are the discriminants with an IntType
+    assert_eq!(Team::Red.to_godot(), -1);
+    assert_eq!(Team::from_godot(10), Team::Blue);
+    let error = Team::try_from_godot(5).unwrap_err().to_string();
+    assert!(error.contains("invalid Team value: 5"));
+
+    let mut team = Team::Red;
+    team.set_property(Team::Blue.get_property());
+    assert_eq!(team, Team::Blue);
+    // unknown values leave the property as it is
+    team.set_property(99);
+    assert_eq!(team, Team::Blue);
+
+    // #[export] fields need the export hint
+    fn exportable<T: Export>() {}
+    exportable::<Team>();
+}
+
+#[test]
+fn godot_ordinals() {
+    enum_ext! {
+        #[enum_def(Godot)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Weather {
+            Sunny,
+            Rainy,
+        }
+    }
+
+    // the ordinals without discriminants
+    assert_eq!(Weather::Rainy.to_godot(), 1);
+    assert_eq!(Weather::try_from_godot(0).ok(), Some(Weather::Sunny));
+}
@@ -0,0 +1,37 @@
+//! Run with `cargo test --features heapless`.
+#![cfg(feature = "heapless")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn parse_list() {
+    #[enum_extend(Heapless)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+        Done,
+    }
+
+    let list: heapless::Vec<Status, 3> = Status::parse_list("Open, InQA ,Done", ',').unwrap();
+    assert_eq!(list.as_slice(), &[Status::Open, Status::InQA, Status::Done]);
+    assert!(Status::parse_list("  ", ',').unwrap().is_empty());
+    assert_eq!(Status::parse_list("Open,Closed", ','), Err("Closed"));
+    // the list has room for count() items
+    assert_eq!(Status::parse_list("Open,Open,Open,Done", ','), Err("Done"));
+}
+
+#[test]
+fn parse_list_embedded() {
+    enum_ext! {
+        #[enum_def(Embedded, Heapless)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Mode {
+            Idle,
+            Run,
+        }
+    }
+
+    let list = Mode::parse_list("Run|Idle", '|').unwrap();
+    assert_eq!(list.as_slice(), &[Mode::Run, Mode::Idle]);
+}
@@ -0,0 +1,47 @@
+//! Run with `cargo test --features jni`. `to_jobject()` and `from_jobject()` need a JVM, so only the ordinal
+//! conversions run here.
+#![cfg(feature = "jni")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn jint_round_trip() {
+    #[enum_extend(IntType = "u8", Jni)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open = 10,
+        InQA = 20,
+        Done = 30,
+    }
+
+    // the ordinals, like Java's ordinal(), not the discriminants
+    let jint: jni::sys::jint = Status::InQA.to_jint();
+    assert_eq!(jint, 1);
+    for status in Status::iter() {
+        assert_eq!(Status::from_jint(status.to_jint()), Some(*status));
+    }
+    assert_eq!(Status::from_jint(3), None);
+    assert_eq!(Status::from_jint(-1), None);
+
+    // the conversions to Java objects take an env and a class
+    let _: fn(
+        &Status,
+        &mut jni::JNIEnv<'static>,
+        &str,
+    ) -> jni::errors::Result<jni::objects::JObject<'static>> = Status::to_jobject;
+}
+
+#[test]
+fn jint_proc() {
+    enum_ext! {
+        #[enum_def(Jni)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Color {
+            Red,
+            Green,
+        }
+    }
+
+    assert_eq!(Color::Green.to_jint(), 1);
+    assert_eq!(Color::from_jint(0), Some(Color::Red));
+}
@@ -0,0 +1,51 @@
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext_merge, enum_extend};
+
+mod billing {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum PaymentStatus {
+        Pending = 10,
+        Paid = 20,
+    }
+}
+
+#[enum_extend]
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderStatus {
+    Open,
+    InQA,
+    Shipped,
+}
+
+enum_ext_merge! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status = OrderStatus { Open, InQA, Shipped } + billing::PaymentStatus { Pending, Paid };
+}
+
+#[test]
+fn merged_variants() {
+    assert_eq!(
+        Status::list(),
+        [
+            Status::Open,
+            Status::InQA,
+            Status::Shipped,
+            Status::Pending,
+            Status::Paid
+        ]
+    );
+    assert_eq!(Status::Paid.ordinal(), 4);
+    assert_eq!(Status::parse("in_qa"), Some(Status::InQA));
+}
+
+#[test]
+fn from_each_source() {
+    assert_eq!(Status::from(OrderStatus::Shipped), Status::Shipped);
+    assert_eq!(
+        Status::from(billing::PaymentStatus::Pending),
+        Status::Pending
+    );
+    for x in OrderStatus::iter() {
+        assert_eq!(Status::from(x.clone()).variant_name(), x.variant_name());
+    }
+}
@@ -0,0 +1,39 @@
+//! Run with `cargo test --features metrics`.
+#![cfg(feature = "metrics")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn metric_label() {
+    #[enum_extend(IntType = "u8", Metrics)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open = 1,
+        InQA = 2,
+    }
+
+    assert_eq!(
+        Status::InQA.metric_label(),
+        metrics::SharedString::from("in_qa")
+    );
+    let label: metrics::SharedString = Status::Open.into();
+    assert_eq!(label.as_ref(), "open");
+
+    // the label works as a label value
+    let labels = [metrics::Label::new("status", Status::InQA.metric_label())];
+    assert_eq!(labels[0].value(), "in_qa");
+}
+
+#[test]
+fn metric_label_proc() {
+    enum_ext! {
+        #[enum_def(Metrics)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Method {
+            HttpGet,
+            HttpPost,
+        }
+    }
+
+    assert_eq!(Method::HttpPost.metric_label().as_ref(), "http_post");
+}
@@ -0,0 +1,38 @@
+//! Run with `cargo test --features otel`.
+#![cfg(feature = "otel")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn otel_key_value() {
+    #[enum_extend(Otel)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum WorkflowState {
+        Open,
+        InQA,
+    }
+
+    assert_eq!(WorkflowState::OTEL_KEY, "workflow_state");
+    let kv = WorkflowState::InQA.otel_key_value();
+    assert_eq!(kv.key.as_str(), "workflow_state");
+    assert_eq!(kv.value, opentelemetry::Value::from("in_qa"));
+    assert_eq!(WorkflowState::Open.otel_value().as_str(), "open");
+    let value: opentelemetry::Value = WorkflowState::InQA.into();
+    assert_eq!(value, WorkflowState::InQA.otel_value());
+}
+
+#[test]
+fn otel_key() {
+    enum_ext! {
+        #[enum_def(Otel, OtelKey = "workflow.state")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum State {
+            Open,
+            Done,
+        }
+    }
+
+    let kv = State::Done.otel_key_value();
+    assert_eq!(kv.key.as_str(), "workflow.state");
+    assert_eq!(kv.value.as_str(), "done");
+}
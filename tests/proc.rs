@@ -19,10 +19,8 @@ fn simple_1() {
     assert_eq!(Simple::B.ordinal(), 1);
     assert_eq!(Simple::C.ordinal(), 2);
 
-    let mut ord = 0;
-    for x in Simple::list() {
+    for (ord, x) in Simple::list().iter().enumerate() {
         assert_eq!(x.ordinal(), ord);
-        ord += 1;
     }
 
     for (i, v) in Simple::iter().enumerate() {
@@ -92,10 +90,8 @@ fn discriminant_1() {
     assert_eq!(Variant::B.ordinal(), 1);
     assert_eq!(Variant::C.ordinal(), 2);
 
-    let mut ord = 0;
-    for x in Variant::list() {
+    for (ord, x) in Variant::list().iter().enumerate() {
         assert_eq!(x.ordinal(), ord);
-        ord += 1;
     }
 
     for (i, v) in Variant::iter().enumerate() {
@@ -148,10 +144,8 @@ fn discriminant_2() {
     assert_eq!(Variant::B.ordinal(), 1);
     assert_eq!(Variant::C.ordinal(), 2);
 
-    let mut ord = 0;
-    for x in Variant::list() {
+    for (ord, x) in Variant::list().iter().enumerate() {
         assert_eq!(x.ordinal(), ord);
-        ord += 1;
     }
 
     for (i, v) in Variant::iter().enumerate() {
@@ -204,10 +198,8 @@ fn discriminant_neg1() {
     assert_eq!(Variant::B.ordinal(), 1);
     assert_eq!(Variant::C.ordinal(), 2);
 
-    let mut ord = 0;
-    for x in Variant::list() {
+    for (ord, x) in Variant::list().iter().enumerate() {
         assert_eq!(x.ordinal(), ord);
-        ord += 1;
     }
 
     for (i, v) in Variant::iter().enumerate() {
@@ -405,3 +397,1444 @@ pub enum PrettyPrint {
 }"##
     );
 }
+
+#[test]
+fn discriminant_expressions() {
+    enum_ext! {
+        #[enum_def(IntType = "i32")]
+        #[derive(Debug, PartialEq)]
+        pub enum Expressions {
+            A = 1 << 1,
+            B = 261u16 as u8 as i32,
+            C = 0x10 + 1,
+            D = -(2 * 3),
+        }
+    }
+
+    assert_eq!(Expressions::from_i32(2), Some(Expressions::A));
+    assert_eq!(Expressions::from_i32(5), Some(Expressions::B));
+    assert_eq!(Expressions::from_i32(17), Some(Expressions::C));
+    assert_eq!(Expressions::from_i32(-6), Some(Expressions::D));
+    assert_eq!(Expressions::from_i32(0), None);
+}
+
+#[test]
+fn int_type_auto() {
+    enum_ext! {
+        #[enum_def(IntType = "auto")]
+        #[derive(Debug, PartialEq)]
+        pub enum Unsigned {
+            A = 1,
+            B = 200,
+        }
+    }
+
+    enum_ext! {
+        #[enum_def(IntType = "auto")]
+        #[derive(Debug, PartialEq)]
+        pub enum Signed {
+            A = -1,
+            B = 200,
+        }
+    }
+
+    assert_eq!(std::mem::size_of::<Unsigned>(), 1);
+    assert_eq!(Unsigned::B.as_u8(), 200);
+    assert_eq!(Unsigned::from_u8(1), Some(Unsigned::A));
+
+    assert_eq!(std::mem::size_of::<Signed>(), 2);
+    assert_eq!(Signed::A.as_i16(), -1);
+    assert_eq!(Signed::from_i16(200), Some(Signed::B));
+}
+
+#[test]
+fn multiple_int_types() {
+    enum_ext! {
+        #[enum_def(IntType = "u8, u32, i64")]
+        #[derive(Debug, PartialEq)]
+        pub enum Multi {
+            A = 1,
+            B = 2,
+            C = 200,
+        }
+    }
+
+    assert_eq!(std::mem::size_of::<Multi>(), 1);
+    for x in Multi::list() {
+        assert_eq!(Multi::from_u8(x.as_u8()), Some(x.clone()));
+        assert_eq!(Multi::from_u32(x.as_u32()), Some(x.clone()));
+        assert_eq!(Multi::from_i64(x.as_i64()), Some(x.clone()));
+    }
+    assert_eq!(Multi::from(200i64), Multi::C);
+}
+
+#[test]
+fn try_from_int_widths() {
+    enum_ext! {
+        #[enum_def(IntType = "i16")]
+        #[derive(Debug, PartialEq)]
+        pub enum Widths {
+            A = -1,
+            B = 300,
+        }
+    }
+
+    assert_eq!(Widths::try_from(-1i64), Ok(Widths::A));
+    assert_eq!(Widths::try_from(300u32), Ok(Widths::B));
+    assert_eq!(Widths::try_from(65836u32), Err(65836));
+    assert_eq!(Widths::try_from(0u8), Err(0));
+}
+
+#[test]
+fn range_between_variants() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Status {
+            Open = 10,
+            InDev = 20,
+            InQA = 30,
+            Done = 40,
+        }
+    }
+
+    assert_eq!(
+        Status::range_inclusive(&Status::Open, &Status::InQA),
+        &[Status::Open, Status::InDev, Status::InQA]
+    );
+    assert_eq!(
+        Status::iter_between(&Status::InQA, &Status::InDev).count(),
+        0
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_string_and_cow() {
+    use std::borrow::Cow;
+
+    enum_ext! {
+        #[enum_def(IntoString)]
+        #[derive(Debug, PartialEq)]
+        pub enum Status {
+            Open,
+            InQA,
+        }
+    }
+
+    assert_eq!(Status::Open.variant_name(), "Open");
+    let s: String = Status::InQA.into();
+    assert_eq!(s, "InQA");
+    let cow: Cow<'static, str> = Status::Open.into();
+    assert_eq!(cow, "Open");
+}
+
+#[test]
+fn compare_by_discriminant() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Codes {
+            B = 20,
+            A = 10,
+            C = 30,
+        }
+    }
+
+    assert_eq!(
+        Codes::A.cmp_discriminant(&Codes::B),
+        std::cmp::Ordering::Less
+    );
+    assert_eq!(Codes::max_by_value(), &Codes::C);
+    assert_eq!(Codes::min_by_value(), &Codes::A);
+}
+
+#[test]
+fn entries_table() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Codes {
+            A = 10,
+            B = 20,
+        }
+    }
+
+    assert_eq!(Codes::ENTRIES, [("A", 0, 10u8), ("B", 1, 20u8)]);
+}
+
+#[test]
+fn variant_attributes_preserved() {
+    enum_ext! {
+        #[derive(Debug, Default, PartialEq)]
+        pub enum Status {
+            #[ext()]
+            Open,
+            #[default]
+            #[ext()]
+            Done,
+        }
+    }
+
+    assert_eq!(Status::default(), Status::Done);
+    assert_eq!(Status::count(), 2);
+}
+
+#[test]
+fn implied_discriminants() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Mixed {
+            A,
+            B = 10,
+            C,
+        }
+    }
+
+    assert_eq!(Mixed::from_u8(0), Some(Mixed::A));
+    assert_eq!(Mixed::from_u8(10), Some(Mixed::B));
+    assert_eq!(Mixed::from_u8(11), Some(Mixed::C));
+    assert_eq!(Mixed::from_u8(1), None);
+}
+
+#[test]
+fn cfg_attr_derives() {
+    enum_ext! {
+        #[enum_def(IntType = "i32")]
+        #[derive(Debug, PartialEq)]
+        #[cfg_attr(test, derive(Clone))]
+        pub enum Enabled {
+            A = -1,
+            B = 1,
+        }
+    }
+
+    assert_eq!(Enabled::from_ordinal(0), Some(Enabled::A));
+    assert_eq!(Enabled::from_i32(1), Some(Enabled::B));
+}
+
+#[test]
+fn char_codes() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Side {
+            #[ext(code = 'B')]
+            Buy = 1,
+            #[ext(code = 'S')]
+            Sell = 2,
+        }
+    }
+
+    assert_eq!(Side::Sell.code(), 'S');
+    assert_eq!(Side::from_code('B'), Some(Side::Buy));
+    assert_eq!(Side::from_u8(2), Some(Side::Sell));
+}
+
+#[test]
+fn snake_and_kebab_case() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Status {
+            InDev,
+            InQA,
+        }
+    }
+
+    assert_eq!(Status::InDev.snake_case(), "in_dev");
+    assert_eq!(Status::InQA.kebab_case(), "in-qa");
+    assert_eq!(Status::from_snake_case("in_qa"), Some(Status::InQA));
+    assert_eq!(Status::from_kebab_case("in-dev"), Some(Status::InDev));
+}
+
+#[test]
+fn parse_names_then_numbers() {
+    enum_ext! {
+        #[enum_def(IntType = "i32")]
+        #[derive(Debug, PartialEq)]
+        pub enum Status {
+            InDev = 1,
+            InQA = 3,
+            Done = -1,
+        }
+    }
+
+    assert_eq!(Status::parse("in_qa"), Some(Status::InQA));
+    assert_eq!(Status::parse("Done"), Some(Status::Done));
+    assert_eq!(Status::parse("3"), Some(Status::InQA));
+    assert_eq!(Status::parse("-1"), Some(Status::Done));
+    assert_eq!(Status::parse("0"), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn parse_and_format_list() {
+    enum_ext! {
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum Status {
+            Open,
+            InQA,
+            Done,
+        }
+    }
+
+    assert_eq!(
+        Status::parse_list("in-qa,done", ','),
+        Ok(vec![Status::InQA, Status::Done])
+    );
+    assert_eq!(Status::parse_list("open,5", ','), Err("5"));
+    assert_eq!(
+        Status::format_list(&[Status::Done, Status::Open], ' '),
+        "Done Open"
+    );
+}
+
+#[test]
+fn bitmask() {
+    enum_ext! {
+        #[enum_def(IntType = "u32")]
+        #[derive(Debug, PartialEq)]
+        pub enum Flag {
+            A = 1,
+            B = 2,
+            C = 4,
+        }
+    }
+
+    assert_eq!(Flag::C.to_mask(), 4u32);
+    assert_eq!(Flag::from_mask_single(1), Some(Flag::A));
+    assert_eq!(
+        Flag::variants_in_mask(6).collect::<Vec<_>>(),
+        vec![Flag::B, Flag::C]
+    );
+    assert_eq!(Flag::mask_of([Flag::A, Flag::C]), 5);
+}
+
+#[test]
+fn custom_order() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Size {
+            #[ext(order = 3)]
+            Large,
+            #[ext(order = 1)]
+            Small,
+            #[ext(order = 2)]
+            Medium,
+        }
+    }
+
+    assert_eq!(Size::list(), [Size::Small, Size::Medium, Size::Large]);
+    assert_eq!(Size::Large.ordinal(), 2);
+    assert_eq!(Size::iter().next(), Some(&Size::Small));
+}
+
+#[test]
+fn names_and_values_statics() {
+    enum_ext! {
+        #[enum_def(Ffi)]
+        #[derive(Debug, PartialEq)]
+        pub enum Color {
+            Red,
+            Green,
+        }
+    }
+
+    assert_eq!(COLOR_NAMES, ["Red", "Green"]);
+    assert_eq!(COLOR_VALUES, [0, 1]);
+}
+
+#[test]
+fn all_pairs_and_all_with() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Bit {
+            Zero = 0,
+            One = 1,
+        }
+    }
+
+    for (a, b) in Bit::all_pairs() {
+        assert_eq!(a.as_u8() ^ b.as_u8() == 1, a != b);
+    }
+    assert_eq!(
+        Bit::all_with(&[String::from("x")]).collect::<Vec<_>>(),
+        vec![
+            (Bit::Zero, String::from("x")),
+            (Bit::One, String::from("x"))
+        ]
+    );
+}
+
+#[test]
+fn next_previous_and_wrapping() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Turn {
+            #[ext(order = 1)]
+            North,
+            #[ext(order = 0)]
+            South,
+        }
+    }
+
+    assert_eq!(Turn::South.next(), Some(&Turn::North));
+    assert_eq!(Turn::North.previous(), Some(&Turn::South));
+    assert_eq!(Turn::North.wrapping_add(1), &Turn::South);
+    assert_eq!(Turn::South.wrapping_sub(3), &Turn::North);
+}
+
+#[test]
+fn atomic_companion() {
+    use std::sync::atomic::Ordering;
+
+    enum_ext! {
+        #[enum_def(IntType = "i16", Atomic)]
+        #[derive(Debug, PartialEq)]
+        pub enum Level {
+            Low = -1,
+            High = 300,
+        }
+    }
+
+    let level = AtomicLevel::new(Level::High);
+    assert_eq!(level.swap(Level::Low, Ordering::SeqCst), Level::High);
+    assert_eq!(level.load(Ordering::SeqCst), Level::Low);
+}
+
+#[test]
+fn schema_hash() {
+    enum_ext! {
+        pub enum Before {
+            A,
+            B,
+        }
+    }
+    enum_ext! {
+        pub enum After {
+            B,
+            A,
+        }
+    }
+    enum_ext! {
+        pub enum Same {
+            A,
+            B,
+        }
+    }
+
+    assert_ne!(Before::schema_hash(), After::schema_hash());
+    assert_eq!(Before::schema_hash(), Same::schema_hash());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn compare_schema() {
+    enum_ext! {
+        #[enum_def(CompareSchema)]
+        #[derive(Debug, PartialEq)]
+        pub enum Color {
+            /// The color red
+            Red,
+            Green,
+        }
+    }
+
+    assert_eq!(Color::schema(), "Red=0;Green=1;");
+    assert_eq!(
+        Color::compare_schema(Color::schema()),
+        ColorSchemaDiff::default()
+    );
+    let diff = Color::compare_schema("Green=0;Blue=1;");
+    assert_eq!(diff.added, vec!["Red".to_string()]);
+    assert_eq!(diff.removed, vec!["Blue".to_string()]);
+    assert_eq!(diff.renumbered, vec![("Green".to_string(), 1, 0)]);
+}
+
+#[test]
+fn from_ascii_bytes() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Color {
+            Red,
+            LightBlue,
+        }
+    }
+
+    assert_eq!(
+        Color::from_ascii_bytes(b"LightBlue"),
+        Some(Color::LightBlue)
+    );
+    assert_eq!(Color::from_ascii_bytes(b"Light Blue"), None);
+}
+
+const BASE: u16 = 1000;
+
+#[test]
+fn const_path_discriminants() {
+    enum_ext! {
+        #[enum_def(IntType = "u16, u32")]
+        #[derive(Debug, PartialEq)]
+        pub enum Code {
+            First = BASE,
+            Second = BASE + 1,
+            Third = self::BASE * 2,
+        }
+    }
+
+    assert_eq!(Code::from_u16(1000), Some(Code::First));
+    assert_eq!(Code::from_u16(1001), Some(Code::Second));
+    assert_eq!(Code::from_u32(2000), Some(Code::Third));
+    assert_eq!(Code::from_u16(0), None);
+}
+
+#[test]
+fn discriminants_referencing_variants() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Flag {
+            A = 1,
+            B = (Self::A as u8) << 1,
+            C = Self::B as u8 * 2,
+        }
+    }
+
+    assert_eq!(Flag::from_u8(2), Some(Flag::B));
+    assert_eq!(Flag::from_u8(4), Some(Flag::C));
+    // all powers of two, so the bitmask helpers are generated too
+    assert_eq!(Flag::mask_of([Flag::A, Flag::C]), 5);
+}
+
+#[test]
+fn no_auto_derives() {
+    enum_ext! {
+        #[enum_def(IntType = "u8", NoAutoDerives)]
+        #[derive(Debug, PartialEq)]
+        pub enum Level {
+            Low = 1,
+            High = 2,
+        }
+    }
+
+    assert_eq!(Level::High.as_u8(), 2);
+    assert_eq!(Level::from(1u8), Level::Low);
+}
+
+#[test]
+fn auto_copy() {
+    enum_ext! {
+        #[enum_def(IntType = "u8", AutoCopy)]
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum Level {
+            Low = 1,
+            High = 2,
+        }
+    }
+
+    let levels: Vec<Level> = Level::iter().copied().collect();
+    assert_eq!(levels, vec![Level::Low, Level::High]);
+    const HIGH: u8 = Level::High.as_u8();
+    assert_eq!(HIGH, 2);
+}
+
+#[test]
+fn derive_extra() {
+    enum_ext! {
+        #[enum_def(IntType = "u8", DeriveExtra = "Copy, Hash, Eq")]
+        #[derive(Debug, PartialEq)]
+        pub enum Level {
+            Low = 1,
+            High = 2,
+        }
+    }
+
+    let mut map = std::collections::HashMap::new();
+    map.insert(Level::High, "high");
+    assert_eq!(map[&Level::High], "high");
+    // Copy comes from DeriveExtra, so as_u8 is const
+    const LOW: u8 = Level::Low.as_u8();
+    assert_eq!(LOW, 1);
+}
+
+#[test]
+fn ordinal_type() {
+    enum_ext! {
+        #[enum_def(IntType = "u16", OrdinalType = "i8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Level {
+            Low = 100,
+            High = 200,
+        }
+    }
+
+    let ord: i8 = Level::High.ordinal();
+    assert_eq!(ord, 1);
+    assert_eq!(Level::from_ordinal(0), Some(Level::Low));
+    assert!(!Level::valid_ordinal(-1));
+    assert_eq!(Level::ref_from_ordinal(-1), None);
+}
+
+#[test]
+fn empty_enum() {
+    enum_ext! {
+        #[derive(Debug)]
+        pub enum Placeholder {}
+    }
+
+    assert_eq!(Placeholder::count(), 0);
+    assert_eq!(Placeholder::list().len(), 0);
+    assert!(Placeholder::iter().next().is_none());
+    assert!(Placeholder::all().is_empty());
+}
+
+#[test]
+fn bits_required() {
+    enum_ext! {
+        #[enum_def(IntType = "u16")]
+        #[derive(Debug, PartialEq)]
+        pub enum Port {
+            Http = 80,
+            Https = 443,
+        }
+    }
+
+    assert_eq!(Port::bits_required(), 9);
+}
+
+#[test]
+fn group_by_prefix() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Key {
+            ArrowUp = 1,
+            ArrowDown = 2,
+            Enter = 3,
+        }
+    }
+
+    let prefixes: Vec<&str> = Key::group_by_prefix().iter().map(|(x, _)| *x).collect();
+    assert_eq!(prefixes, vec!["Arrow", "Enter"]);
+    assert_eq!(Key::group_by_prefix()[0].1, &[Key::ArrowUp, Key::ArrowDown]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn table_string() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Dir {
+            /// Up the stairs
+            Up,
+            Down,
+        }
+    }
+
+    let table = Dir::table_string();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[2], "Up   | 0       | 0     | Up the stairs");
+    assert_eq!(lines[3], "Down | 1       | 1");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn lenient_parsing() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Fruit {
+            BloodOrange,
+            Kiwi,
+        }
+    }
+
+    assert_eq!(
+        Fruit::from_pascal_spaced_lenient(" blood  orange"),
+        Some(Fruit::BloodOrange)
+    );
+    assert_eq!(Fruit::from_snake_case_lenient("KIWI"), Some(Fruit::Kiwi));
+    assert_eq!(Fruit::from_kebab_case_lenient("blood orange"), None);
+}
+
+#[test]
+fn acronym_word_boundaries() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Proto {
+            TCPStream,
+            UDPSocket,
+        }
+    }
+
+    assert_eq!(Proto::TCPStream.pascal_spaced(), "TCP Stream");
+    assert_eq!(Proto::from_snake_case("udp_socket"), Some(Proto::UDPSocket));
+    assert_eq!(Proto::from_kebab_case("tcp-stream"), Some(Proto::TCPStream));
+}
+
+#[test]
+fn ascii_case() {
+    enum_ext! {
+        #[enum_def(AsciiCase)]
+        #[derive(Debug, PartialEq)]
+        pub enum Word {
+            ÉcoleNormale,
+            PlainText,
+        }
+    }
+
+    assert_eq!(Word::ÉcoleNormale.snake_case(), "École_normale");
+    #[cfg(feature = "std")]
+    assert_eq!(
+        Word::from_kebab_case_lenient("PLAIN-TEXT"),
+        Some(Word::PlainText)
+    );
+}
+
+#[test]
+fn ordinal_cast_for_sequential_copy_enums() {
+    enum_ext! {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Dir {
+            North,
+            East,
+            South,
+            West,
+        }
+    }
+
+    const WEST: usize = Dir::West.ordinal();
+    assert_eq!(WEST, 3);
+    assert_eq!(Dir::from_ordinal(Dir::South.ordinal()), Some(Dir::South));
+}
+
+#[test]
+fn from_prefix() {
+    enum_ext! {
+        pub enum Command {
+            Build,
+            Bench,
+            Check,
+        }
+    }
+
+    // Command doesn't derive Debug or PartialEq, so match on the result
+    assert!(matches!(Command::from_prefix("c"), Ok(Command::Check)));
+    let candidates = Command::from_prefix("b").err().unwrap();
+    assert_eq!(
+        candidates.map(|x| x.variant_name()).collect::<Vec<_>>(),
+        ["Build", "Bench"]
+    );
+    assert_eq!(Command::from_prefix("x").err().unwrap().count(), 0);
+}
+
+#[test]
+fn integration_cfg() {
+    enum_ext! {
+        #[enum_def(IntegrationCfg = "all(unix, not(miri))")]
+        #[derive(Debug, PartialEq)]
+        pub enum Level {
+            Low,
+            High,
+        }
+    }
+
+    assert_eq!(Level::count(), 2);
+}
+
+#[test]
+fn for_each_macro() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Shape {
+            Circle,
+            Square,
+        }
+    }
+
+    // generates a test-matrix style function per variant
+    macro_rules! name_len {
+        ($variant:path, $name:expr, $value:expr) => {
+            assert_eq!($variant.variant_name().len(), $name.len());
+            assert_eq!($variant.ordinal(), $value);
+        };
+    }
+    shape_for_each!(name_len);
+}
+
+#[test]
+fn nonzero_discriminants() {
+    enum_ext! {
+        #[enum_def(IntType = "i16")]
+        #[derive(Debug, PartialEq)]
+        pub enum Offset {
+            Back = -1,
+            Forward = 1,
+        }
+    }
+
+    assert_eq!(Offset::Back.as_nonzero_i16().get(), -1);
+    let table: [Option<Offset>; 2] = [Some(Offset::Forward), None];
+    assert_eq!(
+        std::mem::size_of_val(&table),
+        2 * std::mem::size_of::<Offset>()
+    );
+}
+
+#[test]
+fn closest_to() {
+    enum_ext! {
+        #[enum_def(IntType = "i8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Quality {
+            Poor = -10,
+            Fair = 0,
+            Good = 10,
+        }
+    }
+
+    assert_eq!(Quality::closest_to(-128), Quality::Poor);
+    assert_eq!(Quality::closest_to(4), Quality::Fair);
+    assert_eq!(Quality::closest_to_prefer_higher(5), Quality::Good);
+    assert_eq!(Quality::closest_to(127), Quality::Good);
+}
+
+#[test]
+fn from_range_value() {
+    enum_ext! {
+        #[enum_def(IntType = "i8")]
+        #[derive(Debug, PartialEq)]
+        pub enum Severity {
+            #[ext(range = ..0)]
+            Negative,
+            #[ext(range = 0..=10)]
+            Low,
+            #[ext(range = 11..)]
+            High,
+        }
+    }
+
+    assert_eq!(Severity::from_range_value(-128), Some(Severity::Negative));
+    assert_eq!(Severity::from_range_value(0), Some(Severity::Low));
+    assert_eq!(Severity::from_range_value(10), Some(Severity::Low));
+    assert_eq!(Severity::from_range_value(127), Some(Severity::High));
+}
+
+#[test]
+fn sorted_by_key() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Fruit {
+            #[ext(sort_key = "cherry")]
+            Cherry,
+            #[ext(sort_key = "apple")]
+            Apple,
+            #[ext(sort_key = "banana")]
+            Banana,
+        }
+    }
+
+    assert_eq!(Fruit::Banana.sort_key(), "banana");
+    assert_eq!(
+        Fruit::sorted_by_key(),
+        &[Fruit::Apple, Fruit::Banana, Fruit::Cherry]
+    );
+}
+
+#[test]
+fn options_json() {
+    enum_ext! {
+        pub enum Status {
+            /// Waiting on work
+            InProgress,
+            Done,
+        }
+    }
+
+    assert_eq!(
+        Status::options_json(),
+        r#"[{"value":"InProgress","label":"In Progress","description":"Waiting on work"},{"value":"Done","label":"Done","description":""}]"#
+    );
+}
+
+#[test]
+fn flat_case() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Stage {
+            FinalCodeReview,
+            InQA,
+        }
+    }
+
+    assert_eq!(Stage::InQA.flat_case(), "inqa");
+    assert_eq!(Stage::FinalCodeReview.upper_flat(), "FINALCODEREVIEW");
+    assert_eq!(
+        Stage::from_flat_case("finalcodereview"),
+        Some(Stage::FinalCodeReview)
+    );
+    assert_eq!(Stage::from_upper_flat("INQA"), Some(Stage::InQA));
+}
+
+#[test]
+fn custom_case() {
+    enum_ext! {
+        #[enum_def(CustomCase = " / ")]
+        #[derive(Debug, PartialEq)]
+        pub enum Scope {
+            InQA,
+            UserAdmin,
+        }
+    }
+
+    assert_eq!(Scope::UserAdmin.custom_case(), "user / admin");
+    assert_eq!(Scope::from_custom_case("in / qa"), Some(Scope::InQA));
+}
+
+#[test]
+fn sentence_case() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Status {
+            WaitingForApproval,
+            QAInDev,
+        }
+    }
+
+    assert_eq!(
+        Status::WaitingForApproval.sentence_case(),
+        "Waiting for approval"
+    );
+    assert_eq!(Status::QAInDev.sentence_case(), "QA in dev");
+    assert_eq!(
+        Status::from_sentence_case("QA in dev"),
+        Some(Status::QAInDev)
+    );
+}
+
+#[test]
+fn config_block() {
+    enum_ext! {
+        config!(IntType = "u8", AutoCopy);
+
+        #[derive(Debug, PartialEq)]
+        pub enum Light {
+            Red = 1,
+            Green = 2,
+        }
+
+        // an enum_def on the enum wins over the config block
+        #[enum_def(IntType = "i16")]
+        #[derive(Debug, PartialEq)]
+        pub enum Offset {
+            Back = -1,
+            Forward = 1,
+        }
+    }
+
+    assert_eq!(Light::Green.as_u8(), 2);
+    assert_eq!(Light::from_u8(1), Some(Light::Red));
+    let light = Light::Red;
+    let copied = light;
+    assert_eq!(light, copied);
+    assert_eq!(Offset::Back.as_i16(), -1);
+}
+
+#[test]
+fn aliases() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Region {
+            #[ext(alias = "us-east-1")]
+            UsEast,
+            #[ext(alias = "eu-west-1")]
+            #[ext(alias = "ireland")]
+            EuWest,
+        }
+    }
+
+    assert_eq!(Region::parse("us-east-1"), Some(Region::UsEast));
+    assert_eq!(Region::parse("ireland"), Some(Region::EuWest));
+    assert_eq!(Region::EuWest.aliases(), &["eu-west-1", "ireland"]);
+}
+
+#[test]
+fn require_sorted() {
+    enum_ext! {
+        #[enum_def(IntType = "i8", RequireSorted = "value")]
+        #[derive(Debug, PartialEq)]
+        pub enum Level {
+            Low = -5,
+            Mid,
+            High = 10,
+        }
+    }
+
+    assert_eq!(Level::Mid.as_i8(), -4);
+}
+
+#[test]
+fn discriminant_of_and_name_of() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        pub enum Code {
+            #[ext(order = 2)]
+            A = 1,
+            #[ext(order = 1)]
+            B = 2,
+        }
+    }
+
+    // lookups go by ordinal, not declaration order
+    assert_eq!(Code::discriminant_of(0), Some(2));
+    assert_eq!(Code::name_of(0), Some("B"));
+    assert_eq!(Code::name_of(1), Some("A"));
+}
+
+#[test]
+fn all_slice() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Side {
+            #[ext(order = 1)]
+            Left,
+            #[ext(order = 0)]
+            Right,
+        }
+    }
+
+    assert_eq!(Side::all(), &[Side::Right, Side::Left]);
+    assert_eq!(Side::all().len(), Side::count());
+}
+
+#[test]
+fn complex_enum_kinds() {
+    enum_ext! {
+        #[derive(Debug)]
+        pub enum Shape {
+            Circle(f64),
+            RightAngle { width: f64, height: f64 },
+            Point,
+        }
+    }
+
+    let circle = Shape::Circle(1.0);
+    assert!(circle.matches_kind(&Shape::Circle(2.0)));
+    assert_eq!(
+        Shape::RightAngle {
+            width: 1.0,
+            height: 2.0
+        }
+        .snake_case(),
+        "right_angle"
+    );
+    assert_eq!(Shape::Point.ordinal(), 2);
+    assert_eq!(
+        circle.position_in(&[Shape::Point, Shape::Circle(0.0)]),
+        Some(1)
+    );
+}
+
+#[test]
+fn collections() {
+    enum_ext! {
+        #[enum_def(Collections)]
+        #[derive(Debug, PartialEq)]
+        pub enum Tier {
+            #[ext(order = 1)]
+            Free,
+            #[ext(order = 0)]
+            Pro,
+        }
+    }
+
+    // the map is in ordinal order
+    let prices = TierMap::new([20, 0]);
+    assert_eq!(prices[Tier::Free], 0);
+    assert_eq!(prices[Tier::Pro], 20);
+
+    let set = {
+        let mut set = TierSet::default();
+        set.insert(Tier::Free);
+        set
+    };
+    assert!(!set.contains(&Tier::Pro));
+}
+
+#[test]
+fn collection_map_api() {
+    enum_ext! {
+        #[enum_def(Collections)]
+        #[derive(Debug, PartialEq)]
+        pub enum Light {
+            Red,
+            Green,
+        }
+    }
+
+    let mut counts = Light::list()
+        .into_iter()
+        .map(|x| (x, 1))
+        .collect::<LightMap<u32>>();
+    for value in counts.values_mut() {
+        *value += 1;
+    }
+    assert_eq!(counts[Light::Green], 2);
+    assert_eq!(LightMap::from_fn(|x| x.ordinal()).into_values(), [0, 1]);
+}
+
+#[test]
+fn repr_complex_enum() {
+    const BASE: i8 = -4;
+    enum_ext! {
+        #[enum_def(IntType = "i8")]
+        #[derive(Debug)]
+        pub enum Reading {
+            Low(f32) = BASE,
+            High { value: f32 } = 4,
+            Missing,
+        }
+    }
+
+    // Low can't be worked out at macro time, so it's read from the layout
+    assert_eq!(Reading::Low(0.5).as_i8(), -4);
+    assert_eq!(Reading::High { value: 1.0 }.as_i8(), 4);
+    assert_eq!(Reading::Missing.as_i8(), 5);
+}
+
+#[test]
+fn colors() {
+    enum_ext! {
+        #[derive(Debug, PartialEq)]
+        pub enum Workflow {
+            #[ext(color = "yellow")]
+            Pending,
+            #[ext(color = "bright_green")]
+            Done,
+            Archived,
+        }
+    }
+
+    assert_eq!(Workflow::Pending.color(), Some("yellow"));
+    assert_eq!(Workflow::Done.color(), Some("bright_green"));
+    assert_eq!(Workflow::Archived.color(), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn web() {
+    enum_ext! {
+        #[enum_def(Web)]
+        #[derive(Debug, PartialEq)]
+        pub enum Sort {
+            Newest,
+            MostViewed,
+        }
+    }
+
+    assert_eq!(Sort::MostViewed.to_string(), "MostViewed");
+    assert_eq!("most-viewed".parse::<Sort>(), Ok(Sort::MostViewed));
+    let error = "oldest".parse::<Sort>().unwrap_err();
+    assert_eq!(error.input(), "oldest");
+    assert_eq!(
+        error.to_string(),
+        "invalid Sort \"oldest\", expected one of: Newest, MostViewed"
+    );
+}
+
+#[test]
+fn embedded() {
+    enum_ext! {
+        #[enum_def(Embedded)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Led {
+            Off,
+            Blink = 200,
+        }
+    }
+
+    let value: u8 = Led::Blink.as_u8();
+    assert_eq!(value, 200);
+    let ordinal: u8 = Led::Blink.ordinal();
+    assert_eq!(ordinal, 1);
+    assert_eq!(Led::from_u8(0), Some(Led::Off));
+}
+
+#[test]
+fn gaps() {
+    enum_ext! {
+        #[enum_def(IntType = "u16")]
+        #[derive(Debug, PartialEq)]
+        pub enum Code {
+            Ok = 200,
+            Created,
+            NoContent = 204,
+            NotFound = 404,
+            Gone = 410,
+        }
+    }
+
+    assert_eq!(Code::gaps(), &[202..204, 205..404, 405..410]);
+    assert_eq!(Code::next_free_value(), 411);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn serde_mode() {
+    // without the serde or config features there's no Deserialize, but the options are still accepted
+    enum_ext! {
+        #[enum_def(Web, SerdeMode = "lenient")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Channel {
+            Email,
+            Sms,
+            #[ext(other)]
+            Unknown,
+        }
+    }
+
+    assert_eq!(Channel::parse("sms"), Some(Channel::Sms));
+    assert_eq!(Channel::Unknown.ordinal(), 2);
+}
+
+#[test]
+fn versioned_codec() {
+    enum_ext! {
+        #[enum_def(IntType = "u16", Versioned)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Command {
+            Start = 1,
+            Stop = 2,
+            Reboot = 300,
+        }
+    }
+
+    let bytes = Command::Reboot.encode_versioned();
+    assert_eq!(bytes[..4], Command::SCHEMA_VERSION.to_be_bytes());
+    assert_eq!(bytes[4..], [1, 44]);
+    assert_eq!(Command::decode_versioned(&bytes), Ok(Command::Reboot));
+    assert_eq!(
+        Command::decode_versioned(&[&bytes[..4], &[0, 9]].concat()),
+        Err(CommandDecodeError::UnknownDiscriminant(9))
+    );
+    assert_eq!(
+        Command::decode_versioned(&bytes[..2]),
+        Err(CommandDecodeError::WrongLength(2))
+    );
+}
+
+#[test]
+fn proto_names() {
+    enum_ext! {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum MyEnum {
+            InQA,
+            Done,
+        }
+    }
+
+    assert_eq!(MyEnum::InQA.proto_name(), "MY_ENUM_IN_QA");
+    assert_eq!(MyEnum::from_proto_name("MY_ENUM_DONE"), Some(MyEnum::Done));
+    assert_eq!(MyEnum::from_proto_name("DONE"), None);
+}
+
+#[test]
+fn legacy_values() {
+    enum_ext! {
+        #[enum_def(IntType = "u8")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Tier {
+            Free = 1,
+            #[ext(legacy_value = 2, legacy_value = 3)]
+            Pro = 10,
+        }
+    }
+
+    assert_eq!(Tier::from_u8(2), Some(Tier::Pro));
+    assert_eq!(Tier::from_u8(3), Some(Tier::Pro));
+    assert_eq!(Tier::from_u8(10), Some(Tier::Pro));
+    assert_eq!(Tier::try_from(3u32), Ok(Tier::Pro));
+    assert_eq!(Tier::from_u8(4), None);
+    assert_eq!(Tier::Pro.as_u8(), 10);
+}
+
+#[test]
+fn borrowed_payloads() {
+    enum_ext! {
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum Token<'a> {
+            Ident(&'a str),
+            Num(i64),
+            Punct { ch: char, text: &'a str },
+        }
+    }
+
+    let source = String::from("count");
+    let token = Token::Ident(&source);
+    assert_eq!(token.variant_name(), "Ident");
+    assert_eq!(token.ordinal(), 0);
+    assert_eq!(Token::Punct { ch: ';', text: ";" }.snake_case(), "punct");
+    assert!(token.matches_kind(&Token::Ident("other")));
+    assert_eq!(Token::count(), 3);
+}
+
+#[test]
+fn display_case() {
+    enum_ext! {
+        #[enum_def(Display = "pascal_spaced")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum TicketStatus {
+            Open,
+            InQA,
+        }
+    }
+
+    assert_eq!(TicketStatus::InQA.to_string(), "In QA");
+    assert_eq!(format!("[{:>6}]", TicketStatus::Open), "[  Open]");
+}
+
+#[test]
+fn try_from_error() {
+    enum_ext! {
+        #[enum_def(IntType = "u8", TryFrom)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Priority {
+            Low = 1,
+            Medium = 2,
+            High = 5,
+        }
+    }
+
+    fn parse(raw: u8) -> Result<Priority, PriorityTryFromError> {
+        let priority = Priority::try_from(raw)?;
+        Ok(priority)
+    }
+
+    assert_eq!(parse(5), Ok(Priority::High));
+    let error = parse(4).unwrap_err();
+    assert_eq!(error.value(), 4);
+    assert_eq!(error.valid_range(), 1..=5);
+    assert_eq!(
+        error.to_string(),
+        "4 is not a valid Priority discriminant (valid discriminants are in 1..=5)"
+    );
+    // the other integer types report the rejected value in their own width
+    let error = Priority::try_from(300u32).unwrap_err();
+    assert_eq!(error.value(), 300);
+    assert_eq!(
+        error.to_string(),
+        "300 is not a valid Priority discriminant (valid discriminants are in 1..=5)"
+    );
+    assert_eq!(Priority::try_from(4i64).map_err(|x| x.value()), Err(4i64));
+    assert_eq!(Priority::try_from(2u64), Ok(Priority::Medium));
+}
+
+#[test]
+fn is_variant_predicates() {
+    enum_ext! {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum TicketStatus {
+            Open,
+            InQA,
+        }
+    }
+
+    const OPEN: [bool; 2] = [TicketStatus::Open.is_open(), TicketStatus::InQA.is_open()];
+    assert_eq!(OPEN, [true, false]);
+    assert!(TicketStatus::InQA.is_in_qa());
+    assert!(!TicketStatus::InQA.is_open());
+
+    // a raw identifier gets its predicate without the r#
+    enum_ext! {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        #[allow(non_camel_case_types)]
+        pub enum Token {
+            r#type,
+            r#match,
+        }
+    }
+
+    assert!(Token::r#type.is_type());
+    assert!(Token::r#match.is_match());
+    assert!(!Token::r#type.is_match());
+}
+
+#[test]
+fn as_str_and_as_ref() {
+    enum_ext! {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Method {
+            Get,
+            Post,
+        }
+    }
+
+    fn header_len(value: impl AsRef<str>) -> usize {
+        value.as_ref().len()
+    }
+
+    assert_eq!(Method::Post.as_str(), "Post");
+    assert_eq!(header_len(Method::Get), 3);
+}
+
+#[test]
+fn title_case() {
+    enum_ext! {
+        #[enum_def(Display = "title_case")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum TicketStatus {
+            FinalCodeReview,
+            InQA,
+        }
+    }
+
+    assert_eq!(
+        TicketStatus::FinalCodeReview.title_case(),
+        "Final Code Review"
+    );
+    assert_eq!(TicketStatus::InQA.title_case(), "In Qa");
+    assert_eq!(TicketStatus::InQA.to_string(), "In Qa");
+    assert_eq!(
+        TicketStatus::from_title_case("In Qa"),
+        Some(TicketStatus::InQA)
+    );
+    assert_eq!(TicketStatus::from_title_case("In QA"), None);
+}
+
+#[test]
+fn dot_case() {
+    enum_ext! {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Stage {
+            FinalQA,
+            CodeReview,
+        }
+    }
+
+    assert_eq!(Stage::FinalQA.dot_case(), "final.qa");
+    assert_eq!(Stage::from_dot_case("code.review"), Some(Stage::CodeReview));
+    assert_eq!(Stage::from_dot_case("code_review"), None);
+}
+
+#[test]
+fn acronyms() {
+    enum_ext! {
+        #[enum_def(Acronyms = "HTTP,QA,ID")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Stage {
+            QAIDList,
+            HTTPServerID,
+            InQA,
+        }
+    }
+
+    assert_eq!(Stage::QAIDList.pascal_spaced(), "QA ID List");
+    assert_eq!(Stage::QAIDList.snake_case(), "qa_id_list");
+    assert_eq!(Stage::HTTPServerID.kebab_case(), "http-server-id");
+    assert_eq!(Stage::InQA.title_case(), "In QA");
+    assert_eq!(Stage::HTTPServerID.sentence_case(), "HTTP server ID");
+    assert_eq!(Stage::from_snake_case("qa_id_list"), Some(Stage::QAIDList));
+    assert!(Stage::QAIDList.is_qa_id_list());
+}
@@ -0,0 +1,49 @@
+//! Run with `cargo test --features ratatui`.
+#![cfg(feature = "ratatui")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn list_items() {
+    #[enum_extend(Ratatui)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+        Done,
+    }
+
+    let items = Status::list_items();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[1], ratatui::widgets::ListItem::new("In QA"));
+}
+
+#[test]
+fn selector() {
+    enum_ext! {
+        #[enum_def(Ratatui)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Status {
+            Open,
+            InQA,
+            Done,
+        }
+    }
+
+    let mut selector = StatusSelector::new();
+    assert_eq!(*selector.selected(), Status::Open);
+    // the selection wraps around at either end
+    selector.up();
+    assert_eq!(*selector.selected(), Status::Done);
+    selector.down();
+    assert_eq!(*selector.selected(), Status::Open);
+    selector.down();
+    assert_eq!(selector.selected_index(), 1);
+
+    selector.select(&Status::Done);
+    assert_eq!(selector.list_state().selected(), Some(2));
+    assert_eq!(
+        StatusSelector::with_selected(&Status::InQA).selected_index(),
+        1
+    );
+}
@@ -0,0 +1,76 @@
+//! Run with `cargo test --features registry`.
+#![cfg(feature = "registry")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+enum_ext::enum_ext_registry!();
+
+#[enum_extend(Registry)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Open,
+    Done,
+}
+
+enum_ext! {
+    #[enum_def(Registry, IntType = "u128")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Huge {
+        Small = 1,
+        Max = 340282366920938463463374607431768211455,
+    }
+}
+
+#[enum_extend(Registry, IntType = "i8")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Motor {
+    Reverse = -1,
+    Stop,
+    Forward,
+}
+
+// not registered
+#[enum_extend]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hidden {
+    A,
+}
+
+fn info(name: &str) -> &'static EnumExtInfo {
+    EnumExtInfo::iter().find(|x| x.name == name).unwrap()
+}
+
+#[test]
+fn registered() {
+    let mut names: Vec<_> = EnumExtInfo::iter().map(|x| x.name).collect();
+    names.sort();
+    assert_eq!(names, ["Huge", "Motor", "Status"]);
+
+    let status = info("Status");
+    assert_eq!(status.pretty_print, Status::pretty_print());
+    assert_eq!(
+        status.variants,
+        &[
+            ("Open", EnumExtValue::Unsigned(0)),
+            ("Done", EnumExtValue::Unsigned(1))
+        ]
+    );
+}
+
+#[test]
+fn discriminants() {
+    // u128 discriminants are kept whole
+    assert_eq!(
+        info("Huge").variants[1],
+        ("Max", EnumExtValue::Unsigned(u128::MAX))
+    );
+    assert_eq!(
+        info("Motor").variants,
+        &[
+            ("Reverse", EnumExtValue::Signed(-1)),
+            ("Stop", EnumExtValue::Signed(0)),
+            ("Forward", EnumExtValue::Signed(1)),
+        ]
+    );
+    assert_eq!(EnumExtValue::Signed(-1).to_string(), "-1");
+}
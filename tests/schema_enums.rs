@@ -0,0 +1,62 @@
+//! Run with `cargo test --features flatbuffers,capnp`.
+#![cfg(all(feature = "flatbuffers", feature = "capnp"))]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+/// What flatc generates for `enum Color : byte { Red, Green, Blue }`
+mod color_fb {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Color(pub i8);
+
+    #[allow(non_upper_case_globals)]
+    impl Color {
+        pub const Red: Self = Self(0);
+        pub const Green: Self = Self(1);
+        pub const Blue: Self = Self(2);
+        pub const ENUM_VALUES: [Self; 3] = [Self::Red, Self::Green, Self::Blue];
+    }
+}
+
+/// What capnpc generates for `enum Color { red @0; green @1; blue @2; }`
+mod color_capnp {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Color {
+        Red = 0,
+        Green = 1,
+        Blue = 2,
+    }
+}
+
+#[test]
+fn flatbuffers() {
+    #[enum_extend(FlatBuffers = "color_fb::Color")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    assert_eq!(color_fb::Color::from(Color::Blue), color_fb::Color::Blue);
+    assert_eq!(Color::try_from(color_fb::Color(1)), Ok(Color::Green));
+    assert_eq!(Color::try_from(color_fb::Color(9)), Err(color_fb::Color(9)));
+}
+
+#[test]
+fn capnp() {
+    enum_ext! {
+        #[enum_def(Capnp = "color_capnp::Color")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+    }
+
+    assert_eq!(
+        color_capnp::Color::from(Color::Green),
+        color_capnp::Color::Green
+    );
+    assert_eq!(Color::from(color_capnp::Color::Blue), Color::Blue);
+}
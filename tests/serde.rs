@@ -0,0 +1,254 @@
+//! Run with `cargo test --features serde`.
+#![cfg(feature = "serde")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn serde_mask() {
+    #[enum_extend(IntType = "u8")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Permission {
+        Read = 1,
+        Write = 2,
+        Execute = 4,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct File {
+        #[serde(with = "PermissionMask")]
+        permissions: Vec<Permission>,
+    }
+
+    let file = File {
+        permissions: vec![Permission::Read, Permission::Execute],
+    };
+    let json = serde_json::to_string(&file).unwrap();
+    assert_eq!(json, r#"{"permissions":5}"#);
+    assert_eq!(serde_json::from_str::<File>(&json).unwrap(), file);
+    // bits that don't belong to a variant are ignored
+    let file: File = serde_json::from_str(r#"{"permissions":10}"#).unwrap();
+    assert_eq!(file.permissions, [Permission::Write]);
+}
+
+#[test]
+fn serde_collections() {
+    enum_ext! {
+        #[enum_def(Collections)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Status {
+            Open,
+            InQA,
+            Done,
+        }
+    }
+
+    let map = StatusMap::new([1, 2, 3]);
+    let json = serde_json::to_string(&map).unwrap();
+    assert_eq!(json, r#"{"Open":1,"InQA":2,"Done":3}"#);
+    let map: StatusMap<i32> = serde_json::from_str(r#"{"Done":7,"Open":5,"InQA":6}"#).unwrap();
+    assert_eq!(map.into_values(), [5, 6, 7]);
+    // every variant needs a value
+    assert!(serde_json::from_str::<StatusMap<i32>>(r#"{"Done":7,"Open":5}"#).is_err());
+    assert!(serde_json::from_str::<StatusMap<i32>>(r#"{"Closed":1}"#).is_err());
+
+    let mut set = StatusSet::new();
+    set.insert(Status::Done);
+    set.insert(Status::Open);
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(json, r#"["Open","Done"]"#);
+    let set: StatusSet = serde_json::from_str(r#"["InQA"]"#).unwrap();
+    assert!(set.contains(&Status::InQA));
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn serde_web() {
+    enum_ext! {
+        #[enum_def(Web)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Color {
+            Red,
+            DarkGreen,
+        }
+    }
+
+    assert_eq!(
+        serde_json::to_string(&Color::DarkGreen).unwrap(),
+        r#""DarkGreen""#
+    );
+    assert_eq!(
+        serde_json::from_str::<Color>(r#""DarkGreen""#).unwrap(),
+        Color::DarkGreen
+    );
+    assert!(serde_json::from_str::<Color>(r#""Blue""#).is_err());
+    assert!(serde_json::from_str::<Color>("1").is_err());
+}
+
+#[test]
+fn serde_embedded() {
+    #[enum_extend(Embedded)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Mode {
+        Idle = 1,
+        Run = 2,
+        Fault = 200,
+    }
+
+    // the smallest IntType is u8, a single byte with postcard
+    let mut buf = [0u8; 4];
+    let bytes = postcard::to_slice(&Mode::Fault, &mut buf).unwrap();
+    assert_eq!(bytes, [200]);
+    assert_eq!(postcard::from_bytes::<Mode>(&[2]).unwrap(), Mode::Run);
+    assert!(postcard::from_bytes::<Mode>(&[3]).is_err());
+}
+
+#[test]
+fn serde_lenient() {
+    #[enum_extend(Web, SerdeMode = "lenient")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Channel {
+        Email,
+        Sms,
+        #[ext(other)]
+        Unknown,
+    }
+
+    assert_eq!(
+        serde_json::from_str::<Channel>(r#""Sms""#).unwrap(),
+        Channel::Sms
+    );
+    assert_eq!(
+        serde_json::from_str::<Channel>(r#""Fax""#).unwrap(),
+        Channel::Unknown
+    );
+
+    enum_ext! {
+        #[enum_def(Web, SerdeMode = "lenient")]
+        #[derive(Debug, Clone, Copy, PartialEq, Default)]
+        pub enum Priority {
+            #[default]
+            Low,
+            High,
+        }
+    }
+
+    // without #[ext(other)], unknown values are the Default variant
+    assert_eq!(
+        serde_json::from_str::<Priority>(r#""Urgent""#).unwrap(),
+        Priority::Low
+    );
+}
+
+#[test]
+fn serde_int() {
+    #[enum_extend(IntType = "u16", Serde = "int")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Command {
+        Start = 1,
+        Stop = 2,
+        Reboot = 300,
+    }
+
+    assert_eq!(serde_json::to_string(&Command::Reboot).unwrap(), "300");
+    assert_eq!(serde_json::from_str::<Command>("2").unwrap(), Command::Stop);
+    let error = serde_json::from_str::<Command>("3").unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("invalid Command discriminant 3"));
+
+    // postcard stores the u16 as a varint
+    let bytes = postcard::to_allocvec(&Command::Reboot).unwrap();
+    assert_eq!(bytes, [0xac, 0x02]);
+    assert_eq!(
+        postcard::from_bytes::<Command>(&bytes).unwrap(),
+        Command::Reboot
+    );
+}
+
+#[test]
+fn serde_int_derived() {
+    // the enum derives Serialize itself, so only Deserialize is generated
+    enum_ext! {
+        #[enum_def(IntType = "i8", Serde = "int")]
+        #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+        pub enum Motor {
+            Reverse = -1,
+            Stop = 0,
+            Forward = 1,
+        }
+    }
+
+    assert_eq!(
+        serde_json::to_string(&Motor::Reverse).unwrap(),
+        r#""Reverse""#
+    );
+    assert_eq!(serde_json::from_str::<Motor>("-1").unwrap(), Motor::Reverse);
+}
+
+#[test]
+fn serde_int_lenient() {
+    enum_ext! {
+        #[enum_def(IntType = "u8", Serde = "int", SerdeMode = "lenient")]
+        #[derive(Debug, Clone, Copy, PartialEq, Default)]
+        pub enum Priority {
+            #[default]
+            Low = 1,
+            High = 2,
+        }
+    }
+
+    assert_eq!(
+        serde_json::from_str::<Priority>("2").unwrap(),
+        Priority::High
+    );
+    assert_eq!(
+        serde_json::from_str::<Priority>("9").unwrap(),
+        Priority::Low
+    );
+}
+
+#[test]
+fn serde_case() {
+    #[enum_extend(Serde = "snake_case")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+        WontFix,
+    }
+
+    assert_eq!(
+        serde_json::to_string(&Status::WontFix).unwrap(),
+        r#""wont_fix""#
+    );
+    assert_eq!(
+        serde_json::from_str::<Status>(r#""in_qa""#).unwrap(),
+        Status::InQA
+    );
+    assert!(serde_json::from_str::<Status>(r#""InQA""#).is_err());
+
+    enum_ext! {
+        #[enum_def(Serde = "kebab_case")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Region {
+            UsEast,
+            EuWest,
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Server {
+        region: Region,
+    }
+
+    let json = serde_json::to_string(&Server {
+        region: Region::EuWest,
+    })
+    .unwrap();
+    assert_eq!(json, r#"{"region":"eu-west"}"#);
+    assert_eq!(
+        serde_json::from_str::<Server>(&json).unwrap().region,
+        Region::EuWest
+    );
+}
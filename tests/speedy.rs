@@ -0,0 +1,52 @@
+//! Run with `cargo test --features speedy`.
+#![cfg(feature = "speedy")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+use speedy::{Endianness, Readable, Writable};
+
+#[test]
+fn speedy_round_trip() {
+    #[enum_extend(IntType = "u16", Speedy)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum HttpStatus {
+        Ok = 200,
+        NotFound = 404,
+    }
+
+    let bytes = HttpStatus::NotFound
+        .write_to_vec_with_ctx(Endianness::LittleEndian)
+        .unwrap();
+    assert_eq!(bytes, 404u16.to_le_bytes());
+    let read = HttpStatus::read_from_buffer_with_ctx(Endianness::LittleEndian, &bytes).unwrap();
+    assert_eq!(read, HttpStatus::NotFound);
+
+    // an unknown discriminant is an error
+    let bytes = 500u16.to_le_bytes();
+    assert!(HttpStatus::read_from_buffer_with_ctx(Endianness::LittleEndian, &bytes).is_err());
+}
+
+#[test]
+fn speedy_field() {
+    enum_ext! {
+        #[enum_def(IntType = "u8", Speedy)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Color {
+            Red = 1,
+            Green = 2,
+        }
+    }
+
+    #[derive(Debug, PartialEq, Readable, Writable)]
+    struct Pixel {
+        color: Color,
+        alpha: u8,
+    }
+
+    let pixel = Pixel {
+        color: Color::Green,
+        alpha: 255,
+    };
+    let bytes = pixel.write_to_vec().unwrap();
+    assert_eq!(bytes, [2, 255]);
+    assert_eq!(Pixel::read_from_buffer(&bytes).unwrap(), pixel);
+}
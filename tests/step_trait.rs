@@ -0,0 +1,54 @@
+//! `core::iter::Step` is nightly only, run with `cargo +nightly test --features step_trait`.
+#![cfg(feature = "step_trait")]
+#![feature(step_trait)]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+
+#[test]
+fn step_range() {
+    #[enum_extend]
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+    pub enum Status {
+        Open,
+        InDev,
+        InQA,
+        Done,
+    }
+
+    let all: Vec<Status> = (Status::Open..=Status::Done).collect();
+    assert_eq!(all, Status::list());
+
+    let middle: Vec<Status> = (Status::InDev..Status::Done).collect();
+    assert_eq!(middle, [Status::InDev, Status::InQA]);
+
+    assert_eq!((Status::Done..Status::Open).count(), 0);
+}
+
+#[test]
+fn step_range_proc() {
+    enum_ext! {
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        pub enum Status {
+            Open,
+            InDev,
+            InQA,
+            Done,
+        }
+    }
+
+    let rev: Vec<Status> = (Status::InDev..=Status::Done).rev().collect();
+    assert_eq!(rev, [Status::Done, Status::InQA, Status::InDev]);
+}
+
+#[test]
+fn step_range_cfg_attr() {
+    #[enum_extend]
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(test, derive(Copy, PartialOrd))]
+    pub enum Status {
+        Open,
+        Done,
+    }
+
+    assert_eq!((Status::Open..=Status::Done).count(), 2);
+}
@@ -0,0 +1,88 @@
+//! Run with `cargo test --features ts`, and `--features ts,serde` for the `Serde` cases.
+#![cfg(feature = "ts")]
+#![allow(unused, dead_code)]
+use enum_ext::{enum_ext, enum_extend};
+use ts_rs::TS;
+
+#[test]
+fn ts_declaration() {
+    #[enum_extend(TypeScript)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+    }
+
+    assert_eq!(
+        Status::ts_declaration(),
+        r#"type Status = "Open" | "InQA";"#
+    );
+    assert_eq!(Status::name(), "Status");
+    assert_eq!(Status::decl(), Status::ts_declaration());
+    assert_eq!(Status::inline(), r#""Open" | "InQA""#);
+}
+
+#[test]
+fn ts_field() {
+    enum_ext! {
+        #[enum_def(IntType = "u8", TypeScript)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Color {
+            Red = 1,
+            Green = 2,
+        }
+    }
+
+    // the enum can be a field of a struct deriving TS, which refers to it by name
+    #[derive(TS)]
+    struct Paint {
+        color: Color,
+    }
+
+    assert_eq!(Paint::inline(), "{ color: Color, }");
+    assert_eq!(Color::inline(), r#""Red" | "Green""#);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn ts_serde_case() {
+    // the union holds what serde writes
+    #[enum_extend(TypeScript, Serde = "snake_case")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Status {
+        Open,
+        InQA,
+    }
+
+    assert_eq!(
+        Status::ts_declaration(),
+        r#"type Status = "open" | "in_qa";"#
+    );
+    assert_eq!(serde_json::to_string(&Status::InQA).unwrap(), r#""in_qa""#);
+
+    enum_ext! {
+        #[enum_def(TypeScript, Serde = "kebab_case")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Region {
+            UsEast,
+            EuWest,
+        }
+    }
+
+    assert_eq!(Region::inline(), r#""us-east" | "eu-west""#);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn ts_serde_int() {
+    #[enum_extend(TypeScript, IntType = "i16", Serde = "int")]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Motor {
+        Reverse = -1,
+        Stop = 0,
+        Forward = 300,
+    }
+
+    assert_eq!(Motor::ts_declaration(), "type Motor = -1 | 0 | 300;");
+    assert_eq!(serde_json::to_string(&Motor::Forward).unwrap(), "300");
+}